@@ -0,0 +1,62 @@
+//! Compares the cached regular expression validators with compiling the
+//! pattern on every call, as the validators did before the cache.
+//!
+//! Run with `cargo bench --bench validation`.
+
+use form_builder::{Patterns, ValidationMethods, Validator};
+use regex::Regex;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROWS: usize = 20_000;
+
+/// Compiling a pattern takes about a millisecond, so the uncached run uses fewer rows.
+const UNCACHED_ROWS: usize = 500;
+
+/// Runs `f` for every row, prints the time per call and returns it.
+fn measure(name: &str, rows: &[String], f: impl Fn(&str) -> bool) -> Duration {
+    let start = Instant::now();
+    for row in rows {
+        black_box(f(black_box(row)));
+    }
+    let per_call = start.elapsed() / rows.len() as u32;
+    println!("{:<32} {:>10.2?} per call", name, per_call);
+    per_call
+}
+
+fn main() {
+    let rows: Vec<String> = (0..ROWS)
+        .map(|i| format!("user{}@example{}.com", i, i % 10))
+        .collect();
+
+    let uncached = measure(
+        "email, compiled per call",
+        &rows[..UNCACHED_ROWS],
+        |value| {
+            Regex::new(r"^[\w\.-]+@[\w\.-]+\.\w+$")
+                .unwrap()
+                .is_match(value)
+        },
+    );
+    let cached = measure("email, cached", &rows, ValidationMethods::validate_email);
+
+    let validator = Validator::new(vec![
+        (ValidationMethods::not_empty, None),
+        (ValidationMethods::validate_email, None),
+    ]);
+    measure("Validator, cached", &rows, |value| {
+        validator.validate(value).is_ok()
+    });
+
+    let patterns = Patterns::new()
+        .register("email_domain", r"@example\d\.com$")
+        .unwrap();
+    measure("named pattern, cached", &rows, |value| {
+        patterns.matches("email_domain", value).unwrap()
+    });
+
+    println!(
+        "speedup of the cache: {:.0}x",
+        uncached.as_secs_f64() / cached.as_secs_f64()
+    );
+}
@@ -0,0 +1,349 @@
+//! Derive macros for the `form_builder` crate.
+//!
+//! These macros are re-exported by `form_builder` when its `derive` feature is
+//! enabled, and should be used through that crate.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use form_builder::{Form, FormModel, FormOptions};
+//!
+//! #[derive(Debug, Clone, PartialEq, FormOptions)]
+//! enum Plan {
+//!     #[form(label = "Free plan")]
+//!     Free,
+//!     #[form(label = "Pro plan")]
+//!     Pro,
+//! }
+//!
+//! #[derive(Form)]
+//! struct Signup {
+//!     #[form(prompt = "Email:", validate = "not_empty, email")]
+//!     email: String,
+//!     #[form(prompt = "Age (optional):")]
+//!     age: Option<u32>,
+//!     #[form(prompt = "Plan")]
+//!     plan: Plan,
+//! }
+//!
+//! let signup = Signup::fill()?;
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Fields, Ident, LitInt, LitStr, Path, parse_macro_input, spanned::Spanned,
+};
+
+/// Short names accepted by `validate`, mapped to `ValidationMethods` functions.
+///
+/// Any other name is used as the name of a `ValidationMethods` function as is.
+const BUILTIN_VALIDATORS: &[(&str, &str)] = &[
+    ("name", "validate_name"),
+    ("email", "validate_email"),
+    ("not_empty", "not_empty"),
+    ("alpha", "is_alpha"),
+    ("integer", "is_integer"),
+    ("float", "is_float"),
+    ("date", "is_date"),
+    ("time", "is_time"),
+    ("url", "is_url"),
+    ("phone", "is_phone_number"),
+    ("postal_code", "is_postal_code"),
+    ("credit_card", "is_credit_card"),
+    ("uuid", "is_uuid"),
+    ("ipv4", "is_ipv4"),
+    ("ipv6", "is_ipv6"),
+    ("ip", "is_ip_address"),
+    ("cidr", "is_cidr"),
+    ("hostname", "is_hostname"),
+    ("domain", "is_domain"),
+    ("iban", "is_iban"),
+    ("isbn", "is_isbn"),
+    ("semver", "is_semver"),
+    ("hex_color", "is_hex_color"),
+    ("slug", "is_slug"),
+    ("ascii", "is_ascii"),
+    ("alphanumeric", "is_alphanumeric"),
+    ("base64", "is_base64"),
+    ("json", "is_json"),
+    ("duration", "is_iso8601_duration"),
+    ("lat_long", "is_lat_long"),
+];
+
+/// Derives `FormModel` for a struct with named fields.
+///
+/// Every struct field becomes a form field added with its `FormField`
+/// implementation. Fields accept a `#[form(...)]` attribute with the
+/// following keys:
+///
+/// * `name = "..."` - The name of the form field, defaulting to the struct field name.
+/// * `prompt = "..."` - The prompt displayed to the user.
+/// * `validate = "..."` - A comma-separated list of `ValidationMethods` checks,
+///   either by short name (`email`, `not_empty`, ...) or by function name.
+/// * `validate_with = "..."` - A path to a custom `fn(&str) -> bool` check; may be repeated.
+/// * `pattern = "..."` - A regular expression the value must match, checked
+///   when the macro expands and added with `Validator::regex`; may be repeated.
+/// * `message = "..."` - The error message used for failed checks.
+/// * `limit = N` - The maximum number of selections of a multiselect field.
+#[proc_macro_derive(Form, attributes(form))]
+pub fn derive_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_form(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `FormOptions` and `FormField` for a unit-only enum.
+///
+/// Each variant becomes an option of a select field, labelled with its name
+/// or with the `#[form(label = "...")]` attribute.
+#[proc_macro_derive(FormOptions, attributes(form))]
+pub fn derive_form_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_form_options(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The settings read from the `#[form(...)]` attribute of a struct field.
+#[derive(Default)]
+struct FieldAttributes {
+    /// The name of the form field.
+    name: Option<LitStr>,
+    /// The prompt displayed to the user.
+    prompt: Option<LitStr>,
+    /// The `ValidationMethods` functions checking the input.
+    validate: Vec<Path>,
+    /// The regular expressions the value must match.
+    patterns: Vec<LitStr>,
+    /// The error message used for failed checks.
+    message: Option<LitStr>,
+    /// The maximum number of selections.
+    limit: Option<LitInt>,
+}
+
+impl FieldAttributes {
+    /// Reads the `#[form(...)]` attributes of a struct field.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FieldAttributes)` with the settings found.
+    /// * `Err(syn::Error)` if an attribute is malformed or unknown.
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("form")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    result.name = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("prompt") {
+                    result.prompt = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("validate") {
+                    let names: LitStr = meta.value()?.parse()?;
+                    for name in names.value().split(',').map(str::trim) {
+                        result.validate.push(builtin_validator(name, names.span())?);
+                    }
+                } else if meta.path.is_ident("validate_with") {
+                    let path: LitStr = meta.value()?.parse()?;
+                    result.validate.push(path.parse()?);
+                } else if meta.path.is_ident("pattern") {
+                    let pattern: LitStr = meta.value()?.parse()?;
+                    if let Err(err) = regex::Regex::new(&pattern.value()) {
+                        return Err(syn::Error::new(pattern.span(), err));
+                    }
+                    result.patterns.push(pattern);
+                } else if meta.path.is_ident("message") {
+                    result.message = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("limit") {
+                    result.limit = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unknown form attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(result)
+    }
+}
+
+/// Resolves a name used in `validate` to a `ValidationMethods` function path.
+///
+/// # Returns
+///
+/// * `Ok(Path)` pointing at the validation function.
+/// * `Err(syn::Error)` if the name is not a valid identifier.
+fn builtin_validator(name: &str, span: Span) -> syn::Result<Path> {
+    let function = BUILTIN_VALIDATORS
+        .iter()
+        .find(|(short, _)| *short == name)
+        .map_or(name, |(_, function)| function);
+    let ident = syn::parse_str::<Ident>(function)
+        .map_err(|_| syn::Error::new(span, format!("unknown validator `{}`", name)))?;
+    Ok(syn::parse_quote!(::form_builder::ValidationMethods::#ident))
+}
+
+/// Generates the `FormModel` implementation for a struct.
+fn expand_form(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "Form can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "Form can only be derived for structs",
+            ));
+        }
+    };
+
+    let mut add_fields = Vec::new();
+    let mut read_fields = Vec::new();
+    for field in fields {
+        let field_ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let attrs = FieldAttributes::parse(&field.attrs)?;
+
+        let name = attrs
+            .name
+            .map(|name| name.value())
+            .unwrap_or_else(|| field_ident.to_string());
+        let prompt = attrs
+            .prompt
+            .map(|prompt| prompt.value())
+            .unwrap_or_else(|| format!("Enter {}:", name.replace('_', " ")));
+        let message = match attrs.message {
+            Some(message) => quote!(::std::option::Option::Some(#message)),
+            None => quote!(::std::option::Option::None),
+        };
+        let validator = if attrs.validate.is_empty() && attrs.patterns.is_empty() {
+            quote!(::std::option::Option::None)
+        } else {
+            let checks = attrs.validate.iter();
+            let patterns = attrs.patterns.iter();
+            quote! {
+                ::std::option::Option::Some(::form_builder::Validator::new(vec![
+                    #((#checks as fn(&str) -> bool, #message)),*
+                ])#(.regex(#patterns, #message).expect("checked by the Form derive"))*)
+            }
+        };
+        let limit = match attrs.limit {
+            Some(limit) => quote!(::std::option::Option::Some(#limit)),
+            None => quote!(::std::option::Option::None),
+        };
+
+        add_fields.push(quote! {
+            let builder = <#ty as ::form_builder::FormField>::add_to(
+                builder, #name, #prompt, #validator, #limit,
+            );
+        });
+        read_fields.push(quote! {
+            #field_ident: <#ty as ::form_builder::FormField>::read_from(form, #name)?
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::form_builder::FormModel for #ident #ty_generics #where_clause {
+            fn form_builder() -> ::form_builder::FormBuilder {
+                let builder = ::form_builder::FormBuilder::new();
+                #(#add_fields)*
+                builder
+            }
+
+            fn from_form(
+                form: &::form_builder::form::Form,
+            ) -> ::std::result::Result<Self, ::std::string::String> {
+                ::std::result::Result::Ok(Self {
+                    #(#read_fields),*
+                })
+            }
+        }
+    })
+}
+
+/// Reads the label of an enum variant from its `#[form(label = "...")]` attribute.
+///
+/// # Returns
+///
+/// * `Ok(Some(String))` if a label is set, `Ok(None)` otherwise.
+/// * `Err(syn::Error)` if an attribute is malformed or unknown.
+fn variant_label(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    let mut label = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("form")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                let value: LitStr = meta.value()?.parse()?;
+                label = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("unknown form attribute"))
+            }
+        })?;
+    }
+    Ok(label)
+}
+
+/// Generates the `FormOptions` and `FormField` implementations for an enum.
+fn expand_form_options(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "FormOptions can only be derived for enums",
+            ));
+        }
+    };
+
+    let mut options = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.span(),
+                "FormOptions can only be derived for enums with unit variants",
+            ));
+        }
+        let variant_ident = &variant.ident;
+        let label = variant_label(&variant.attrs)?.unwrap_or_else(|| variant_ident.to_string());
+        options.push(quote!((#ident::#variant_ident, #label)));
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::form_builder::FormOptions for #ident #ty_generics #where_clause {
+            fn options() -> ::std::vec::Vec<(Self, &'static str)> {
+                vec![#(#options),*]
+            }
+        }
+
+        impl #impl_generics ::form_builder::FormField for #ident #ty_generics #where_clause {
+            fn add_to(
+                builder: ::form_builder::FormBuilder,
+                name: &str,
+                prompt: &str,
+                _validator: ::std::option::Option<::form_builder::Validator>,
+                _limit: ::std::option::Option<usize>,
+            ) -> ::form_builder::FormBuilder {
+                ::form_builder::model::add_select_field::<Self>(builder, name, prompt)
+            }
+
+            fn read_from(
+                form: &::form_builder::form::Form,
+                name: &str,
+            ) -> ::std::result::Result<Self, ::std::string::String> {
+                ::form_builder::model::read_select_field::<Self>(form, name)
+            }
+        }
+    })
+}
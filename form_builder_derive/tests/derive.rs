@@ -0,0 +1,193 @@
+use form_builder::field::Field;
+use form_builder::form::Form;
+use form_builder::multiselect_field::MultiselectField;
+use form_builder::select_field::SelectField;
+use form_builder::{Form, FormModel, FormOptions};
+
+#[derive(Debug, Clone, PartialEq, FormOptions)]
+enum Plan {
+    #[form(label = "Free plan")]
+    Free,
+    #[form(label = "Pro plan")]
+    Pro,
+}
+
+#[derive(Debug, Clone, PartialEq, FormOptions)]
+enum Hobby {
+    Reading,
+    Sports,
+    Music,
+}
+
+fn validate_company(value: &str) -> bool {
+    value.ends_with("Ltd")
+}
+
+#[derive(Debug, PartialEq, Form)]
+struct Signup {
+    #[form(
+        prompt = "Email:",
+        validate = "not_empty, email",
+        message = "Invalid email"
+    )]
+    email: String,
+    #[form(name = "years", prompt = "Age (optional):")]
+    age: Option<u32>,
+    #[form(validate_with = "validate_company")]
+    company: String,
+    plan: Plan,
+    #[form(prompt = "Hobbies", limit = 2)]
+    hobbies: Vec<Hobby>,
+}
+
+/// Replaces the field stored under `order` with an already filled one.
+fn set_field(form: &mut Form, order: u32, field: Box<dyn form_builder::field::FieldTrait>) {
+    form.fields.get_mut(&order).unwrap().1 = field;
+}
+
+/// Stores `value` in `field`, as if the field had been filled.
+fn filled<T>(mut field: Field<T>, value: T) -> Box<Field<T>> {
+    field.value = Some(value);
+    Box::new(field)
+}
+
+#[test]
+fn test_form_builder_fields() {
+    let form = Signup::form_builder().build();
+    let names: Vec<&str> = form
+        .fields
+        .values()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(names, ["email", "years", "company", "plan", "hobbies"]);
+
+    let email = form
+        .find_field("email")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Field<String>>()
+        .unwrap();
+    assert_eq!(email.prompt, "Email:");
+    let validator = email.validator.as_ref().unwrap();
+    assert_eq!(validator.validate("test@example.com"), Ok(()));
+    assert_eq!(validator.validate("test"), Err("Invalid email".to_string()));
+
+    let company = form
+        .find_field("company")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Field<String>>()
+        .unwrap();
+    assert_eq!(company.prompt, "Enter company:");
+    assert!(
+        company
+            .validator
+            .as_ref()
+            .unwrap()
+            .validate("Acme")
+            .is_err()
+    );
+
+    let hobbies = form
+        .find_field("hobbies")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<MultiselectField<Hobby>>()
+        .unwrap();
+    assert_eq!(hobbies.limit, Some(2));
+    assert_eq!(hobbies.options.len(), 3);
+}
+
+#[test]
+fn test_from_form() {
+    let mut form = Signup::form_builder().build();
+    set_field(
+        &mut form,
+        0,
+        filled(
+            Field::<String>::new("", None),
+            "test@example.com".to_string(),
+        ),
+    );
+    set_field(
+        &mut form,
+        1,
+        filled(Field::<Option<u32>>::optional("", None), None),
+    );
+    set_field(
+        &mut form,
+        2,
+        filled(Field::<String>::new("", None), "Acme Ltd".to_string()),
+    );
+    set_field(
+        &mut form,
+        3,
+        Box::new(SelectField {
+            prompt: String::new(),
+            options: Vec::new(),
+            value: Some(Plan::Pro),
+            details: Default::default(),
+            provider: None,
+            policy: Default::default(),
+        }),
+    );
+    set_field(
+        &mut form,
+        4,
+        Box::new(MultiselectField {
+            prompt: String::new(),
+            options: Vec::new(),
+            value: vec![Hobby::Reading, Hobby::Music],
+            limit: Some(2),
+            details: Default::default(),
+            provider: None,
+            policy: Default::default(),
+        }),
+    );
+
+    assert_eq!(
+        Signup::from_form(&form),
+        Ok(Signup {
+            email: "test@example.com".to_string(),
+            age: None,
+            company: "Acme Ltd".to_string(),
+            plan: Plan::Pro,
+            hobbies: vec![Hobby::Reading, Hobby::Music],
+        })
+    );
+}
+
+#[test]
+fn test_from_form_unfilled() {
+    let form = Signup::form_builder().build();
+    assert!(Signup::from_form(&form).is_err());
+}
+
+#[test]
+fn test_form_options() {
+    assert_eq!(
+        Plan::options(),
+        vec![(Plan::Free, "Free plan"), (Plan::Pro, "Pro plan")]
+    );
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Form)]
+struct Order {
+    #[form(pattern = r"^ORD-\d+$", message = "Invalid order id")]
+    id: String,
+}
+
+#[test]
+fn test_pattern_attribute() {
+    let form = Order::form_builder().build();
+    let id = form
+        .find_field("id")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Field<String>>()
+        .unwrap();
+    let validator = id.validator.as_ref().unwrap();
+    assert_eq!(validator.validate("ORD-7"), Ok(()));
+    assert_eq!(validator.validate("7"), Err("Invalid order id".to_string()));
+}
@@ -0,0 +1,105 @@
+use form_builder::form::Form;
+use form_builder::{FillError, Value};
+
+/// Fills a form from a document of answers instead of prompting.
+///
+/// The answers go through `Form::fill_from_answers`, so text answers are
+/// validated like typed input. Numbers and booleans may be given as JSON
+/// values or as text, and missing or `null` answers count as empty input.
+/// Select answers are option values, and multiselect answers lists of them.
+///
+/// # Parameters
+///
+/// * `form` - The form to be filled.
+/// * `answers` - An object of answers by field name.
+///
+/// # Returns
+///
+/// * `Ok(())` if every field is filled.
+/// * `Err(FillError)` naming the first answer that is missing, unknown or invalid.
+pub fn fill(form: &mut Form, answers: &Value) -> Result<(), FillError> {
+    let Value::Record(answers) = answers else {
+        return Err(FillError::Other(
+            "The answers must be an object".to_string(),
+        ));
+    };
+    form.fill_from_answers(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definition;
+
+    /// Builds the form of a definition for testing.
+    fn form() -> Form {
+        let document = Value::from_json(
+            r#"{"fields":[
+                {"name":"email","rules":["not_empty","validate_email"]},
+                {"name":"age","type":"integer","optional":true},
+                {"name":"plan","type":"select","options":["free","pro"]},
+                {"name":"tags","type":"multiselect","options":[1,2,3],"limit":2}
+            ]}"#,
+        )
+        .unwrap();
+        definition::parse(&document).unwrap().builder.build()
+    }
+
+    #[test]
+    fn test_fill_from_answers() {
+        let mut form = form();
+        let answers =
+            Value::from_json(r#"{"email":"jane@example.com","plan":"pro","tags":[1,3]}"#).unwrap();
+        assert_eq!(fill(&mut form, &answers), Ok(()));
+        assert_eq!(
+            Value::Record(form.values()).to_json(),
+            r#"{"email":"jane@example.com","age":null,"plan":"pro","tags":[1,3]}"#
+        );
+
+        let answers =
+            Value::from_json(r#"{"email":"jane@example.com","age":"42","plan":"free","tags":[2]}"#)
+                .unwrap();
+        assert_eq!(fill(&mut form, &answers), Ok(()));
+        assert_eq!(form.get_optional::<i64>("age"), Ok(Some(42)));
+    }
+
+    #[test]
+    fn test_fill_from_invalid_answers() {
+        let fill_error = |answers: &str| {
+            fill(&mut form(), &Value::from_json(answers).unwrap())
+                .err()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            fill_error(r#"{"email":"jane","plan":"pro","tags":[1]}"#),
+            "Field 'email': Must be a valid email address"
+        );
+        assert_eq!(
+            fill_error(r#"{"email":"a@b.io","age":"old","plan":"pro","tags":[1]}"#),
+            "Field 'age': 'old' is not a valid integer"
+        );
+        assert_eq!(
+            fill_error(r#"{"email":"a@b.io","plan":"gold","tags":[1]}"#),
+            "Field 'plan': 'gold' is not an option"
+        );
+        assert_eq!(
+            fill_error(r#"{"email":"a@b.io","plan":"pro","tags":[1,2,3]}"#),
+            "Field 'tags': Please choose at most 2 options"
+        );
+        assert_eq!(
+            fill_error(r#"{"plan":"pro","tags":[1]}"#),
+            "Field 'email': Missing answer"
+        );
+        assert_eq!(
+            fill_error(r#"{"email":"a@b.io","nickname":"J"}"#),
+            "Unknown field 'nickname' in the answers"
+        );
+        assert_eq!(
+            fill(&mut form(), &Value::from_json("[]").unwrap()),
+            Err(FillError::Other(
+                "The answers must be an object".to_string()
+            ))
+        );
+    }
+}
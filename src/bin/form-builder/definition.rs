@@ -0,0 +1,487 @@
+use crate::toml;
+use form_builder::{FormBuilder, NamedRule, RetryPolicy, Validator, Value, ValueMap};
+use std::fmt::Debug;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The keys of a form definition.
+const FORM_KEYS: &[&str] = &["title", "max_attempts", "fields"];
+
+/// The keys of a field definition.
+const FIELD_KEYS: &[&str] = &[
+    "name",
+    "prompt",
+    "type",
+    "optional",
+    "rules",
+    "pattern",
+    "help",
+    "placeholder",
+    "secret",
+    "options",
+    "limit",
+];
+
+/// The keys of an option given as a table.
+const OPTION_KEYS: &[&str] = &["value", "label", "description"];
+
+/// A form read from a definition file.
+pub struct Definition {
+    /// The title printed before the form is filled interactively.
+    pub title: Option<String>,
+    /// The builder with the fields of the form.
+    pub builder: FormBuilder,
+}
+
+/// Reads a JSON or TOML document, choosing the format by the file extension.
+///
+/// # Parameters
+///
+/// * `path` - The path of a `.json` or `.toml` file.
+///
+/// # Returns
+///
+/// * `Ok(Value)` with the document.
+/// * `Err(String)` if the file cannot be read, has another extension or is invalid.
+pub fn read_document(path: &Path) -> Result<Value, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("Cannot read '{}': {}", path.display(), err))?;
+    let document = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => Value::from_json(&text).map_err(|err| format!("Invalid JSON: {}", err)),
+        Some("toml") => toml::parse(&text),
+        _ => {
+            return Err(format!(
+                "'{}' must be a .json or .toml file",
+                path.display()
+            ));
+        }
+    };
+    document.map_err(|err| format!("{} in '{}'", err, path.display()))
+}
+
+/// Reads a form definition from a JSON or TOML file.
+///
+/// # Parameters
+///
+/// * `path` - The path of a `.json` or `.toml` file.
+///
+/// # Returns
+///
+/// * `Ok(Definition)` with the form.
+/// * `Err(String)` if the file cannot be read or the definition is invalid.
+pub fn load(path: &Path) -> Result<Definition, String> {
+    parse(&read_document(path)?)
+}
+
+/// Builds a form from a definition document.
+///
+/// The document has an optional `title`, an optional `max_attempts` and a
+/// list of `fields`. Each field has a `name` and optionally a `prompt`, a
+/// `type` (`string`, `integer`, `float`, `bool`, `char`, `select` or
+/// `multiselect`), `help` and, for text fields, `optional`, `rules` naming
+/// built-in `ValidationMethods` functions, a regular expression `pattern`, a
+/// `placeholder` and `secret`. Select and multiselect fields have `options`,
+/// given as values or as tables with a `value`, `label` and `description`,
+/// and multiselect fields may have a `limit`.
+///
+/// # Parameters
+///
+/// * `document` - The definition.
+///
+/// # Returns
+///
+/// * `Ok(Definition)` with the form.
+/// * `Err(String)` naming the first invalid or unknown key.
+pub fn parse(document: &Value) -> Result<Definition, String> {
+    let Value::Record(document) = document else {
+        return Err("The definition must be an object".to_string());
+    };
+    check_keys(document, FORM_KEYS, "the definition")?;
+    let title = string(document, "title", "the definition")?.map(str::to_string);
+    let max_attempts = integer(document, "max_attempts", "the definition")?;
+    let mut builder = FormBuilder::new();
+    if let Some(max_attempts) = max_attempts {
+        if max_attempts == 0 {
+            return Err("'max_attempts' must be at least 1".to_string());
+        }
+        builder = builder.retry_policy(RetryPolicy::attempts(max_attempts));
+    }
+    let fields = match document.get("fields") {
+        Some(Value::List(fields)) if !fields.is_empty() => fields,
+        Some(Value::List(_)) | None => return Err("The definition has no fields".to_string()),
+        Some(_) => return Err("'fields' must be a list".to_string()),
+    };
+    let mut names: Vec<&str> = Vec::new();
+    for field in fields {
+        let Value::Record(field) = field else {
+            return Err("Every field must be an object".to_string());
+        };
+        let Some(name) = string(field, "name", "a field")? else {
+            return Err("Every field needs a 'name'".to_string());
+        };
+        if names.contains(&name) {
+            return Err(format!("Field '{}' is defined twice", name));
+        }
+        names.push(name);
+        builder = add_field(builder, name, field)?;
+    }
+    Ok(Definition { title, builder })
+}
+
+/// Adds a field from its definition.
+fn add_field(builder: FormBuilder, name: &str, field: &ValueMap) -> Result<FormBuilder, String> {
+    let context = format!("field '{}'", name);
+    check_keys(field, FIELD_KEYS, &context)?;
+    let prompt = string(field, "prompt", &context)?.unwrap_or(name);
+    let kind = string(field, "type", &context)?.unwrap_or("string");
+    let optional = boolean(field, "optional", &context)?;
+    let validator = validator(field, &context)?;
+    let is_text = !matches!(kind, "select" | "multiselect");
+    for key in ["optional", "rules", "pattern", "placeholder", "secret"] {
+        if !is_text && field.get(key).is_some() {
+            return Err(format!(
+                "'{}' of {} applies to text fields only",
+                key, context
+            ));
+        }
+    }
+    if kind != "multiselect" && field.get("limit").is_some() {
+        return Err(format!(
+            "'limit' of {} applies to multiselect fields only",
+            context
+        ));
+    }
+    let mut builder = match kind {
+        "string" => add_input::<String>(builder, name, prompt, validator, optional),
+        "integer" => add_input::<i64>(builder, name, prompt, validator, optional),
+        "float" => add_input::<f64>(builder, name, prompt, validator, optional),
+        "bool" => add_input::<bool>(builder, name, prompt, validator, optional),
+        "char" => add_input::<char>(builder, name, prompt, validator, optional),
+        "select" | "multiselect" => add_choices(builder, name, prompt, kind, field, &context)?,
+        _ => return Err(format!("Unknown type '{}' of {}", kind, context)),
+    };
+    if let Some(help) = string(field, "help", &context)? {
+        builder = builder.help(name, help);
+    }
+    if let Some(placeholder) = string(field, "placeholder", &context)? {
+        builder = builder.placeholder(name, placeholder);
+    }
+    if boolean(field, "secret", &context)? {
+        builder = builder.secret(name);
+    }
+    Ok(builder)
+}
+
+/// Builds the validator of a text field from its `rules` and `pattern`.
+fn validator(field: &ValueMap, context: &str) -> Result<Option<Validator>, String> {
+    let names = match field.get("rules") {
+        None => Vec::new(),
+        Some(Value::List(names)) => names
+            .iter()
+            .map(|name| match name {
+                Value::String(name) => Ok(name.as_str()),
+                _ => Err(format!("'rules' of {} must list rule names", context)),
+            })
+            .collect::<Result<Vec<&str>, String>>()?,
+        Some(_) => return Err(format!("'rules' of {} must be a list", context)),
+    };
+    let mut rules = Vec::new();
+    for name in names {
+        let rule = NamedRule::from_name(name)
+            .ok_or_else(|| format!("Unknown rule '{}' of {}", name, context))?;
+        rules.push(rule);
+    }
+    let pattern = string(field, "pattern", context)?;
+    if rules.is_empty() && pattern.is_none() {
+        return Ok(None);
+    }
+    let mut validator = Validator::new(Vec::new());
+    for rule in rules {
+        validator = validator.builtin(rule, Some(rule.requirement()));
+    }
+    if let Some(pattern) = pattern {
+        validator = validator
+            .regex(pattern, None)
+            .map_err(|err| format!("Invalid 'pattern' of {}: {}", context, err))?;
+    }
+    Ok(Some(validator))
+}
+
+/// Adds a text field, or an optional one whose value may be absent.
+fn add_input<T>(
+    builder: FormBuilder,
+    name: &str,
+    prompt: &str,
+    validator: Option<Validator>,
+    optional: bool,
+) -> FormBuilder
+where
+    T: 'static + FromStr + Debug + Clone + Default,
+    T::Err: Debug,
+{
+    if optional {
+        builder.add_optional_field::<T>(name, prompt, validator)
+    } else {
+        builder.add_field::<T>(name, prompt, validator)
+    }
+}
+
+/// Adds a select or multiselect field with the `options` of its definition.
+///
+/// Option values are all strings or all integers, and are their own labels
+/// unless a label is given.
+fn add_choices(
+    builder: FormBuilder,
+    name: &str,
+    prompt: &str,
+    kind: &str,
+    field: &ValueMap,
+    context: &str,
+) -> Result<FormBuilder, String> {
+    let options = match field.get("options") {
+        Some(Value::List(options)) if !options.is_empty() => options,
+        Some(Value::List(_)) | None => {
+            return Err(format!("{} has no options", capitalize(context)));
+        }
+        Some(_) => return Err(format!("'options' of {} must be a list", context)),
+    };
+    let mut values = Vec::new();
+    let mut labels = Vec::new();
+    let mut descriptions = Vec::new();
+    for option in options {
+        let (value, label, description) = match option {
+            Value::Record(option) => {
+                check_keys(option, OPTION_KEYS, &format!("an option of {}", context))?;
+                let value = option
+                    .get("value")
+                    .ok_or_else(|| format!("Every option of {} needs a 'value'", context))?;
+                let label = string(option, "label", context)?.map(str::to_string);
+                let description = string(option, "description", context)?;
+                (value, label, description)
+            }
+            value => (value, None, None),
+        };
+        let label = label.unwrap_or_else(|| value.to_string());
+        if let Some(description) = description {
+            descriptions.push((value.clone(), description));
+        }
+        values.push(value);
+        labels.push(label);
+    }
+    let limit = integer(field, "limit", context)?;
+    let multiselect = (kind == "multiselect").then_some(limit);
+    let integers: Option<Vec<i64>> = values
+        .iter()
+        .map(|value| match value {
+            Value::Integer(value) => Some(*value),
+            _ => None,
+        })
+        .collect();
+    let strings: Option<Vec<String>> = values
+        .iter()
+        .map(|value| match value {
+            Value::String(value) => Some(value.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut builder = match (integers, strings) {
+        (Some(values), _) => {
+            add_options::<i64>(builder, name, prompt, values, &labels, multiselect)
+        }
+        (None, Some(values)) => {
+            add_options::<String>(builder, name, prompt, values, &labels, multiselect)
+        }
+        (None, None) => {
+            return Err(format!(
+                "Options of {} must be all strings or all integers",
+                context
+            ));
+        }
+    };
+    for (value, description) in descriptions {
+        builder = builder.describe_option(name, value, description);
+    }
+    Ok(builder)
+}
+
+/// Adds a select field, or a multiselect field with the given limit.
+fn add_options<T>(
+    builder: FormBuilder,
+    name: &str,
+    prompt: &str,
+    values: Vec<T>,
+    labels: &[String],
+    multiselect: Option<Option<usize>>,
+) -> FormBuilder
+where
+    T: 'static + Clone + PartialEq + Debug,
+{
+    let options = values
+        .into_iter()
+        .zip(labels.iter().map(String::as_str))
+        .collect();
+    match multiselect {
+        Some(limit) => builder.add_multiselect(name, prompt, options, limit),
+        None => builder.add_select(name, prompt, options),
+    }
+}
+
+/// Fails on the first key not in the allowed list.
+fn check_keys(map: &ValueMap, allowed: &[&str], context: &str) -> Result<(), String> {
+    match map.iter().find(|(key, _)| !allowed.contains(key)) {
+        Some((key, _)) => Err(format!("Unknown key '{}' in {}", key, context)),
+        None => Ok(()),
+    }
+}
+
+/// Reads a key with a string value.
+fn string<'a>(map: &'a ValueMap, key: &str, context: &str) -> Result<Option<&'a str>, String> {
+    match map.get(key) {
+        None => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(_) => Err(format!("'{}' of {} must be a string", key, context)),
+    }
+}
+
+/// Reads a key with a boolean value, which is `false` if missing.
+fn boolean(map: &ValueMap, key: &str, context: &str) -> Result<bool, String> {
+    match map.get(key) {
+        None => Ok(false),
+        Some(Value::Bool(value)) => Ok(*value),
+        Some(_) => Err(format!("'{}' of {} must be true or false", key, context)),
+    }
+}
+
+/// Reads a key with a non-negative integer value.
+fn integer(map: &ValueMap, key: &str, context: &str) -> Result<Option<usize>, String> {
+    match map.get(key) {
+        None => Ok(None),
+        Some(Value::Integer(value)) if *value >= 0 => Ok(Some(*value as usize)),
+        Some(_) => Err(format!(
+            "'{}' of {} must be a non-negative integer",
+            key, context
+        )),
+    }
+}
+
+/// Capitalizes the first letter of a context for the start of a message.
+fn capitalize(context: &str) -> String {
+    let mut chars = context.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use form_builder::FieldKind;
+
+    #[test]
+    fn test_parse_definition() {
+        let document = toml::parse(
+            r#"
+            title = "Sign up"
+            max_attempts = 3
+
+            [[fields]]
+            name = "email"
+            prompt = "Email:"
+            rules = ["not_empty", "validate_email"]
+            placeholder = "jane@example.com"
+
+            [[fields]]
+            name = "code"
+            pattern = "^[A-Z]{3}$"
+
+            [[fields]]
+            name = "age"
+            type = "integer"
+            optional = true
+
+            [[fields]]
+            name = "plan"
+            type = "select"
+            options = [{ value = 1, label = "Free", description = "One seat" }, { value = 2, label = "Pro" }]
+
+            [[fields]]
+            name = "tags"
+            type = "multiselect"
+            options = ["rust", "go"]
+            limit = 1
+            "#,
+        )
+        .unwrap();
+        let definition = parse(&document).unwrap();
+        assert_eq!(definition.title.as_deref(), Some("Sign up"));
+
+        let form = definition.builder.build();
+        assert_eq!(form.retry, Some(RetryPolicy::attempts(3)));
+        let email = form.find_field("email").unwrap();
+        assert_eq!(email.prompt(), "Email:");
+        assert_eq!(
+            email.requirements(),
+            ["Must not be empty", "Must be a valid email address"]
+        );
+        let code = form.find_field("code").unwrap();
+        assert_eq!(code.requirements(), ["Must match '^[A-Z]{3}$'"]);
+        let validator = code.validator().unwrap();
+        assert_eq!(validator.validate("ABC"), Ok(()));
+        assert!(validator.validate("abc").is_err());
+        assert!(form.find_field("age").unwrap().is_optional());
+        let plan = form.find_field("plan").unwrap();
+        assert_eq!(plan.kind(), FieldKind::Select);
+        assert_eq!(
+            plan.options(),
+            [
+                (Value::Integer(1), "Free".to_string()),
+                (Value::Integer(2), "Pro".to_string())
+            ]
+        );
+        let tags = form.find_field("tags").unwrap();
+        assert_eq!(
+            tags.options()[0],
+            (Value::String("rust".to_string()), "rust".to_string())
+        );
+        assert_eq!(tags.selection_limit(), Some(1));
+    }
+
+    #[test]
+    fn test_parse_definition_errors() {
+        let parse_error =
+            |definition: &str| parse(&Value::from_json(definition).unwrap()).err().unwrap();
+        assert_eq!(
+            parse_error(r#"{"fields":[{"name":"a","rules":["is_disposable"]}]}"#),
+            "Unknown rule 'is_disposable' of field 'a'"
+        );
+        assert!(
+            parse_error(r#"{"fields":[{"name":"a","pattern":"("}]}"#)
+                .starts_with("Invalid 'pattern' of field 'a': ")
+        );
+        assert_eq!(
+            parse_error(r#"{"fields":[{"name":"a","kind":"string"}]}"#),
+            "Unknown key 'kind' in field 'a'"
+        );
+        assert_eq!(
+            parse_error(r#"{"fields":[{"name":"a","type":"select"}]}"#),
+            "Field 'a' has no options"
+        );
+        assert_eq!(
+            parse_error(r#"{"fields":[{"name":"a","type":"select","options":[1,"b"]}]}"#),
+            "Options of field 'a' must be all strings or all integers"
+        );
+        assert_eq!(
+            parse_error(r#"{"fields":[{"name":"a","type":"select","options":[1],"secret":true}]}"#),
+            "'secret' of field 'a' applies to text fields only"
+        );
+        assert_eq!(
+            parse_error(r#"{"fields":[{"name":"a"},{"name":"a"}]}"#),
+            "Field 'a' is defined twice"
+        );
+        assert_eq!(
+            parse_error(r#"{"fields":[]}"#),
+            "The definition has no fields"
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! A command-line runner filling forms defined in JSON or TOML files.
+//!
+//! The form is filled interactively, or headless from a file of answers,
+//! and the answers are written as JSON, YAML or environment variable lines,
+//! so shell scripts can collect structured input without writing Rust.
+
+/// Module filling forms from files of answers.
+mod answers;
+
+/// Module reading form definitions.
+mod definition;
+
+/// Module writing the answers of a form.
+mod output;
+
+/// Module containing a parser for TOML documents.
+mod toml;
+
+use form_builder::FillError;
+use form_builder::input::{InputError, PromptOutput};
+use output::Format;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// The exit code of arguments, definitions or files that are invalid.
+const EXIT_ERROR: u8 = 1;
+
+/// The exit code of answers failing validation.
+const EXIT_INVALID: u8 = 2;
+
+/// The exit code of filling cancelled before the form was complete.
+const EXIT_CANCELLED: u8 = 3;
+
+/// The help printed for `--help`.
+const USAGE: &str = "\
+Usage: form-builder <DEFINITION> [OPTIONS]
+
+Fills the form defined in a .json or .toml file and writes the answers.
+
+Options:
+  -a, --answers <FILE>   Read the answers from a .json or .toml file instead of prompting
+  -f, --format <FORMAT>  Write the answers as json (default), yaml or env
+  -o, --output <FILE>    Write the answers to a file instead of stdout
+  -h, --help             Print this help
+
+Prompts are written to stderr, or to the terminal when stdin is
+redirected, so the answers written to stdout can be piped or captured.
+
+Exit codes:
+  0  The form was filled
+  1  The arguments, definition or files are invalid
+  2  An answer failed validation
+  3  Input ended, was interrupted or timed out before the form was complete
+";
+
+/// The options given on the command line.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// The path of the form definition.
+    definition: PathBuf,
+    /// The path of the answers, or `None` to prompt for them.
+    answers: Option<PathBuf>,
+    /// The format the answers are written in.
+    format: Format,
+    /// The path the answers are written to, or `None` for stdout.
+    output: Option<PathBuf>,
+}
+
+/// Why the runner stopped without writing answers.
+#[derive(Debug, PartialEq)]
+enum Failure {
+    /// The arguments, definition or files are invalid.
+    Error(String),
+    /// An answer failed validation.
+    Invalid(String),
+    /// Filling was cancelled before the form was complete.
+    Cancelled(String),
+}
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            let (code, message) = match failure {
+                Failure::Error(message) => (EXIT_ERROR, message),
+                Failure::Invalid(message) => (EXIT_INVALID, message),
+                Failure::Cancelled(message) => (EXIT_CANCELLED, message),
+            };
+            eprintln!("form-builder: {}", message);
+            ExitCode::from(code)
+        }
+    }
+}
+
+/// Runs the command with its arguments.
+///
+/// # Parameters
+///
+/// * `args` - The arguments, without the name of the program.
+///
+/// # Returns
+///
+/// * `Ok(())` if the answers were written or the help was printed.
+/// * `Err(Failure)` with the reason the runner stopped.
+fn run(args: Vec<String>) -> Result<(), Failure> {
+    let Some(options) = parse_args(args).map_err(Failure::Error)? else {
+        print!("{}", USAGE);
+        return Ok(());
+    };
+    let definition = definition::load(&options.definition).map_err(Failure::Error)?;
+    let mut form = definition
+        .builder
+        .prompt_output(PromptOutput::Stderr)
+        .build();
+    match &options.answers {
+        Some(path) => {
+            let answers = definition::read_document(path).map_err(Failure::Error)?;
+            answers::fill(&mut form, &answers).map_err(classify)?;
+        }
+        None => {
+            if let Some(title) = &definition.title {
+                eprintln!("{}", title);
+            }
+            form.fill().map_err(classify)?;
+        }
+    }
+    let text = output::write(&form.values(), options.format);
+    match &options.output {
+        Some(path) => fs::write(path, text)
+            .map_err(|err| Failure::Error(format!("Cannot write '{}': {}", path.display(), err))),
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+/// Tells why filling a form interactively or from answers failed.
+///
+/// # Parameters
+///
+/// * `error` - The error of `Form::fill`.
+///
+/// # Returns
+///
+/// * `Failure::Invalid` if a field used up its attempts or got input or an
+///   answer that is invalid, `Failure::Cancelled` if input ended early, was
+///   interrupted or timed out, or a hook cancelled filling, and
+///   `Failure::Error` otherwise.
+fn classify(error: FillError) -> Failure {
+    let message = error.to_string();
+    match error {
+        FillError::Input {
+            error: InputError::AttemptsExhausted(_) | InputError::Invalid(_),
+            ..
+        } => Failure::Invalid(message),
+        FillError::Input {
+            error: InputError::Eof | InputError::Interrupted | InputError::TimedOut,
+            ..
+        }
+        | FillError::Cancelled(_) => Failure::Cancelled(message),
+        FillError::Input {
+            error: InputError::Io(_),
+            ..
+        }
+        | FillError::Other(_) => Failure::Error(message),
+    }
+}
+
+/// Parses the command line arguments.
+///
+/// # Returns
+///
+/// * `Ok(Some(Options))` with the options.
+/// * `Ok(None)` if the help was asked for.
+/// * `Err(String)` if the arguments are invalid.
+fn parse_args(args: Vec<String>) -> Result<Option<Options>, String> {
+    let mut definition = None;
+    let mut answers = None;
+    let mut format = Format::Json;
+    let mut output = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |option: &str| {
+            args.next()
+                .ok_or_else(|| format!("Missing value of '{}'", option))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-a" | "--answers" => answers = Some(PathBuf::from(value(&arg)?)),
+            "-f" | "--format" => format = Format::parse(&value(&arg)?)?,
+            "-o" | "--output" => output = Some(PathBuf::from(value(&arg)?)),
+            _ if arg.starts_with('-') => {
+                return Err(format!("Unknown option '{}'; see --help", arg));
+            }
+            _ if definition.is_some() => {
+                return Err(format!("Unexpected argument '{}'; see --help", arg));
+            }
+            _ => definition = Some(PathBuf::from(arg)),
+        }
+    }
+    let definition = definition.ok_or("Missing the definition file; see --help")?;
+    Ok(Some(Options {
+        definition,
+        answers,
+        format,
+        output,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Converts arguments to strings.
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(args(&[
+                "form.toml",
+                "-a",
+                "answers.json",
+                "--format",
+                "env"
+            ])),
+            Ok(Some(Options {
+                definition: PathBuf::from("form.toml"),
+                answers: Some(PathBuf::from("answers.json")),
+                format: Format::Env,
+                output: None,
+            }))
+        );
+        assert_eq!(parse_args(args(&["form.toml", "--help"])), Ok(None));
+        assert_eq!(
+            parse_args(args(&["form.toml", "-o"])),
+            Err("Missing value of '-o'".to_string())
+        );
+        assert_eq!(
+            parse_args(args(&["-x"])),
+            Err("Unknown option '-x'; see --help".to_string())
+        );
+        assert_eq!(
+            parse_args(Vec::new()),
+            Err("Missing the definition file; see --help".to_string())
+        );
+    }
+
+    /// Creates the error of a prompt of the `age` field.
+    fn input_error(error: InputError) -> FillError {
+        FillError::Input {
+            field: "age".to_string(),
+            error,
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            classify(input_error(InputError::AttemptsExhausted(3))),
+            Failure::Invalid("Field 'age': No valid input after 3 attempts".to_string())
+        );
+        assert_eq!(
+            classify(input_error(InputError::Invalid("Not a number".to_string()))),
+            Failure::Invalid("Field 'age': Not a number".to_string())
+        );
+        assert_eq!(
+            classify(input_error(InputError::Eof)),
+            Failure::Cancelled("Field 'age': Unexpected end of input".to_string())
+        );
+        assert_eq!(
+            classify(input_error(InputError::Interrupted)),
+            Failure::Cancelled("Field 'age': Interrupted".to_string())
+        );
+        assert_eq!(
+            classify(FillError::Cancelled("age".to_string())),
+            Failure::Cancelled("Filling was cancelled at field 'age'".to_string())
+        );
+        assert_eq!(
+            classify(input_error(InputError::Io("Broken pipe".to_string()))),
+            Failure::Error("Field 'age': Broken pipe".to_string())
+        );
+        assert_eq!(
+            classify(FillError::Other("Cannot write draft".to_string())),
+            Failure::Error("Cannot write draft".to_string())
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use form_builder::{Value, ValueMap};
+
+/// The format the answers are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A JSON object on one line.
+    Json,
+    /// A YAML mapping.
+    Yaml,
+    /// `NAME='value'` lines for the shell, such as for `eval` or `source`.
+    Env,
+}
+
+impl Format {
+    /// Parses the name of a format.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - `json`, `yaml` or `env`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Format)` with the format.
+    /// * `Err(String)` for other names.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "json" => Ok(Format::Json),
+            "yaml" => Ok(Format::Yaml),
+            "env" => Ok(Format::Env),
+            _ => Err(format!("Unknown format '{}'; use json, yaml or env", name)),
+        }
+    }
+}
+
+/// Writes the answers of a form.
+///
+/// # Parameters
+///
+/// * `answers` - The values of the fields by name.
+/// * `format` - The format to be written.
+///
+/// # Returns
+///
+/// * The text, ending with a line break.
+pub fn write(answers: &ValueMap, format: Format) -> String {
+    let mut output = String::new();
+    match format {
+        Format::Json => {
+            output.push_str(&Value::Record(answers.clone()).to_json());
+            output.push('\n');
+        }
+        Format::Yaml => write_yaml(answers, 0, &mut output),
+        Format::Env => write_env(answers, "", &mut output),
+    }
+    output
+}
+
+/// Appends a mapping as YAML, indented by `indent` spaces.
+///
+/// Scalars are written as JSON, which YAML reads unchanged, so strings are
+/// always quoted.
+fn write_yaml(answers: &ValueMap, indent: usize, output: &mut String) {
+    let padding = " ".repeat(indent);
+    for (name, value) in answers.iter() {
+        let key = if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            name.to_string()
+        } else {
+            Value::String(name.to_string()).to_json()
+        };
+        match value {
+            Value::Record(values) if !values.is_empty() => {
+                output.push_str(&format!("{}{}:\n", padding, key));
+                write_yaml(values, indent + 2, output);
+            }
+            Value::List(values) if !values.is_empty() => {
+                output.push_str(&format!("{}{}:\n", padding, key));
+                for value in values {
+                    output.push_str(&format!("{}  - {}\n", padding, value.to_json()));
+                }
+            }
+            _ => output.push_str(&format!("{}{}: {}\n", padding, key, value.to_json())),
+        }
+    }
+}
+
+/// Appends the answers as `NAME='value'` lines, with names prefixed by `prefix`.
+///
+/// Names are upper-cased with other characters than letters and digits
+/// replaced by `_`. Fields of records are prefixed with the name of the
+/// record, lists are joined by commas and missing values are empty.
+fn write_env(answers: &ValueMap, prefix: &str, output: &mut String) {
+    for (name, value) in answers.iter() {
+        let name: String = format!("{}{}", prefix, name)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        match value {
+            Value::Record(values) => write_env(values, &format!("{}_", name), output),
+            _ => output.push_str(&format!("{}={}\n", name, shell_quote(&env_text(value)))),
+        }
+    }
+}
+
+/// Formats a value as the text of an environment variable.
+fn env_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::List(values) => values.iter().map(env_text).collect::<Vec<_>>().join(","),
+        Value::Record(_) => value.to_json(),
+        _ => value.to_string(),
+    }
+}
+
+/// Quotes text for the shell with single quotes.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the answers of a form for testing.
+    fn answers() -> ValueMap {
+        let mut address = ValueMap::new();
+        address.insert("city", Value::String("Kraków".to_string()));
+        let mut answers = ValueMap::new();
+        answers.insert("name", Value::String("Jane O'Hara".to_string()));
+        answers.insert("age", Value::Integer(42));
+        answers.insert("nick-name", Value::Null);
+        answers.insert(
+            "tags",
+            Value::List(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string()),
+            ]),
+        );
+        answers.insert("address", Value::Record(address));
+        answers
+    }
+
+    #[test]
+    fn test_write_formats() {
+        assert_eq!(
+            write(&answers(), Format::Json),
+            "{\"name\":\"Jane O'Hara\",\"age\":42,\"nick-name\":null,\"tags\":[\"a\",\"b\"],\"address\":{\"city\":\"Kraków\"}}\n"
+        );
+        assert_eq!(
+            write(&answers(), Format::Yaml),
+            "name: \"Jane O'Hara\"\nage: 42\nnick-name: null\ntags:\n  - \"a\"\n  - \"b\"\naddress:\n  city: \"Kraków\"\n"
+        );
+        assert_eq!(
+            write(&answers(), Format::Env),
+            "NAME='Jane O'\\''Hara'\nAGE='42'\nNICK_NAME=''\nTAGS='a,b'\nADDRESS_CITY='Kraków'\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(Format::parse("yaml"), Ok(Format::Yaml));
+        assert_eq!(
+            Format::parse("xml"),
+            Err("Unknown format 'xml'; use json, yaml or env".to_string())
+        );
+    }
+}
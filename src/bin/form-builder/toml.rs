@@ -0,0 +1,502 @@
+use form_builder::{Value, ValueMap};
+
+/// A table of a TOML document while it is read.
+#[derive(Debug, Default)]
+struct Table {
+    /// The entries of the table, in order.
+    entries: Vec<(String, Item)>,
+    /// Whether the table was opened by a `[table]` header or a dotted key,
+    /// so that a second header for it is an error.
+    defined: bool,
+}
+
+/// An entry of a table while the document is read.
+#[derive(Debug)]
+enum Item {
+    /// A value given after `=`.
+    Value(Value),
+    /// A table opened by a header or a dotted key.
+    Table(Table),
+    /// An array of tables opened by `[[array]]` headers.
+    Array(Vec<Table>),
+}
+
+impl Table {
+    /// Finds the entry with a key.
+    fn get_mut(&mut self, key: &str) -> Option<&mut Item> {
+        self.entries
+            .iter_mut()
+            .find(|(name, _)| name == key)
+            .map(|(_, item)| item)
+    }
+
+    /// Finds the table at a key, creating it if it is missing.
+    ///
+    /// The last table of an array of tables stands for the array.
+    fn table_mut(&mut self, key: &str) -> Result<&mut Table, String> {
+        if self.get_mut(key).is_none() {
+            self.entries
+                .push((key.to_string(), Item::Table(Table::default())));
+        }
+        match self.get_mut(key) {
+            Some(Item::Table(table)) => Ok(table),
+            Some(Item::Array(tables)) => tables
+                .last_mut()
+                .ok_or_else(|| format!("Key '{}' is not a table", key)),
+            _ => Err(format!("Key '{}' is not a table", key)),
+        }
+    }
+
+    /// Finds the table at a path of keys, creating the missing tables.
+    fn path_mut(&mut self, path: &[String]) -> Result<&mut Table, String> {
+        path.iter()
+            .try_fold(self, |table, key| table.table_mut(key))
+    }
+
+    /// Converts the table to a `Value::Record`.
+    fn into_value(self) -> Value {
+        let mut map = ValueMap::new();
+        for (key, item) in self.entries {
+            let value = match item {
+                Item::Value(value) => value,
+                Item::Table(table) => table.into_value(),
+                Item::Array(tables) => {
+                    Value::List(tables.into_iter().map(Table::into_value).collect())
+                }
+            };
+            map.insert(&key, value);
+        }
+        Value::Record(map)
+    }
+}
+
+/// Parses a TOML document into a `Value::Record`.
+///
+/// Tables, arrays of tables, dotted keys, basic and literal strings,
+/// integers, floats, booleans, arrays and inline tables are supported.
+/// Multi-line strings and dates are not.
+///
+/// Keys containing dots cannot be looked up with `ValueMap::get`, which
+/// reads dots as paths, so they are rejected too.
+///
+/// # Parameters
+///
+/// * `input` - The TOML text.
+///
+/// # Returns
+///
+/// * `Ok(Value)` with the document as a record.
+/// * `Err(String)` with the line of the first error.
+pub fn parse(input: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        position: 0,
+        line: 1,
+    };
+    parser
+        .parse_document()
+        .map_err(|err| format!("Invalid TOML on line {}: {}", parser.line, err))
+}
+
+/// Splits a dotted key into its last key and the keys of the tables containing it.
+///
+/// # Returns
+///
+/// * `Ok((last, parents))` with the last key and the keys before it.
+/// * `Err(String)` if the key is empty.
+fn split_key(path: &[String]) -> Result<(&String, &[String]), String> {
+    path.split_last()
+        .ok_or_else(|| "Expected a key".to_string())
+}
+
+/// A parser reading a TOML document character by character.
+struct Parser {
+    /// The characters of the document.
+    chars: Vec<char>,
+    /// The index of the next character.
+    position: usize,
+    /// The line of the next character, for error messages.
+    line: usize,
+}
+
+impl Parser {
+    /// Reads the whole document.
+    fn parse_document(&mut self) -> Result<Value, String> {
+        let mut root = Table::default();
+        let mut current: Vec<String> = Vec::new();
+        loop {
+            self.skip_blank_lines();
+            match self.peek() {
+                None => break,
+                Some('[') => {
+                    self.position += 1;
+                    let is_array = self.eat('[');
+                    self.skip_spaces();
+                    let path = self.parse_key()?;
+                    self.skip_spaces();
+                    if !(self.eat(']') && (!is_array || self.eat(']'))) {
+                        return Err("Expected ']' after the table name".to_string());
+                    }
+                    self.end_line()?;
+                    if is_array {
+                        let (last, parents) = split_key(&path)?;
+                        let parent = root.path_mut(parents)?;
+                        match parent.get_mut(last) {
+                            None => parent
+                                .entries
+                                .push((last.clone(), Item::Array(vec![Table::default()]))),
+                            Some(Item::Array(tables)) => tables.push(Table::default()),
+                            Some(_) => {
+                                return Err(format!("Key '{}' is not an array of tables", last));
+                            }
+                        }
+                    } else {
+                        let table = root.path_mut(&path)?;
+                        if table.defined {
+                            return Err(format!("Table '{}' is defined twice", path.join(".")));
+                        }
+                        table.defined = true;
+                    }
+                    current = path;
+                }
+                Some(_) => {
+                    let table = root.path_mut(&current)?;
+                    self.parse_entry(table)?;
+                    self.end_line()?;
+                }
+            }
+        }
+        Ok(root.into_value())
+    }
+
+    /// Reads a `key = value` entry into a table.
+    fn parse_entry(&mut self, table: &mut Table) -> Result<(), String> {
+        let path = self.parse_key()?;
+        self.skip_spaces();
+        if !self.eat('=') {
+            return Err("Expected '=' after the key".to_string());
+        }
+        self.skip_spaces();
+        let value = self.parse_value()?;
+        let (last, parents) = split_key(&path)?;
+        let table = table.path_mut(parents)?;
+        if table.get_mut(last).is_some() {
+            return Err(format!("Key '{}' is defined twice", last));
+        }
+        table.entries.push((last.clone(), Item::Value(value)));
+        Ok(())
+    }
+
+    /// Reads a key, which may be dotted.
+    fn parse_key(&mut self) -> Result<Vec<String>, String> {
+        let mut path = Vec::new();
+        loop {
+            self.skip_spaces();
+            let key = match self.peek() {
+                Some('"') => self.parse_basic_string()?,
+                Some('\'') => self.parse_literal_string()?,
+                _ => {
+                    let start = self.position;
+                    while self
+                        .peek()
+                        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                    {
+                        self.position += 1;
+                    }
+                    if start == self.position {
+                        return Err("Expected a key".to_string());
+                    }
+                    self.chars[start..self.position].iter().collect()
+                }
+            };
+            if key.contains('.') {
+                return Err(format!("Unsupported key '{}' containing a dot", key));
+            }
+            path.push(key);
+            self.skip_spaces();
+            if !self.eat('.') {
+                return Ok(path);
+            }
+        }
+    }
+
+    /// Reads a value.
+    fn parse_value(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some('"') => {
+                if self.starts_with("\"\"\"") {
+                    return Err("Multi-line strings are not supported".to_string());
+                }
+                self.parse_basic_string().map(Value::String)
+            }
+            Some('\'') => {
+                if self.starts_with("'''") {
+                    return Err("Multi-line strings are not supported".to_string());
+                }
+                self.parse_literal_string().map(Value::String)
+            }
+            Some('[') => self.parse_array(),
+            Some('{') => self.parse_inline_table(),
+            Some(_) if self.starts_with("true") => {
+                self.position += 4;
+                Ok(Value::Bool(true))
+            }
+            Some(_) if self.starts_with("false") => {
+                self.position += 5;
+                Ok(Value::Bool(false))
+            }
+            Some(_) => self.parse_number(),
+            None => Err("Expected a value".to_string()),
+        }
+    }
+
+    /// Reads an array, which may span several lines.
+    fn parse_array(&mut self) -> Result<Value, String> {
+        self.position += 1;
+        let mut values = Vec::new();
+        loop {
+            self.skip_blank_lines();
+            if self.eat(']') {
+                return Ok(Value::List(values));
+            }
+            values.push(self.parse_value()?);
+            self.skip_blank_lines();
+            if !self.eat(',') {
+                self.skip_blank_lines();
+                if self.eat(']') {
+                    return Ok(Value::List(values));
+                }
+                return Err("Expected ',' or ']' in the array".to_string());
+            }
+        }
+    }
+
+    /// Reads an inline table on a single line.
+    fn parse_inline_table(&mut self) -> Result<Value, String> {
+        self.position += 1;
+        let mut table = Table::default();
+        self.skip_spaces();
+        if self.eat('}') {
+            return Ok(table.into_value());
+        }
+        loop {
+            self.skip_spaces();
+            self.parse_entry(&mut table)?;
+            self.skip_spaces();
+            if self.eat('}') {
+                return Ok(table.into_value());
+            }
+            if !self.eat(',') {
+                return Err("Expected ',' or '}' in the inline table".to_string());
+            }
+        }
+    }
+
+    /// Reads an integer or a float.
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.' | '_'))
+        {
+            self.position += 1;
+        }
+        let text: String = self.chars[start..self.position].iter().collect();
+        let digits = text.replace('_', "");
+        if let Ok(value) = digits.parse::<i64>() {
+            return Ok(Value::Integer(value));
+        }
+        match digits.parse::<f64>() {
+            Ok(value) if digits.chars().any(|c| c.is_ascii_digit()) => Ok(Value::Float(value)),
+            _ => Err(format!("Unsupported value '{}'", text)),
+        }
+    }
+
+    /// Reads a string in double quotes, with escapes.
+    fn parse_basic_string(&mut self) -> Result<String, String> {
+        self.position += 1;
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                None | Some('\n') => return Err("Unterminated string".to_string()),
+                Some('"') => return Ok(value),
+                Some('\\') => {
+                    let c = match self.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some(kind @ ('u' | 'U')) => {
+                            let length = if kind == 'u' { 4 } else { 8 };
+                            let code: String = (0..length).filter_map(|_| self.next()).collect();
+                            u32::from_str_radix(&code, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| format!("Invalid escape '\\{}{}'", kind, code))?
+                        }
+                        other => {
+                            return Err(format!(
+                                "Invalid escape '\\{}'",
+                                other.map_or_else(String::new, String::from)
+                            ));
+                        }
+                    };
+                    value.push(c);
+                }
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    /// Reads a string in single quotes, without escapes.
+    fn parse_literal_string(&mut self) -> Result<String, String> {
+        self.position += 1;
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                None | Some('\n') => return Err("Unterminated string".to_string()),
+                Some('\'') => return Ok(value),
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    /// Expects the end of a line after an entry or header, allowing a comment.
+    fn end_line(&mut self) -> Result<(), String> {
+        self.skip_spaces();
+        self.skip_comment();
+        match self.peek() {
+            None => Ok(()),
+            Some('\n') => {
+                self.next();
+                Ok(())
+            }
+            Some('\r') if self.starts_with("\r\n") => {
+                self.next();
+                self.next();
+                Ok(())
+            }
+            Some(c) => Err(format!("Unexpected '{}' at the end of the line", c)),
+        }
+    }
+
+    /// Skips spaces, tabs, comments and line breaks.
+    fn skip_blank_lines(&mut self) {
+        loop {
+            self.skip_spaces();
+            self.skip_comment();
+            match self.peek() {
+                Some('\n' | '\r') => {
+                    self.next();
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Skips spaces and tabs.
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.position += 1;
+        }
+    }
+
+    /// Skips a comment up to the end of the line.
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while !matches!(self.peek(), None | Some('\n')) {
+                self.position += 1;
+            }
+        }
+    }
+
+    /// Returns the next character without reading it.
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    /// Reads the next character, counting lines.
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    /// Reads a character if it is the next one.
+    fn eat(&mut self, c: char) -> bool {
+        let matched = self.peek() == Some(c);
+        if matched {
+            self.position += 1;
+        }
+        matched
+    }
+
+    /// Checks whether the remaining text starts with `prefix`.
+    fn starts_with(&self, prefix: &str) -> bool {
+        prefix
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.position + i) == Some(&c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_document() {
+        let document = parse(
+            r#"
+            # A signup form
+            title = "Sign up"
+            retry.max_attempts = 3
+
+            [[fields]]
+            name = "email"
+            rules = [
+                "not_empty", # required
+                'validate_email',
+            ]
+
+            [[fields]]
+            name = "plan"
+            options = [{ value = 1, label = "Free é" }, { value = 2_000, label = "Pro" }]
+            ratio = -1.5
+            secret = false
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            document.to_json(),
+            r#"{"title":"Sign up","retry":{"max_attempts":3},"fields":[{"name":"email","rules":["not_empty","validate_email"]},{"name":"plan","options":[{"value":1,"label":"Free é"},{"value":2000,"label":"Pro"}],"ratio":-1.5,"secret":false}]}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("a = 1\na = 2"),
+            Err("Invalid TOML on line 2: Key 'a' is defined twice".to_string())
+        );
+        assert_eq!(
+            parse("name = \"open"),
+            Err("Invalid TOML on line 1: Unterminated string".to_string())
+        );
+        assert_eq!(
+            parse("when = 1979-05-27"),
+            Err("Invalid TOML on line 1: Unsupported value '1979-05-27'".to_string())
+        );
+        assert_eq!(
+            parse("[a]\nx = 1\n[a]"),
+            Err("Invalid TOML on line 3: Table 'a' is defined twice".to_string())
+        );
+    }
+}
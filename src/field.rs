@@ -1,8 +1,116 @@
-use crate::input::read_input;
+use crate::hooks::ErrorHook;
+use crate::input::{
+    ErrorCallback, InputError, PromptDetails, PromptOutput, parse_from_str, parse_optional,
+    read_parsed_input,
+};
+use crate::policy::{FieldPolicy, RetryPolicy, TimeoutPolicy};
+use crate::render::Renderer;
+use crate::transform::{Transformer, ValueTransformer, apply_transformers};
 use crate::validation::Validator;
-use std::fmt::Debug;
+use crate::value::{FieldKind, Value, ValueKind, ValueMap};
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::rc::Rc;
 use std::str::FromStr;
 
+/// The state of a form being filled, passed to each field.
+#[derive(Debug, Clone, Default)]
+pub struct FillContext {
+    /// The name of the field being filled or restored, empty outside a form.
+    pub field: String,
+    /// The values of the fields filled so far, including those of enclosing forms.
+    pub answers: ValueMap,
+    /// The retry policy of fields without their own policy.
+    pub retry: RetryPolicy,
+    /// The timeout of fields without their own timeout, if any.
+    pub timeout: Option<TimeoutPolicy>,
+    /// The hook told about invalid input, set by the form with the hook.
+    pub(crate) on_validation_error: Option<ErrorHook>,
+    /// The renderer drawing the prompts, or `None` for `AnsiRenderer`.
+    pub renderer: Option<Rc<dyn Renderer>>,
+    /// The stream the prompts are written to when stdin is used.
+    pub output: PromptOutput,
+}
+
+impl FillContext {
+    /// Tells the validation error hook of the form, if any, about invalid input.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field with invalid input.
+    /// * `message` - The error message shown to the user.
+    pub(crate) fn report_error(&self, name: &str, message: &str) {
+        if let Some(hook) = &self.on_validation_error {
+            hook.call(name, message, &self.answers);
+        }
+    }
+
+    /// Rejects the answer of the field being filled without a prompt.
+    ///
+    /// The validation error hook is told about the answer like about invalid
+    /// typed input.
+    ///
+    /// # Parameters
+    ///
+    /// * `message` - Why the answer is invalid.
+    ///
+    /// # Returns
+    ///
+    /// * The `FillError` naming the field, with `InputError::Invalid`.
+    pub(crate) fn reject(&self, message: String) -> FillError {
+        self.report_error(&self.field, &message);
+        FillError::Input {
+            field: self.field.clone(),
+            error: InputError::Invalid(message),
+        }
+    }
+}
+
+/// An error ending the filling of a field or form before it is complete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FillError {
+    /// The prompt of a field ended without an answer and its policy fails.
+    Input {
+        /// The name of the field.
+        field: String,
+        /// Why the prompt ended.
+        error: InputError,
+    },
+    /// A hook cancelled filling at the named field.
+    Cancelled(String),
+    /// Any other error, such as a draft that cannot be saved.
+    Other(String),
+}
+
+impl Display for FillError {
+    /// Formats the error as a message for the user.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FillError::Input { field, error } => write!(f, "Field '{}': {}", field, error),
+            FillError::Cancelled(field) => {
+                write!(f, "Filling was cancelled at field '{}'", field)
+            }
+            FillError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for FillError {}
+
+impl From<String> for FillError {
+    /// Wraps the message of an error that is not about input.
+    fn from(message: String) -> Self {
+        FillError::Other(message)
+    }
+}
+
+impl From<FillError> for String {
+    /// Converts the error to its message.
+    fn from(error: FillError) -> Self {
+        error.to_string()
+    }
+}
+
 /// A trait for form fields.
 pub trait FieldTrait {
     /// Fills the field by prompting the user for input.
@@ -13,6 +121,24 @@ pub trait FieldTrait {
     /// * `Err(String)` if there is an error filling the field.
     fn fill(&mut self) -> Result<(), String>;
 
+    /// Fills the field with access to the state of the form being filled.
+    ///
+    /// Fields that do not depend on earlier answers keep the default, which
+    /// calls `fill`.
+    ///
+    /// # Parameters
+    ///
+    /// * `context` - The state of the form, including the answers filled so far.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the field is successfully filled.
+    /// * `Err(FillError)` if there is an error filling the field.
+    fn fill_with_context(&mut self, context: &FillContext) -> Result<(), FillError> {
+        let _ = context;
+        Ok(self.fill()?)
+    }
+
     /// Returns a reference to the field as a `dyn Any`.
     ///
     /// # Returns
@@ -20,6 +146,163 @@ pub trait FieldTrait {
     /// * A reference to the field as a `dyn Any`.
     fn as_any(&self) -> &dyn std::any::Any;
 
+    /// Returns a mutable reference to the field as a `dyn Any`, so `FormBuilder` can configure it.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&mut dyn Any)` for the fields of this crate, `None` otherwise.
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        None
+    }
+
+    /// Returns the input transformers of the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&mut Vec<Transformer>)` for fields reading text input, `None` otherwise.
+    fn transformers_mut(&mut self) -> Option<&mut Vec<Transformer>> {
+        None
+    }
+
+    /// Returns the help text, placeholder and option descriptions of the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&FieldDetails)` for fields prompting the user directly, `None` otherwise.
+    fn details(&self) -> Option<&FieldDetails> {
+        None
+    }
+
+    /// Returns the help text, placeholder and option descriptions of the field for editing.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&mut FieldDetails)` for fields prompting the user directly, `None` otherwise.
+    fn details_mut(&mut self) -> Option<&mut FieldDetails> {
+        None
+    }
+
+    /// Checks whether the field holds a secret, which is hidden while typed and never saved in drafts.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the field is secret, `false` otherwise.
+    fn is_secret(&self) -> bool {
+        false
+    }
+
+    /// Returns the secret flag of the field for editing.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&mut bool)` for fields reading text input, `None` otherwise.
+    fn secret_mut(&mut self) -> Option<&mut bool> {
+        None
+    }
+
+    /// Gets the answer of the field as it is saved in drafts.
+    ///
+    /// # Returns
+    ///
+    /// * The raw input of text fields, the selected values of select fields,
+    ///   or `Value::Null` if the field has no answer.
+    fn draft_value(&self) -> Value {
+        Value::Null
+    }
+
+    /// Restores the answer of the field from a draft.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The answer returned by `draft_value`.
+    /// * `context` - The state of the form, including the answers restored so far.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the answer is successfully restored.
+    /// * `Err(String)` if the answer does not fit the field.
+    fn restore(&mut self, value: &Value, context: &FillContext) -> Result<(), String> {
+        let _ = value;
+        Err(format!("Field '{}' cannot be restored", context.field))
+    }
+
+    /// Fills the field from an answer given without prompting, such as one read from a file.
+    ///
+    /// Text answers are transformed, validated and parsed like typed input,
+    /// select answers are option values and multiselect answers lists of them.
+    ///
+    /// # Parameters
+    ///
+    /// * `answer` - The answer, or `Value::Null` if none was given.
+    /// * `context` - The state of the form, including the answers filled so far.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the field is filled from the answer.
+    /// * `Err(FillError)` with `InputError::Invalid` if the answer is invalid,
+    ///   or a message if the field cannot be answered without a prompt.
+    fn answer(&mut self, answer: &Value, context: &FillContext) -> Result<(), FillError> {
+        let _ = answer;
+        Err(FillError::Other(format!(
+            "Field '{}' cannot be answered without a prompt",
+            context.field
+        )))
+    }
+
+    /// Returns the retry policy and default answer of the field for editing.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&mut FieldPolicy)` for fields prompting the user directly, `None` otherwise.
+    fn policy_mut(&mut self) -> Option<&mut FieldPolicy> {
+        None
+    }
+
+    /// Describes the requirements valid input of the field has to meet.
+    ///
+    /// # Returns
+    ///
+    /// * A list of human-readable requirements, empty for fields without validation.
+    fn requirements(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns the validator checking the input of the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Validator)` for fields with a validator, `None` otherwise.
+    fn validator(&self) -> Option<&Validator> {
+        None
+    }
+
+    /// Checks whether the field needs an answer, rejecting empty input.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the field cannot be left empty, `false` otherwise.
+    fn is_required(&self) -> bool {
+        false
+    }
+
+    /// Checks whether the value of the field may be absent, as with `Option` and `Optional` fields.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if empty input leaves the field without a value, `false` otherwise.
+    fn is_optional(&self) -> bool {
+        false
+    }
+
+    /// Returns the maximum number of options that can be chosen.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` for multiselect fields with a limit, `None` otherwise.
+    fn selection_limit(&self) -> Option<usize> {
+        None
+    }
+
     /// Gets the value of the field as a string.
     ///
     /// # Returns
@@ -27,6 +310,99 @@ pub trait FieldTrait {
     /// * `Ok(String)` if the field value is successfully retrieved.
     /// * `Err(String)` if the field has no value.
     fn get_value(&self) -> Result<String, String>;
+
+    /// Returns the prompt of the field.
+    ///
+    /// # Returns
+    ///
+    /// * The prompt message displayed to the user, or the heading of a section.
+    ///   The default is empty.
+    fn prompt(&self) -> &str {
+        ""
+    }
+
+    /// Returns the kind of the field.
+    ///
+    /// # Returns
+    ///
+    /// * The `FieldKind` of the field. The default is a text input.
+    fn kind(&self) -> FieldKind {
+        FieldKind::Input(ValueKind::String)
+    }
+
+    /// Returns the options of the field.
+    ///
+    /// # Returns
+    ///
+    /// * A list of option values and labels, empty for fields without options.
+    fn options(&self) -> Vec<(Value, String)> {
+        Vec::new()
+    }
+
+    /// Gets the value of the field as a dynamically typed `Value`.
+    ///
+    /// # Returns
+    ///
+    /// * The value of the field, or `Value::Null` if the field has no value.
+    ///   The default is the text returned by `get_value`.
+    fn value(&self) -> Value {
+        self.get_value().map_or(Value::Null, Value::String)
+    }
+}
+
+/// Texts describing a field beyond its prompt.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldDetails {
+    /// Help text shown when the user types `?` or presses F1.
+    pub help: Option<String>,
+    /// An example shown dimmed in an empty text input.
+    pub placeholder: Option<String>,
+    /// Descriptions of the options of a select field, by option value.
+    pub option_descriptions: Vec<(Value, String)>,
+}
+
+impl FieldDetails {
+    /// Borrows the details as the details of a prompt.
+    ///
+    /// # Parameters
+    ///
+    /// * `requirements` - The requirements of valid input.
+    /// * `transformers` - The transformers applied to text input.
+    ///
+    /// # Returns
+    ///
+    /// * A `PromptDetails` instance referring to the details.
+    pub fn prompt_details<'a>(
+        &'a self,
+        requirements: &'a [String],
+        transformers: &'a [Transformer],
+    ) -> PromptDetails<'a> {
+        PromptDetails {
+            help: self.help.as_deref(),
+            requirements,
+            placeholder: self.placeholder.as_deref(),
+            descriptions: &self.option_descriptions,
+            transformers,
+            secret: false,
+            max_attempts: None,
+            timeout: None,
+            countdown: false,
+            on_error: None,
+            renderer: None,
+            output: PromptOutput::Stdout,
+        }
+    }
+}
+
+/// Names the kind of value a text field parses, for error messages.
+fn kind_name(kind: ValueKind) -> &'static str {
+    match kind {
+        ValueKind::Integer => "integer",
+        ValueKind::Float => "number",
+        ValueKind::Bool => "boolean (true or false)",
+        ValueKind::Char => "single character",
+        ValueKind::String | ValueKind::Other => "value",
+    }
 }
 
 /// A struct representing a form field.
@@ -38,12 +414,87 @@ pub struct Field<T> {
     pub validator: Option<Validator>,
     /// The value of the field.
     pub value: Option<T>,
+    /// The function converting the validated input to a value.
+    pub(crate) parse: fn(&str) -> Result<T, String>,
+    /// The transformers applied to the input before it is validated.
+    pub transformers: Vec<Transformer>,
+    /// The transformers applied to the value after it is parsed.
+    pub after_parse: Vec<ValueTransformer<T>>,
+    /// The help text and placeholder of the field.
+    pub details: FieldDetails,
+    /// The validated input the value was parsed from.
+    pub raw: Option<String>,
+    /// Whether the input is hidden while typed and excluded from drafts.
+    pub secret: bool,
+    /// The retry policy and default answer of the field.
+    pub policy: FieldPolicy,
 }
 
-impl<T> FieldTrait for Field<T>
+impl<T> Field<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    /// Creates a new, empty field parsing its input with `FromStr`.
+    ///
+    /// # Parameters
+    ///
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `validator` - An optional `Validator` instance to validate the input.
+    ///
+    /// # Returns
+    ///
+    /// * A new `Field` instance.
+    pub fn new(prompt: &str, validator: Option<Validator>) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            validator,
+            value: None,
+            parse: parse_from_str::<T>,
+            transformers: Vec::new(),
+            after_parse: Vec::new(),
+            details: FieldDetails::default(),
+            raw: None,
+            secret: false,
+            policy: FieldPolicy::default(),
+        }
+    }
+}
+
+impl<T> Field<Option<T>>
 where
-    T: 'static + FromStr + Debug + Clone + Default,
+    T: FromStr,
     T::Err: Debug,
+{
+    /// Creates a new, empty optional field, where empty input is stored as `None`.
+    ///
+    /// # Parameters
+    ///
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `validator` - An optional `Validator` instance to validate the input.
+    ///
+    /// # Returns
+    ///
+    /// * A new `Field` instance.
+    pub fn optional(prompt: &str, validator: Option<Validator>) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            validator,
+            value: None,
+            parse: parse_optional::<T>,
+            transformers: Vec::new(),
+            after_parse: Vec::new(),
+            details: FieldDetails::default(),
+            raw: None,
+            secret: false,
+            policy: FieldPolicy::default(),
+        }
+    }
+}
+
+impl<T> FieldTrait for Field<T>
+where
+    T: 'static + Debug + Clone,
 {
     /// Fills the field by prompting the user for input.
     ///
@@ -52,15 +503,38 @@ where
     /// * `Ok(())` if the field is successfully filled.
     /// * `Err(String)` if there is an error filling the field.
     fn fill(&mut self) -> Result<(), String> {
-        loop {
-            if let Ok(value) = read_input::<T>(&self.prompt, self.validator.as_ref()) {
-                self.value = Some(value);
-                break;
-            } else {
-                eprintln!("Invalid input. Please try again.");
+        Ok(self.fill_with_context(&FillContext::default())?)
+    }
+
+    /// Fills the field, applying its retry policy and timeout or those of the form.
+    fn fill_with_context(&mut self, context: &FillContext) -> Result<(), FillError> {
+        let requirements = self.requirements();
+        let report = |message: &str| context.report_error(&context.field, message);
+        let details = self.policy.prompt_details(
+            context,
+            PromptDetails {
+                secret: self.secret,
+                on_error: Some(ErrorCallback(&report)),
+                ..self
+                    .details
+                    .prompt_details(&requirements, &self.transformers)
+            },
+        );
+        match read_parsed_input(&self.prompt, self.validator.as_ref(), &details, self.parse) {
+            Ok((value, input)) => {
+                self.value = Some(self.apply_after_parse(value));
+                self.raw = Some(input);
+                Ok(())
             }
+            Err(error) => match self.policy.recover(&context.field, context, error)? {
+                Some(default) => Ok(self.restore(&default, context)?),
+                None => {
+                    self.value = None;
+                    self.raw = None;
+                    Ok(())
+                }
+            },
         }
-        Ok(())
     }
 
     /// Returns a reference to the field as a `dyn Any`.
@@ -72,6 +546,11 @@ where
         self
     }
 
+    /// Returns a mutable reference to the field as a `dyn Any`.
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+
     /// Gets the value of the field as a string.
     ///
     /// # Returns
@@ -84,12 +563,144 @@ where
             .ok_or_else(|| "Field has no value".to_string())
             .map(|v| format!("{:?}", v))
     }
+
+    /// Returns the input transformers of the field.
+    fn transformers_mut(&mut self) -> Option<&mut Vec<Transformer>> {
+        Some(&mut self.transformers)
+    }
+
+    /// Returns the help text and placeholder of the field.
+    fn details(&self) -> Option<&FieldDetails> {
+        Some(&self.details)
+    }
+
+    /// Returns the help text and placeholder of the field for editing.
+    fn details_mut(&mut self) -> Option<&mut FieldDetails> {
+        Some(&mut self.details)
+    }
+
+    /// Checks whether the field is secret.
+    fn is_secret(&self) -> bool {
+        self.secret
+    }
+
+    /// Returns the secret flag of the field for editing.
+    fn secret_mut(&mut self) -> Option<&mut bool> {
+        Some(&mut self.secret)
+    }
+
+    /// Gets the raw input of the field.
+    fn draft_value(&self) -> Value {
+        self.raw.clone().map_or(Value::Null, Value::String)
+    }
+
+    /// Restores the value of the field by parsing the raw input saved in a draft.
+    fn restore(&mut self, value: &Value, context: &FillContext) -> Result<(), String> {
+        let Value::String(input) = value else {
+            return Err(format!(
+                "Invalid draft answer for field '{}'",
+                context.field
+            ));
+        };
+        let value = (self.parse)(input)
+            .map_err(|e| format!("Invalid draft answer for field '{}': {}", context.field, e))?;
+        self.value = Some(self.apply_after_parse(value));
+        self.raw = Some(input.clone());
+        Ok(())
+    }
+
+    /// Fills the field from an answer, transforming, validating and parsing it like typed input.
+    fn answer(&mut self, answer: &Value, context: &FillContext) -> Result<(), FillError> {
+        let input = match answer {
+            Value::Null if self.is_required() => {
+                return Err(context.reject("Missing answer".to_string()));
+            }
+            Value::Null => String::new(),
+            Value::String(input) => input.clone(),
+            Value::Integer(_) | Value::Float(_) | Value::Bool(_) | Value::Char(_) => {
+                answer.to_string()
+            }
+            Value::List(_) | Value::Record(_) => {
+                return Err(context.reject("The answer must be a single value".to_string()));
+            }
+        };
+        let input = apply_transformers(&self.transformers, input.trim());
+        if let Some(validator) = &self.validator {
+            validator
+                .validate(&input)
+                .map_err(|err| context.reject(err))?;
+        }
+        let value = (self.parse)(&input).map_err(|_| {
+            context.reject(format!(
+                "'{}' is not a valid {}",
+                input,
+                kind_name(ValueKind::of::<T>())
+            ))
+        })?;
+        self.value = Some(self.apply_after_parse(value));
+        self.raw = Some(input);
+        Ok(())
+    }
+
+    /// Returns the retry policy and default answer of the field for editing.
+    fn policy_mut(&mut self) -> Option<&mut FieldPolicy> {
+        Some(&mut self.policy)
+    }
+
+    /// Describes the requirements of the field's validator.
+    fn requirements(&self) -> Vec<String> {
+        self.validator
+            .as_ref()
+            .map_or_else(Vec::new, Validator::requirements)
+    }
+
+    /// Returns the validator of the field.
+    fn validator(&self) -> Option<&Validator> {
+        self.validator.as_ref()
+    }
+
+    /// Checks whether empty input fails to parse or to validate.
+    fn is_required(&self) -> bool {
+        (self.parse)("").is_err()
+            || self
+                .validator
+                .as_ref()
+                .is_some_and(|validator| validator.validate("").is_err())
+    }
+
+    /// Checks whether empty input parses to a missing value.
+    fn is_optional(&self) -> bool {
+        (self.parse)("").is_ok_and(|value| Value::of(&value).is_null())
+    }
+
+    /// Returns the prompt of the field.
+    fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    /// Returns the kind of the field.
+    fn kind(&self) -> FieldKind {
+        FieldKind::Input(ValueKind::of::<T>())
+    }
+
+    /// Gets the value of the field as a dynamically typed `Value`.
+    fn value(&self) -> Value {
+        self.value.as_ref().map_or(Value::Null, Value::of)
+    }
+}
+
+impl<T> Field<T> {
+    /// Applies the transformers of the parsed value in order.
+    fn apply_after_parse(&self, value: T) -> T {
+        self.after_parse
+            .iter()
+            .fold(value, |value, transformer| transformer.apply(value))
+    }
 }
 
 impl<T> Field<T>
 where
-    T: 'static + FromStr + Debug + Clone,
-    T::Err: Debug,
+    T: Clone,
 {
     /// Gets the value of the field.
     ///
@@ -101,6 +712,6 @@ where
         self.value
             .as_ref()
             .ok_or_else(|| "Field has no value".to_string())
-            .map(|v| v.clone())
+            .cloned()
     }
 }
@@ -1,69 +1,665 @@
-use crate::field::{Field, FieldTrait};
+use crate::field::{Field, FieldTrait, FillContext, FillError};
+use crate::handle::FieldHandle;
+use crate::hooks::{Flow, Hooks};
+use crate::html;
+use crate::input::PromptOutput;
+use crate::json;
 use crate::multiselect_field::MultiselectField;
 use crate::optional::Optional;
+use crate::policy::{RetryPolicy, TimeoutPolicy};
+use crate::render::Renderer;
+use crate::repeat_field::RepeatField;
+use crate::schema;
+use crate::section_field::SectionField;
 use crate::select_field::SelectField;
-use std::collections::BTreeMap;
-use std::fmt::Debug;
-use std::str::FromStr;
+use crate::value::{Value, ValueMap};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Checks whether every field nested in a restored section or repeatable group was restored too.
+fn is_restored_completely(field: &dyn FieldTrait) -> bool {
+    if let Some(section) = field.as_any().downcast_ref::<SectionField>() {
+        section.form.is_complete()
+    } else if let Some(repeat) = field.as_any().downcast_ref::<RepeatField>() {
+        repeat.entries.iter().all(Form::is_complete)
+    } else {
+        true
+    }
+}
+
+/// Collects the answers of the enclosing form and of the given fields, in field order.
+fn context_answers(
+    enclosing: &ValueMap,
+    fields: &BTreeMap<u32, (String, Box<dyn FieldTrait>)>,
+    keys: &[u32],
+) -> ValueMap {
+    let mut answers = enclosing.clone();
+    for key in keys {
+        let (name, field) = &fields[key];
+        answers.insert(name, field.value());
+    }
+    answers
+}
 
 /// A struct representing a form with multiple fields.
 pub struct Form {
     /// A map of field order to field name and field trait object.
     pub fields: BTreeMap<u32, (String, Box<dyn FieldTrait>)>,
+    /// An optional path the draft is saved to after each field during `fill`.
+    pub autosave: Option<PathBuf>,
+    /// The retry policy of fields without their own policy, or `None` to use the enclosing form's.
+    pub retry: Option<RetryPolicy>,
+    /// The timeout of fields without their own timeout, or `None` to use the enclosing form's.
+    pub timeout: Option<TimeoutPolicy>,
+    /// The hooks run while the form is filled.
+    pub hooks: Hooks,
+    /// The renderer drawing the prompts, or `None` to use the enclosing form's.
+    pub renderer: Option<Rc<dyn Renderer>>,
+    /// The stream the prompts are written to, or `None` to use the enclosing form's.
+    pub prompt_output: Option<PromptOutput>,
+    /// The order of the fields filled so far, which `fill` skips when resuming.
+    filled: BTreeSet<u32>,
+    /// The order of the filled fields that hooks skipped, saved in drafts without an answer.
+    skipped: BTreeSet<u32>,
+    /// The id of the form, matched against the handles of its fields.
+    id: u64,
 }
 
 impl Form {
+    /// Creates a form from its fields.
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The id of the form, shared by the handles of its fields.
+    /// * `fields` - A map of field order to field name and field trait object.
+    ///
+    /// # Returns
+    ///
+    /// * A new `Form` instance without autosave.
+    pub(crate) fn new(id: u64, fields: BTreeMap<u32, (String, Box<dyn FieldTrait>)>) -> Self {
+        Self {
+            fields,
+            autosave: None,
+            retry: None,
+            timeout: None,
+            hooks: Hooks::default(),
+            renderer: None,
+            prompt_output: None,
+            filled: BTreeSet::new(),
+            skipped: BTreeSet::new(),
+            id,
+        }
+    }
+
     /// Fills all fields in the form by prompting the user for input.
     ///
     /// # Returns
     ///
     /// * `Ok(())` if all fields are successfully filled.
-    /// * `Err(String)` if there is an error filling any field.
-    pub fn fill(&mut self) -> Result<(), String> {
-        for (_order, (_name, field)) in &mut self.fields {
-            field.fill()?;
+    /// * `Err(FillError)` if there is an error filling any field.
+    pub fn fill(&mut self) -> Result<(), FillError> {
+        self.fill_with_context(&FillContext::default())
+    }
+
+    /// Fills all fields in the form, passing the answers filled so far to each field.
+    ///
+    /// The answers of the enclosing form are visible to the fields of a
+    /// section or repeated entry, next to the answers of their siblings.
+    ///
+    /// Fields restored with `load_draft` are skipped, so filling resumes at the
+    /// first unfilled field. If `autosave` is set, the draft is saved after
+    /// each field filled or skipped and removed once the form is complete.
+    ///
+    /// The `hooks` run around each field and may skip fields, jump to another
+    /// field or cancel filling. When filling stops early, the draft is kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `context` - The state of the enclosing form, or `FillContext::default()`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all fields are successfully filled.
+    /// * `Err(FillError)` with the field and input error if a prompt ended
+    ///   without an answer, or the field if a hook cancelled filling.
+    pub fn fill_with_context(&mut self, context: &FillContext) -> Result<(), FillError> {
+        self.fill_fields(context, None)
+    }
+
+    /// Fills all fields in the form from answers instead of prompting the user.
+    ///
+    /// Each answer goes through `FieldTrait::answer`, so text answers are
+    /// transformed, validated and parsed like typed input. Otherwise filling
+    /// works like `fill`: the hooks run around each field, may skip fields or
+    /// cancel filling, and the draft is saved if `autosave` is set.
+    ///
+    /// # Parameters
+    ///
+    /// * `answers` - The answers by field name. Missing answers are `Value::Null`,
+    ///   sections are answered with records and repeatable groups with lists of them.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all fields are successfully filled.
+    /// * `Err(FillError)` with `InputError::Invalid` for the first invalid
+    ///   answer, or a message if an answer names an unknown field.
+    pub fn fill_from_answers(&mut self, answers: &ValueMap) -> Result<(), FillError> {
+        self.fill_from_answers_with_context(answers, &FillContext::default())
+    }
+
+    /// Fills all fields in the form from answers, passing the answers filled so far to each field.
+    ///
+    /// # Parameters
+    ///
+    /// * `answers` - The answers by field name.
+    /// * `context` - The state of the enclosing form, or `FillContext::default()`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all fields are successfully filled.
+    /// * `Err(FillError)` if an answer is invalid or names an unknown field.
+    pub(crate) fn fill_from_answers_with_context(
+        &mut self,
+        answers: &ValueMap,
+        context: &FillContext,
+    ) -> Result<(), FillError> {
+        if let Some((name, _)) = answers
+            .iter()
+            .find(|(name, _)| !self.fields.values().any(|(field, _)| field == name))
+        {
+            return Err(FillError::Other(format!(
+                "Unknown field '{}' in the answers",
+                name
+            )));
+        }
+        self.fill_fields(context, Some(answers))
+    }
+
+    /// Fills the fields of the form by prompting or from answers, running the hooks around each field.
+    ///
+    /// # Parameters
+    ///
+    /// * `context` - The state of the enclosing form.
+    /// * `answers` - The answers to fill the fields from, or `None` to prompt the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all fields are successfully filled.
+    /// * `Err(FillError)` if there is an error filling any field.
+    fn fill_fields(
+        &mut self,
+        context: &FillContext,
+        answers: Option<&ValueMap>,
+    ) -> Result<(), FillError> {
+        if self.is_complete() {
+            self.filled.clear();
+            self.skipped.clear();
+        }
+
+        let enclosing = &context.answers;
+        let mut context = context.clone();
+        if let Some(retry) = self.retry {
+            context.retry = retry;
+        }
+        if self.timeout.is_some() {
+            context.timeout = self.timeout;
+        }
+        if self.renderer.is_some() {
+            context.renderer = self.renderer.clone();
+        }
+        if let Some(output) = self.prompt_output {
+            context.output = output;
+        }
+        if self.hooks.on_validation_error.is_some() {
+            context.on_validation_error = self.hooks.on_validation_error.clone();
+        }
+        let keys: Vec<u32> = self.fields.keys().copied().collect();
+        let mut index = 0;
+        let mut skip = false;
+        while index < keys.len() {
+            let key = keys[index];
+            let name = self.fields[&key].0.clone();
+            context.field = name.clone();
+            context.answers = context_answers(enclosing, &self.fields, &keys[..index]);
+            if self.filled.contains(&key) {
+                index += 1;
+                continue;
+            }
+
+            let flow = if std::mem::take(&mut skip) {
+                Flow::Skip
+            } else {
+                self.hooks.run_before_field(&name, &context.answers)
+            };
+            let flow = match flow {
+                Flow::Continue => {
+                    let (_, field) = self.fields.get_mut(&key).unwrap();
+                    let filled = match answers {
+                        Some(answers) => {
+                            field.answer(answers.get(&name).unwrap_or(&Value::Null), &context)
+                        }
+                        None => field.fill_with_context(&context),
+                    };
+                    if let Err(error) = filled {
+                        self.hooks.run_on_cancel(&name, &context.answers);
+                        return Err(error);
+                    }
+                    let value = field.value();
+                    context.answers.insert(&name, value.clone());
+                    self.filled.insert(key);
+                    self.skipped.remove(&key);
+                    if let Some(path) = &self.autosave {
+                        self.save_draft(path)?;
+                    }
+                    match self.hooks.run_after_field(&name, &value, &context.answers) {
+                        Flow::Skip => {
+                            skip = true;
+                            Flow::Continue
+                        }
+                        flow => flow,
+                    }
+                }
+                Flow::Skip => {
+                    self.filled.insert(key);
+                    self.skipped.insert(key);
+                    if let Some(path) = &self.autosave {
+                        self.save_draft(path)?;
+                    }
+                    Flow::Continue
+                }
+                flow => flow,
+            };
+
+            match flow {
+                Flow::Continue | Flow::Skip => index += 1,
+                Flow::Goto(target) => {
+                    let Some(position) = keys.iter().position(|key| self.fields[key].0 == target)
+                    else {
+                        return Err(FillError::Other(format!(
+                            "Cannot go to unknown field '{}'",
+                            target
+                        )));
+                    };
+                    if position > index {
+                        self.filled.extend(&keys[index..position]);
+                        self.skipped.extend(&keys[index..position]);
+                        if let Some(path) = &self.autosave {
+                            self.save_draft(path)?;
+                        }
+                    } else {
+                        for key in &keys[position..] {
+                            self.filled.remove(key);
+                            self.skipped.remove(key);
+                        }
+                    }
+                    index = position;
+                }
+                Flow::Cancel => {
+                    self.hooks.run_on_cancel(&name, &context.answers);
+                    return Err(FillError::Cancelled(name));
+                }
+            }
+        }
+        context.answers = context_answers(enclosing, &self.fields, &keys);
+        self.hooks.run_on_complete(&context.answers);
+
+        if let Some(path) = &self.autosave
+            && path.exists()
+        {
+            fs::remove_file(path)
+                .map_err(|e| format!("Failed to remove draft '{}': {}", path.display(), e))?;
         }
         Ok(())
     }
 
+    /// Checks whether every field of the form has been filled or restored.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the form is complete, `false` otherwise.
+    pub fn is_complete(&self) -> bool {
+        self.fields.keys().all(|key| self.filled.contains(key))
+    }
+
+    /// Computes a fingerprint of the form definition.
+    ///
+    /// The fingerprint covers the names, kinds and option labels of all
+    /// fields, including those of sections and repeatable groups, and changes
+    /// whenever the definition does.
+    ///
+    /// # Returns
+    ///
+    /// * The 64-bit FNV-1a hash of the definition as 16 hex digits.
+    pub fn fingerprint(&self) -> String {
+        let mut schema = String::new();
+        self.describe_schema(&mut schema);
+        let hash = schema
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+        format!("{:016x}", hash)
+    }
+
+    /// Appends a description of the fields of the form to `schema`.
+    fn describe_schema(&self, schema: &mut String) {
+        for (name, field) in self.fields.values() {
+            schema.push_str(&format!("{}:{:?}", name, field.kind()));
+            for (_, label) in field.options() {
+                schema.push_str(&format!("|{}", label));
+            }
+            if let Some(section) = field.as_any().downcast_ref::<SectionField>() {
+                schema.push('{');
+                section.form.describe_schema(schema);
+                schema.push('}');
+            } else if let Some(repeat) = field.as_any().downcast_ref::<RepeatField>() {
+                schema.push('{');
+                (repeat.template)().describe_schema(schema);
+                schema.push('}');
+            }
+            schema.push(';');
+        }
+    }
+
+    /// Exports the form definition as an HTML `<form>` element.
+    ///
+    /// Each field is written as a label with its prompt and a matching input:
+    /// a text, password or number input, a checkbox for `bool` fields, or a
+    /// `<select>` list for select and multiselect fields. Sections and
+    /// repeatable groups become `<fieldset>` elements, and the names of their
+    /// fields are dotted paths such as `address.city`.
+    ///
+    /// The `required`, `pattern`, `min` and `max` attributes are derived from
+    /// the built-in `ValidationMethods` and the named patterns of each
+    /// field's validator. Custom validation functions have no HTML
+    /// counterpart and are left out.
+    ///
+    /// # Returns
+    ///
+    /// * The HTML text of the form.
+    pub fn to_html(&self) -> String {
+        html::write(self)
+    }
+
+    /// Exports the JSON Schema of the values of the form.
+    ///
+    /// The schema is a JSON Schema 2020-12 object with a property for each
+    /// field, titled with its prompt:
+    ///
+    /// * Text fields have the type of their value and are listed in
+    ///   `required` unless they are optional, in which case `null` is
+    ///   allowed too. `not_empty` becomes `minLength`, and the built-in
+    ///   rules with a standard meaning become a `format`, such as `email`.
+    ///   The other rules based on a regular expression, named patterns and
+    ///   expressions added with `Validator::regex` become a `pattern`.
+    /// * Select fields list their options in `oneOf`, each a `const` value
+    ///   with its label as `title` and its description, if any.
+    /// * Multiselect fields are arrays of at least one unique option, with
+    ///   their limit as `maxItems`.
+    /// * Sections are nested objects, and repeatable groups are arrays of them.
+    ///
+    /// Help texts become descriptions, placeholders examples, and secret
+    /// fields are `writeOnly`. Custom validation functions have no JSON
+    /// Schema counterpart and are left out.
+    ///
+    /// # Returns
+    ///
+    /// * The schema as compact JSON text.
+    pub fn to_json_schema(&self) -> String {
+        schema::write(self)
+    }
+
+    /// Gets the draft answers of the filled fields, excluding secret fields.
+    ///
+    /// Fields skipped by hooks are saved as `null`, whatever value they kept.
+    ///
+    /// # Returns
+    ///
+    /// * A `ValueMap` of field names to the answers saved in drafts.
+    pub(crate) fn draft_answers(&self) -> ValueMap {
+        let mut answers = ValueMap::new();
+        for (key, (name, field)) in &self.fields {
+            if self.skipped.contains(key) {
+                answers.insert(name, Value::Null);
+            } else if self.filled.contains(key) && !field.is_secret() {
+                answers.insert(name, field.draft_value());
+            }
+        }
+        answers
+    }
+
+    /// Restores the fields with an answer in `answers`, in field order.
+    ///
+    /// Fields saved as `null` are marked as filled and keep no value.
+    ///
+    /// # Parameters
+    ///
+    /// * `answers` - The draft answers by field name.
+    /// * `context` - The state of the enclosing form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all answers are successfully restored.
+    /// * `Err(String)` if an answer does not fit its field.
+    pub(crate) fn restore_answers(
+        &mut self,
+        answers: &ValueMap,
+        context: &FillContext,
+    ) -> Result<(), String> {
+        let mut context = context.clone();
+        self.filled.clear();
+        self.skipped.clear();
+        for (key, (name, field)) in self.fields.iter_mut() {
+            match answers.get(name) {
+                // Fields left without an answer, such as skipped ones, are saved as `null`.
+                Some(Value::Null) => {
+                    self.filled.insert(*key);
+                    self.skipped.insert(*key);
+                }
+                Some(value) => {
+                    context.field = name.clone();
+                    field.restore(value, &context)?;
+                    if is_restored_completely(field.as_ref()) {
+                        self.filled.insert(*key);
+                    }
+                }
+                None => {}
+            }
+            context.answers.insert(name, field.value());
+        }
+        Ok(())
+    }
+
+    /// Saves the answers of the filled fields to a draft file.
+    ///
+    /// The draft is a JSON object with the `fingerprint` of the form and the
+    /// raw `answers` of its fields. Secret fields are never saved.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path of the draft file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the draft is successfully written.
+    /// * `Err(String)` if the file cannot be written.
+    pub fn save_draft(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let mut draft = ValueMap::new();
+        draft.insert("fingerprint", Value::String(self.fingerprint()));
+        draft.insert("answers", Value::Record(self.draft_answers()));
+        fs::write(path, json::write(&Value::Record(draft)))
+            .map_err(|e| format!("Failed to write draft '{}': {}", path.display(), e))
+    }
+
+    /// Restores the answers saved in a draft file, so `fill` resumes at the first unfilled field.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path of the draft file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the answers are successfully restored.
+    /// * `Err(String)` if the file cannot be read, was saved from a different
+    ///   form definition, or contains an answer that does not fit its field.
+    pub fn load_draft(&mut self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read draft '{}': {}", path.display(), e))?;
+        let Value::Record(draft) =
+            json::parse(&text).map_err(|e| format!("Invalid draft '{}': {}", path.display(), e))?
+        else {
+            return Err(format!("Invalid draft '{}'", path.display()));
+        };
+        if draft.get("fingerprint") != Some(&Value::String(self.fingerprint())) {
+            return Err(format!(
+                "Draft '{}' was saved from a different form definition",
+                path.display()
+            ));
+        }
+        let Some(Value::Record(answers)) = draft.get("answers") else {
+            return Err(format!("Invalid draft '{}'", path.display()));
+        };
+        self.restore_answers(answers, &FillContext::default())
+    }
+
+    /// Finds a field by its name.
+    ///
+    /// Names containing dots are resolved through sections, so `address.city`
+    /// refers to the `city` field of the `address` section.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name or dotted path of the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(&dyn FieldTrait)` if the field is found.
+    /// * `Err(String)` if the field is not found.
+    pub fn find_field(&self, name: &str) -> Result<&dyn FieldTrait, String> {
+        if let Some((_, field)) = self
+            .fields
+            .values()
+            .find(|(field_name, _)| field_name == name)
+        {
+            return Ok(field.as_ref());
+        }
+
+        name.split_once('.')
+            .and_then(|(section, rest)| {
+                let (_, field) = self
+                    .fields
+                    .values()
+                    .find(|(field_name, _)| field_name == section)?;
+                let section = field.as_any().downcast_ref::<SectionField>()?;
+                section.form.find_field(rest).ok()
+            })
+            .ok_or_else(|| format!("Field '{}' not found", name))
+    }
+
+    /// Gets the values of all fields as dynamically typed values.
+    ///
+    /// Sections are returned as `Value::Record` and repeatable groups as a
+    /// `Value::List` of records. Fields without a value are `Value::Null`.
+    ///
+    /// # Returns
+    ///
+    /// * A `ValueMap` of field names to values, in field order.
+    pub fn values(&self) -> ValueMap {
+        let mut values = ValueMap::new();
+        for (name, field) in self.fields.values() {
+            values.insert(name, field.value());
+        }
+        values
+    }
+
+    /// Formats the values of all fields as a list of `name: value` pairs.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` with the formatted values.
+    /// * `Err(String)` if any field has no value.
+    pub(crate) fn format_values(&self) -> Result<String, String> {
+        let values = self
+            .fields
+            .values()
+            .map(|(name, field)| Ok(format!("{}: {}", name, field.get_value()?)))
+            .collect::<Result<Vec<String>, String>>()?;
+        Ok(format!("{{{}}}", values.join(", ")))
+    }
+
     /// Gets the value of a field by its name.
     ///
     /// # Parameters
     ///
-    /// * `name` - The name of the field.
+    /// * `name` - The name or dotted path of the field.
     ///
     /// # Returns
     ///
-    /// * `Ok(T)` if the field value is successfully retrieved.
+    /// * `Ok(T)` if the field value is successfully retrieved, or `T::default()`
+    ///   for an optional field left empty; use `get_optional` to tell it apart
+    ///   from an entered value.
     /// * `Err(String)` if the field is not found or has an incorrect type.
     pub fn get_value<T>(&self, name: &str) -> Result<T, String>
     where
-        T: 'static + FromStr + Debug + Clone + Default + PartialEq,
-        T::Err: Debug,
+        T: 'static + Clone + Default,
     {
-        let field = self
-            .fields
-            .values()
-            .find(|(field_name, _)| field_name == name)
-            .ok_or_else(|| format!("Field '{}' not found", name))?
-            .1
-            .as_ref();
+        let field = self.find_field(name)?;
 
         if let Some(field) = field.as_any().downcast_ref::<Field<T>>() {
             field.get_value()
         } else if let Some(field) = field.as_any().downcast_ref::<Field<Optional<T>>>() {
-            field.get_value().map(|opt| match opt {
-                Optional::Some(value) => value,
-                Optional::None => T::default(),
-            })
+            field.get_value().map(Optional::unwrap_or_default)
+        } else if let Some(field) = field.as_any().downcast_ref::<Field<Option<T>>>() {
+            field.get_value().map(Option::unwrap_or_default)
         } else if let Some(field) = field.as_any().downcast_ref::<SelectField<T>>() {
-            field
-                .get_value()
-                .and_then(|v| v.parse::<T>().map_err(|e| format!("{:?}", e)))
+            field.get_value()
         } else if let Some(field) = field.as_any().downcast_ref::<MultiselectField<T>>() {
-            field
-                .get_value()
-                .and_then(|v| v.parse::<T>().map_err(|e| format!("{:?}", e)))
+            let mut values = field.get_value()?;
+            if values.len() == 1 {
+                Ok(values.remove(0))
+            } else {
+                Err(format!(
+                    "Field '{}' has multiple values, use get_value_vec instead",
+                    name
+                ))
+            }
+        } else {
+            Err(format!("Field '{}' has incorrect type", name))
+        }
+    }
+
+    /// Gets the value of a field by its name, keeping track of skipped optional fields.
+    ///
+    /// Unlike `get_value`, an optional field left empty is returned as `None`
+    /// instead of an error, so it can be told apart from an entered value.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name or dotted path of the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(T))` if the field has a value.
+    /// * `Ok(None)` if the field is optional and was left empty.
+    /// * `Err(String)` if the field is not found, has an incorrect type or has not been filled.
+    pub fn get_optional<T>(&self, name: &str) -> Result<Option<T>, String>
+    where
+        T: 'static + Clone,
+    {
+        let field = self.find_field(name)?;
+
+        if let Some(field) = field.as_any().downcast_ref::<Field<Option<T>>>() {
+            field.get_value()
+        } else if let Some(field) = field.as_any().downcast_ref::<Field<Optional<T>>>() {
+            field.get_value().map(Option::from)
+        } else if let Some(field) = field.as_any().downcast_ref::<Field<T>>() {
+            field.get_value().map(Some)
+        } else if let Some(field) = field.as_any().downcast_ref::<SelectField<T>>() {
+            field.get_value().map(Some)
         } else {
             Err(format!("Field '{}' has incorrect type", name))
         }
@@ -73,48 +669,73 @@ impl Form {
     ///
     /// # Parameters
     ///
-    /// * `name` - The name of the field.
+    /// * `name` - The name or dotted path of the field.
     ///
     /// # Returns
     ///
-    /// * `Ok(Vec<T>)` if the field value is successfully retrieved.
+    /// * `Ok(Vec<T>)` if the field value is successfully retrieved, empty for
+    ///   an optional field left empty.
     /// * `Err(String)` if the field is not found or has an incorrect type.
     pub fn get_value_vec<T>(&self, name: &str) -> Result<Vec<T>, String>
     where
-        T: 'static + FromStr + Debug + Clone + Default + PartialEq,
-        T::Err: Debug,
+        T: 'static + Clone,
     {
-        let field = self
-            .fields
-            .values()
-            .find(|(field_name, _)| field_name == name)
-            .ok_or_else(|| format!("Field '{}' not found", name))?
-            .1
-            .as_ref();
+        let field = self.find_field(name)?;
 
         if let Some(field) = field.as_any().downcast_ref::<Field<T>>() {
             Ok(vec![field.get_value()?])
         } else if let Some(field) = field.as_any().downcast_ref::<Field<Optional<T>>>() {
-            let value = field.get_value()?;
-            match value {
-                Optional::Some(v) => Ok(vec![v]),
-                Optional::None => Ok(vec![T::default()]),
-            }
+            Ok(Option::from(field.get_value()?).into_iter().collect())
+        } else if let Some(field) = field.as_any().downcast_ref::<Field<Option<T>>>() {
+            Ok(field.get_value()?.into_iter().collect())
         } else if let Some(field) = field.as_any().downcast_ref::<SelectField<T>>() {
-            let value = field
-                .get_value()
-                .and_then(|v| v.parse::<T>().map_err(|e| format!("{:?}", e)))?;
-            Ok(vec![value])
+            Ok(vec![field.get_value()?])
         } else if let Some(field) = field.as_any().downcast_ref::<MultiselectField<T>>() {
-            let value = field.get_value()?;
-            let value = value.trim_matches(|c| c == '[' || c == ']').to_string();
-            let values: Result<Vec<T>, _> = value
-                .split(',')
-                .map(|s| s.trim_matches(|c| c == '"' || c == ' ').parse::<T>())
-                .collect();
-            values.map_err(|e| format!("{:?}", e))
+            field.get_value()
         } else {
             Err(format!("Field '{}' has incorrect type", name))
         }
     }
+
+    /// Gets the entries of a repeatable group by its name.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name or dotted path of the repeatable group.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(&[Form])` with one filled form per entry.
+    /// * `Err(String)` if the field is not found or is not a repeatable group.
+    pub fn get_records(&self, name: &str) -> Result<&[Form], String> {
+        let field = self.find_field(name)?;
+
+        field
+            .as_any()
+            .downcast_ref::<RepeatField>()
+            .map(|field| field.entries.as_slice())
+            .ok_or_else(|| format!("Field '{}' is not a repeatable group", name))
+    }
+
+    /// Gets the value of a field through a typed handle.
+    ///
+    /// # Parameters
+    ///
+    /// * `handle` - A handle returned by one of the `add_typed_*` methods of `FormBuilder`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` if the field value is successfully retrieved.
+    /// * `Err(String)` if the field has not been filled yet, the handle belongs
+    ///   to another form, or the field no longer has the type of the handle.
+    pub fn get<T>(&self, handle: &FieldHandle<T>) -> Result<T, String> {
+        let (name, field) = self
+            .fields
+            .get(&handle.order)
+            .filter(|_| handle.form == self.id)
+            .ok_or_else(|| "Field handle does not belong to this form".to_string())?;
+        (handle.extract)(field.as_ref())
+            .ok_or_else(|| format!("Field '{}' does not have the type of the handle", name))?
+            .ok_or_else(|| format!("Field '{}' has no value", name))
+    }
 }
@@ -1,19 +1,49 @@
-use crate::field::Field;
 use crate::field::FieldTrait;
+use crate::field::{Field, FieldDetails};
 use crate::form::Form;
+use crate::handle::{
+    FieldHandle, extract_field, extract_multiselect, extract_select, next_form_id,
+};
+use crate::hooks::Hooks;
+use crate::input::PromptOutput;
 use crate::multiselect_field::MultiselectField;
+use crate::optional::Optional;
+use crate::policy::{FieldPolicy, RetryPolicy, TimeoutPolicy};
+use crate::provider::OptionProvider;
+use crate::render::Renderer;
+use crate::repeat_field::RepeatField;
+use crate::schema;
+use crate::section_field::SectionField;
 use crate::select_field::SelectField;
+use crate::transform::{Transformer, ValueTransformer};
 use crate::validation::Validator;
+use crate::value::{FieldKind, Value};
 use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::path::PathBuf;
+use std::rc::Rc;
 use std::str::FromStr;
 
 /// A builder for creating forms with various fields.
 pub struct FormBuilder {
+    /// The id of the built form, shared by the handles of its fields.
+    id: u64,
     /// A map of field order to field name and field trait object.
     fields: BTreeMap<u32, (String, Box<dyn FieldTrait>)>,
     /// A counter to keep track of the order of fields.
     counter: u32,
+    /// An optional path the draft of the built form is saved to while it is filled.
+    autosave: Option<PathBuf>,
+    /// The retry policy of fields without their own policy.
+    retry: Option<RetryPolicy>,
+    /// The timeout of fields without their own timeout.
+    timeout: Option<TimeoutPolicy>,
+    /// The hooks run while the built form is filled.
+    hooks: Hooks,
+    /// The renderer drawing the prompts of the built form.
+    renderer: Option<Rc<dyn Renderer>>,
+    /// The stream the prompts of the built form are written to.
+    prompt_output: Option<PromptOutput>,
 }
 
 impl FormBuilder {
@@ -24,13 +54,48 @@ impl FormBuilder {
     /// * A new `FormBuilder` instance.
     pub fn new() -> Self {
         Self {
+            id: next_form_id(),
             fields: BTreeMap::new(),
             counter: 0,
+            autosave: None,
+            retry: None,
+            timeout: None,
+            hooks: Hooks::default(),
+            renderer: None,
+            prompt_output: None,
         }
     }
 
+    /// Creates a builder with the fields described by a JSON Schema.
+    ///
+    /// This reads the subset of JSON Schema written by `Form::to_json_schema`.
+    /// String, integer, number and boolean properties become text fields of
+    /// `String`, `i64`, `f64` and `bool`, or `char` for strings of exactly one
+    /// character, and are optional unless they are `required` and do not
+    /// allow `null`. Options listed by `enum` or `oneOf` become select
+    /// fields, arrays of them with a `minItems` of 1, if any, multiselect
+    /// fields, objects sections and arrays of objects repeatable groups. Each
+    /// `pattern` is checked with `Validator::regex`, so no named pattern is
+    /// registered.
+    ///
+    /// # Parameters
+    ///
+    /// * `schema` - The JSON text of the schema.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FormBuilder)` with a field for each property, in order.
+    /// * `Err(String)` if the schema is not valid JSON, or names the first
+    ///   unsupported keyword, type or format and where it is used.
+    pub fn from_json_schema(schema: &str) -> Result<Self, String> {
+        schema::read(schema)
+    }
+
     /// Adds a field to the form.
     ///
+    /// Use `add_optional_field` for a standard `Option<T>` value, which cannot
+    /// be added here because `Option<T>` does not implement `FromStr`.
+    ///
     /// # Parameters
     ///
     /// * `name` - The name of the field.
@@ -53,11 +118,47 @@ impl FormBuilder {
             self.counter,
             (
                 name.to_string(),
-                Box::new(Field::<T> {
-                    prompt: prompt.to_string(),
-                    validator,
-                    value: None,
-                }),
+                Box::new(Field::<T>::new(prompt, validator)),
+            ),
+        );
+        self.counter += 1;
+        self
+    }
+
+    /// Adds an optional field to the form.
+    ///
+    /// Empty input is accepted and stored as `None`, so it can be told apart
+    /// from an entered value with `Form::get_optional` or
+    /// `Form::get_value::<Option<T>>`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field.
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `validator` - An optional `Validator` instance to validate the input.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the entered value. It must implement the `FromStr`, `Debug`, and `Clone` traits.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added field.
+    pub fn add_optional_field<T>(
+        mut self,
+        name: &str,
+        prompt: &str,
+        validator: Option<Validator>,
+    ) -> Self
+    where
+        T: 'static + FromStr + Debug + Clone,
+        T::Err: Debug,
+    {
+        self.fields.insert(
+            self.counter,
+            (
+                name.to_string(),
+                Box::new(Field::<Option<T>>::optional(prompt, validator)),
             ),
         );
         self.counter += 1;
@@ -74,15 +175,14 @@ impl FormBuilder {
     ///
     /// # Type Parameters
     ///
-    /// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, `Debug`, and `FromStr` traits.
+    /// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, and `Debug` traits.
     ///
     /// # Returns
     ///
     /// * The `FormBuilder` instance with the added select field.
     pub fn add_select<T>(mut self, name: &str, prompt: &str, options: Vec<(T, &str)>) -> Self
     where
-        T: 'static + Clone + PartialEq + Debug + FromStr,
-        T::Err: Debug,
+        T: 'static + Clone + PartialEq + Debug,
     {
         let options = options
             .into_iter()
@@ -96,6 +196,292 @@ impl FormBuilder {
                     prompt: prompt.to_string(),
                     options,
                     value: None,
+                    details: FieldDetails::default(),
+                    provider: None,
+                    policy: FieldPolicy::default(),
+                }),
+            ),
+        );
+        self.counter += 1;
+        self
+    }
+
+    /// Adds a select field whose options are loaded when it is filled.
+    ///
+    /// The provider receives the answers of the fields filled before, so the
+    /// options can depend on an earlier answer.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field.
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `provider` - The provider of the options, such as a closure taking `&ValueMap`.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, and `Debug` traits.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added select field.
+    pub fn add_select_with<T, P>(mut self, name: &str, prompt: &str, provider: P) -> Self
+    where
+        T: 'static + Clone + PartialEq + Debug,
+        P: OptionProvider<T> + 'static,
+    {
+        self.fields.insert(
+            self.counter,
+            (
+                name.to_string(),
+                Box::new(SelectField {
+                    prompt: prompt.to_string(),
+                    options: Vec::new(),
+                    value: None,
+                    details: FieldDetails::default(),
+                    provider: Some(Box::new(provider)),
+                    policy: FieldPolicy::default(),
+                }),
+            ),
+        );
+        self.counter += 1;
+        self
+    }
+
+    /// Adds a multiselect field to the form.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field.
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `options` - A list of options available for selection.
+    /// * `limit` - An optional limit on the number of selections.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, and `Debug` traits.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added multiselect field.
+    pub fn add_multiselect<T>(
+        mut self,
+        name: &str,
+        prompt: &str,
+        options: Vec<(T, &str)>,
+        limit: Option<usize>,
+    ) -> Self
+    where
+        T: 'static + Clone + PartialEq + Debug,
+    {
+        let options = options
+            .into_iter()
+            .map(|(v, s)| (v, s.to_string()))
+            .collect();
+        self.fields.insert(
+            self.counter,
+            (
+                name.to_string(),
+                Box::new(MultiselectField {
+                    prompt: prompt.to_string(),
+                    options,
+                    value: Vec::new(),
+                    limit,
+                    details: FieldDetails::default(),
+                    provider: None,
+                    policy: FieldPolicy::default(),
+                }),
+            ),
+        );
+        self.counter += 1;
+        self
+    }
+
+    /// Adds a multiselect field whose options are loaded when it is filled.
+    ///
+    /// The provider receives the answers of the fields filled before, so the
+    /// options can depend on an earlier answer.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field.
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `provider` - The provider of the options, such as a closure taking `&ValueMap`.
+    /// * `limit` - An optional limit on the number of selections.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, and `Debug` traits.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added multiselect field.
+    pub fn add_multiselect_with<T, P>(
+        mut self,
+        name: &str,
+        prompt: &str,
+        provider: P,
+        limit: Option<usize>,
+    ) -> Self
+    where
+        T: 'static + Clone + PartialEq + Debug,
+        P: OptionProvider<T> + 'static,
+    {
+        self.fields.insert(
+            self.counter,
+            (
+                name.to_string(),
+                Box::new(MultiselectField {
+                    prompt: prompt.to_string(),
+                    options: Vec::new(),
+                    value: Vec::new(),
+                    limit,
+                    details: FieldDetails::default(),
+                    provider: Some(Box::new(provider)),
+                    policy: FieldPolicy::default(),
+                }),
+            ),
+        );
+        self.counter += 1;
+        self
+    }
+
+    /// Adds a field to the form and returns a typed handle to its value.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field.
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `validator` - An optional `Validator` instance to validate the input.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the field value. It must implement the `FromStr`, `Debug`, `Clone`, and `Default` traits.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added field and a `FieldHandle<T>` for `Form::get`.
+    pub fn add_typed_field<T>(
+        self,
+        name: &str,
+        prompt: &str,
+        validator: Option<Validator>,
+    ) -> (Self, FieldHandle<T>)
+    where
+        T: 'static + FromStr + Debug + Clone + Default,
+        T::Err: Debug,
+    {
+        let handle = FieldHandle::new(self.id, self.counter, extract_field::<T>);
+        (self.add_field::<T>(name, prompt, validator), handle)
+    }
+
+    /// Adds a select field to the form and returns a typed handle to its value.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field.
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `options` - A list of options available for selection.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, and `Debug` traits.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added select field and a `FieldHandle<T>` for `Form::get`.
+    pub fn add_typed_select<T>(
+        self,
+        name: &str,
+        prompt: &str,
+        options: Vec<(T, &str)>,
+    ) -> (Self, FieldHandle<T>)
+    where
+        T: 'static + Clone + PartialEq + Debug,
+    {
+        let handle = FieldHandle::new(self.id, self.counter, extract_select::<T>);
+        (self.add_select(name, prompt, options), handle)
+    }
+
+    /// Adds a multiselect field to the form and returns a typed handle to its values.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field.
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `options` - A list of options available for selection.
+    /// * `limit` - An optional limit on the number of selections.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, and `Debug` traits.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added multiselect field and a `FieldHandle<Vec<T>>` for `Form::get`.
+    pub fn add_typed_multiselect<T>(
+        self,
+        name: &str,
+        prompt: &str,
+        options: Vec<(T, &str)>,
+        limit: Option<usize>,
+    ) -> (Self, FieldHandle<Vec<T>>)
+    where
+        T: 'static + Clone + PartialEq + Debug,
+    {
+        let handle = FieldHandle::new(self.id, self.counter, extract_multiselect::<T>);
+        (self.add_multiselect(name, prompt, options, limit), handle)
+    }
+
+    /// Adds a repeatable group of fields to the form.
+    ///
+    /// The user is asked for entries until they decline to add another one or
+    /// `max` entries have been collected. The first `min` entries are requested
+    /// without asking.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field.
+    /// * `prompt` - The prompt message to be displayed before the first entry.
+    /// * `template` - A function building the sub-form used for each entry.
+    /// * `min` - The minimum number of entries.
+    /// * `max` - An optional maximum number of entries.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added repeatable group.
+    ///
+    /// # Panics
+    ///
+    /// * If `min` is greater than `max`.
+    pub fn add_repeat<F>(
+        mut self,
+        name: &str,
+        prompt: &str,
+        template: F,
+        min: usize,
+        max: Option<usize>,
+    ) -> Self
+    where
+        F: Fn() -> Form + 'static,
+    {
+        if let Some(max) = max
+            && min > max
+        {
+            panic!(
+                "Field '{}' requires {} entries but allows at most {}",
+                name, min, max
+            );
+        }
+        self.fields.insert(
+            self.counter,
+            (
+                name.to_string(),
+                Box::new(RepeatField {
+                    prompt: prompt.to_string(),
+                    template: Box::new(template),
+                    min,
+                    max,
+                    entries: Vec::new(),
                 }),
             ),
         );
@@ -103,50 +489,454 @@ impl FormBuilder {
         self
     }
 
-    /// Adds a multiselect field to the form.
+    /// Adds a section containing a sub-form to the form.
+    ///
+    /// The heading and description are printed before the fields of the
+    /// sub-form, and its values are addressed with dotted paths such as
+    /// `address.city`. To reuse a section in several forms, build the sub-form
+    /// in a function and call it for each parent.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the section.
+    /// * `heading` - The heading to be displayed before the fields.
+    /// * `description` - An optional description to be displayed below the heading.
+    /// * `form` - The sub-form containing the fields of the section.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added section.
+    pub fn add_section(
+        mut self,
+        name: &str,
+        heading: &str,
+        description: Option<&str>,
+        form: Form,
+    ) -> Self {
+        self.fields.insert(
+            self.counter,
+            (
+                name.to_string(),
+                Box::new(SectionField {
+                    heading: heading.to_string(),
+                    description: description.map(str::to_string),
+                    form,
+                }),
+            ),
+        );
+        self.counter += 1;
+        self
+    }
+
+    /// Finds a field added to the builder by its name.
+    ///
+    /// # Panics
+    ///
+    /// * If no field with the name has been added.
+    fn field_mut(&mut self, name: &str) -> &mut dyn FieldTrait {
+        self.fields
+            .values_mut()
+            .find(|(field_name, _)| field_name == name)
+            .map(|(_, field)| field.as_mut())
+            .unwrap_or_else(|| panic!("Field '{}' has not been added", name))
+    }
+
+    /// Adds an input transformer to a field.
+    ///
+    /// Transformers run in the order they were added, after the input has
+    /// been trimmed and before it is validated and parsed.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of a field added with `add_field` or `add_optional_field`.
+    /// * `transformer` - The transformer, such as `Transformer::lowercase()`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the transformer added.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or does not read text input.
+    pub fn transform(mut self, name: &str, transformer: Transformer) -> Self {
+        self.field_mut(name)
+            .transformers_mut()
+            .unwrap_or_else(|| panic!("Field '{}' does not read text input", name))
+            .push(transformer);
+        self
+    }
+
+    /// Sets the help text of a field, shown when the user types `?` or presses F1.
+    ///
+    /// The requirements of the field's validator are listed below the help text.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of a field, select field or multiselect field.
+    /// * `help` - The help text.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the help text set.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or does not prompt the user directly.
+    pub fn help(mut self, name: &str, help: &str) -> Self {
+        self.details_mut(name).help = Some(help.to_string());
+        self
+    }
+
+    /// Sets the placeholder of a field, shown dimmed while its input is empty.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of a field added with `add_field` or `add_optional_field`.
+    /// * `placeholder` - An example of valid input, such as `"jane@example.com"`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the placeholder set.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or does not read text input.
+    pub fn placeholder(mut self, name: &str, placeholder: &str) -> Self {
+        let field = self.field_mut(name);
+        if field.transformers_mut().is_none() {
+            panic!("Field '{}' does not read text input", name);
+        }
+        field.details_mut().unwrap().placeholder = Some(placeholder.to_string());
+        self
+    }
+
+    /// Sets the description of an option of a select or multiselect field.
+    ///
+    /// The description is shown below the list while the option is highlighted.
+    /// Options loaded from a provider are matched by value when they are shown.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of a select or multiselect field.
+    /// * `option` - The value of the option.
+    /// * `description` - The description of the option.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the description set.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added, has no options, or has fixed options none of which has the value.
+    pub fn describe_option<T>(mut self, name: &str, option: T, description: &str) -> Self
+    where
+        T: 'static + Debug,
+    {
+        let value = self.option_value(name, &option);
+        let descriptions = &mut self.details_mut(name).option_descriptions;
+        descriptions.retain(|(option, _)| *option != value);
+        descriptions.push((value, description.to_string()));
+        self
+    }
+
+    /// Converts an option of a select or multiselect field to a `Value`.
+    ///
+    /// Fields whose options come from a provider have none until they are
+    /// filled, so their options are checked when they are loaded instead.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added, has no options, or has fixed options none of which has the value.
+    fn option_value<T>(&mut self, name: &str, option: &T) -> Value
+    where
+        T: 'static + Debug,
+    {
+        let field = self.field_mut(name);
+        if !matches!(field.kind(), FieldKind::Select | FieldKind::Multiselect) {
+            panic!("Field '{}' has no options", name);
+        }
+        let value = Value::of(option);
+        let options = field.options();
+        if !options.is_empty() && options.iter().all(|(option, _)| *option != value) {
+            panic!("Field '{}' has no option '{}'", name, value);
+        }
+        value
+    }
+
+    /// Marks a field as secret, hiding its input while typed and excluding it from drafts.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of a field added with `add_field` or `add_optional_field`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the field marked as secret.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or does not read text input.
+    pub fn secret(mut self, name: &str) -> Self {
+        *self
+            .field_mut(name)
+            .secret_mut()
+            .unwrap_or_else(|| panic!("Field '{}' does not read text input", name)) = true;
+        self
+    }
+
+    /// Saves a draft of the built form after each field while it is filled.
+    ///
+    /// The draft is removed once the form is complete. Load it with
+    /// `Form::load_draft` to resume an interrupted form.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path of the draft file.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with autosave enabled.
+    pub fn autosave(mut self, path: impl Into<PathBuf>) -> Self {
+        self.autosave = Some(path.into());
+        self
+    }
+
+    /// Sets the retry policy of a field, overriding the policy of the form.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of a field, select field or multiselect field.
+    /// * `policy` - The retry policy, such as `RetryPolicy::attempts(3)`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the retry policy set.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or does not prompt the user directly.
+    pub fn retry(mut self, name: &str, policy: RetryPolicy) -> Self {
+        self.policy_mut(name).retry = Some(policy);
+        self
+    }
+
+    /// Sets the retry policy of all fields without their own policy, including those of sections.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy` - The retry policy, such as `RetryPolicy::attempts(3)`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the retry policy set.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Sets the time the user has to answer a field, overriding the timeout of the form.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of a field, select field or multiselect field.
+    /// * `policy` - The timeout, such as `TimeoutPolicy::after(Duration::from_secs(30))`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the timeout set.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or does not prompt the user directly.
+    pub fn timeout(mut self, name: &str, policy: TimeoutPolicy) -> Self {
+        self.policy_mut(name).timeout = Some(policy);
+        self
+    }
+
+    /// Sets the time the user has to answer each field without its own timeout, including those of sections.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy` - The timeout, such as `TimeoutPolicy::after(Duration::from_secs(30))`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the timeout set.
+    pub fn timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeout = Some(policy);
+        self
+    }
+
+    /// Sets the hooks run while the form is filled.
+    ///
+    /// # Parameters
+    ///
+    /// * `hooks` - The hooks, such as `Hooks::new().on_complete(|answers| println!("{:?}", answers))`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the hooks set.
+    pub fn hooks(mut self, hooks: Hooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Sets the renderer drawing the prompts of the form, including those of sections.
+    ///
+    /// # Parameters
+    ///
+    /// * `renderer` - The renderer, such as `PlainRenderer`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the renderer set.
+    pub fn renderer(mut self, renderer: impl Renderer + 'static) -> Self {
+        self.renderer = Some(Rc::new(renderer));
+        self
+    }
+
+    /// Sets the stream the prompts of the form are written to when stdin is used.
+    ///
+    /// Prompts go to stdout by default. A program printing its results to
+    /// stdout writes the prompts to stderr instead. When stdin is redirected,
+    /// prompts are written to the controlling terminal either way.
+    ///
+    /// # Parameters
+    ///
+    /// * `output` - The stream, such as `PromptOutput::Stderr`.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the output set.
+    pub fn prompt_output(mut self, output: PromptOutput) -> Self {
+        self.prompt_output = Some(output);
+        self
+    }
+
+    /// Sets the default answer of a field, used by `Fallback::Default`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of a field added with `add_field` or `add_optional_field`.
+    /// * `input` - The default input, parsed like typed input.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the default answer set.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or does not read text input.
+    pub fn default_value(mut self, name: &str, input: &str) -> Self {
+        let field = self.field_mut(name);
+        if field.transformers_mut().is_none() {
+            panic!("Field '{}' does not read text input", name);
+        }
+        field.policy_mut().unwrap().default = Some(Value::String(input.to_string()));
+        self
+    }
+
+    /// Sets the default option of a select field, or adds one to the defaults of a multiselect field.
+    ///
+    /// Options loaded from a provider are checked when the default is applied.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of a select or multiselect field.
+    /// * `option` - The value of the option.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the default answer set.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added, has no options, or has fixed options none of which has the value.
+    pub fn default_option<T>(mut self, name: &str, option: T) -> Self
+    where
+        T: 'static + Debug,
+    {
+        let value = self.option_value(name, &option);
+        let kind = self.field_mut(name).kind();
+        let policy = self.policy_mut(name);
+        policy.default = match (kind, policy.default.take()) {
+            (FieldKind::Multiselect, Some(Value::List(mut values))) => {
+                values.push(value);
+                Some(Value::List(values))
+            }
+            (FieldKind::Multiselect, _) => Some(Value::List(vec![value])),
+            _ => Some(value),
+        };
+        self
+    }
+
+    /// Returns the retry policy and default answer of a field added to the builder.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or does not prompt the user directly.
+    fn policy_mut(&mut self, name: &str) -> &mut FieldPolicy {
+        self.field_mut(name)
+            .policy_mut()
+            .unwrap_or_else(|| panic!("Field '{}' does not prompt the user directly", name))
+    }
+
+    /// Returns the details of a field added to the builder.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or does not prompt the user directly.
+    fn details_mut(&mut self, name: &str) -> &mut FieldDetails {
+        self.field_mut(name)
+            .details_mut()
+            .unwrap_or_else(|| panic!("Field '{}' does not prompt the user directly", name))
+    }
+
+    /// Adds a transformer of the parsed value to a field.
+    ///
+    /// For optional fields, the transformer is applied to entered values only.
     ///
     /// # Parameters
     ///
-    /// * `name` - The name of the field.
-    /// * `prompt` - The prompt message to be displayed to the user.
-    /// * `options` - A list of options available for selection.
-    /// * `limit` - An optional limit on the number of selections.
+    /// * `name` - The name of a field added with `add_field` or `add_optional_field`.
+    /// * `transformer` - The function transforming the parsed value.
     ///
     /// # Type Parameters
     ///
-    /// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, `Debug`, and `FromStr` traits.
+    /// * `T` - The type of the field value, or of the entered value for optional fields.
     ///
     /// # Returns
     ///
-    /// * The `FormBuilder` instance with the added multiselect field.
-    pub fn add_multiselect<T>(
-        mut self,
-        name: &str,
-        prompt: &str,
-        options: Vec<(T, &str)>,
-        limit: Option<usize>,
-    ) -> Self
+    /// * The `FormBuilder` instance with the transformer added.
+    ///
+    /// # Panics
+    ///
+    /// * If the field has not been added or its value is not of type `T`.
+    pub fn after_parse<T, F>(mut self, name: &str, transformer: F) -> Self
     where
-        T: 'static + Clone + PartialEq + Debug + FromStr,
-        T::Err: Debug,
+        T: 'static,
+        F: Fn(T) -> T + Send + Sync + 'static,
     {
-        let options = options
-            .into_iter()
-            .map(|(v, s)| (v, s.to_string()))
-            .collect();
-        self.fields.insert(
-            self.counter,
-            (
-                name.to_string(),
-                Box::new(MultiselectField {
-                    prompt: prompt.to_string(),
-                    options,
-                    value: Vec::new(),
-                    limit,
-                }),
-            ),
-        );
-        self.counter += 1;
+        let Some(field) = self.field_mut(name).as_any_mut() else {
+            panic!("Field '{}' has incorrect type", name);
+        };
+        if let Some(field) = field.downcast_mut::<Field<T>>() {
+            field.after_parse.push(ValueTransformer::new(transformer));
+        } else if let Some(field) = field.downcast_mut::<Field<Option<T>>>() {
+            field
+                .after_parse
+                .push(ValueTransformer::new(move |value: Option<T>| {
+                    value.map(&transformer)
+                }));
+        } else if let Some(field) = field.downcast_mut::<Field<Optional<T>>>() {
+            field
+                .after_parse
+                .push(ValueTransformer::new(move |value: Optional<T>| {
+                    value.map(&transformer)
+                }));
+        } else {
+            panic!("Field '{}' has incorrect type", name);
+        }
         self
     }
 
@@ -156,17 +946,40 @@ impl FormBuilder {
     ///
     /// * A `Form` instance containing the added fields.
     pub fn build(self) -> Form {
-        Form {
-            fields: self.fields,
-        }
+        let mut form = Form::new(self.id, self.fields);
+        form.autosave = self.autosave;
+        form.retry = self.retry;
+        form.timeout = self.timeout;
+        form.hooks = self.hooks;
+        form.renderer = self.renderer;
+        form.prompt_output = self.prompt_output;
+        form
+    }
+}
+
+impl Default for FormBuilder {
+    /// Returns an empty `FormBuilder`, equivalent to `FormBuilder::new()`.
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::validation::{ValidationMethods, Validator};
+    use crate::field::{FillContext, FillError};
+    use crate::hooks::Flow;
+    use crate::input::{InputError, PromptOutput};
+    use crate::optional::Optional;
+    use crate::policy::Fallback;
+    use crate::render::{Frame, PlainRenderer, PromptKind};
+    use crate::testing::Script;
+    use crate::validation::{NamedRule, ValidationMethods, Validator};
+    use crate::value::{FieldKind, Value, ValueKind, ValueMap};
+    use std::cell::RefCell;
     use std::io::{BufRead, Cursor};
+    use std::rc::Rc;
+    use std::time::Duration;
 
     /// Sets up a name validator for testing.
     ///
@@ -329,4 +1142,1063 @@ mod tests {
         let form = form_builder.build();
         assert_eq!(form.fields.len(), 1);
     }
+
+    #[test]
+    fn test_add_repeat() {
+        let form_builder = FormBuilder::new().add_repeat(
+            "members",
+            "Team members:",
+            || {
+                FormBuilder::new()
+                    .add_field::<String>("name", "Enter name:", None)
+                    .add_field::<u32>("age", "Enter age:", None)
+                    .build()
+            },
+            1,
+            Some(3),
+        );
+        let form = form_builder.build();
+        assert_eq!(form.fields.len(), 1);
+        assert_eq!(form.get_records("members").map(|r| r.len()), Ok(0));
+    }
+
+    /// Builds a form with a repeatable group of team members for testing.
+    fn team_form(min: usize, max: Option<usize>) -> Form {
+        FormBuilder::new()
+            .add_repeat(
+                "members",
+                "Team members:",
+                || {
+                    FormBuilder::new()
+                        .add_field::<String>("name", "Enter name:", None)
+                        .build()
+                },
+                min,
+                max,
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_repeat_limits() {
+        let mut form = team_form(1, Some(2));
+        let (result, transcript) = Script::new()
+            .line("Ann")
+            .line("y")
+            .line("Bob")
+            .line("y")
+            .fill(&mut form);
+        assert_eq!(result, Ok(()));
+        assert_eq!(form.get_records("members").map(|r| r.len()), Ok(2));
+        assert_eq!(transcript.unused.len(), 2);
+        assert_eq!(
+            transcript
+                .frames
+                .iter()
+                .filter(|frame| frame.contains("(y/n)"))
+                .count(),
+            1
+        );
+
+        let mut form = team_form(0, None);
+        let (result, _) = Script::new().line("n").fill(&mut form);
+        assert_eq!(result, Ok(()));
+        assert_eq!(form.get_records("members").map(|r| r.len()), Ok(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Field 'members' requires 3 entries but allows at most 2")]
+    fn test_repeat_min_above_max() {
+        team_form(3, Some(2));
+    }
+
+    #[test]
+    fn test_typed_handles() {
+        let (form_builder, name) =
+            FormBuilder::new().add_typed_field::<String>("name", "Enter name:", None);
+        let (form_builder, gender) = form_builder.add_typed_select(
+            "gender",
+            "Select your gender:",
+            vec![(1u32, "Male"), (2u32, "Female")],
+        );
+        let (form_builder, hobbies) = form_builder.add_typed_multiselect(
+            "hobbies",
+            "Select your hobbies:",
+            vec![
+                ("reading".to_string(), "Reading"),
+                ("music".to_string(), "Music"),
+            ],
+            None,
+        );
+        let mut form = form_builder.build();
+        assert!(form.get(&name).is_err());
+        assert!(form.get(&hobbies).is_err());
+
+        form.fields.get_mut(&0).unwrap().1 = Box::new(Field {
+            value: Some("John".to_string()),
+            ..Field::<String>::new("Enter name:", None)
+        });
+        form.fields.get_mut(&1).unwrap().1 = Box::new(SelectField {
+            prompt: "Select your gender:".to_string(),
+            options: vec![(1u32, "Male".to_string()), (2u32, "Female".to_string())],
+            value: Some(2u32),
+            details: FieldDetails::default(),
+            provider: None,
+            policy: FieldPolicy::default(),
+        });
+        form.fields.get_mut(&2).unwrap().1 = Box::new(MultiselectField {
+            prompt: "Select your hobbies:".to_string(),
+            options: Vec::new(),
+            value: vec!["music".to_string()],
+            limit: None,
+            details: FieldDetails::default(),
+            provider: None,
+            policy: FieldPolicy::default(),
+        });
+
+        assert_eq!(form.get(&name), Ok("John".to_string()));
+        assert_eq!(form.get(&gender), Ok(2));
+        assert_eq!(form.get(&hobbies), Ok(vec!["music".to_string()]));
+
+        form.fields.get_mut(&0).unwrap().1 = Box::new(Field::<u32>::new("Age:", None));
+        assert_eq!(
+            form.get(&name),
+            Err("Field 'name' does not have the type of the handle".to_string())
+        );
+
+        let other = FormBuilder::new()
+            .add_field::<String>("name", "Enter name:", None)
+            .build();
+        assert_eq!(
+            other.get(&gender),
+            Err("Field handle does not belong to this form".to_string())
+        );
+    }
+
+    #[test]
+    fn test_select_values_are_lossless() {
+        let mut form = FormBuilder::new()
+            .add_select(
+                "gender",
+                "Select your gender:",
+                vec![("M".to_string(), "Male")],
+            )
+            .add_multiselect(
+                "tags",
+                "Select tags:",
+                vec![("a,b".to_string(), "A and B"), ("\"c\"".to_string(), "C")],
+                None,
+            )
+            .build();
+        form.fields.get_mut(&0).unwrap().1 = Box::new(SelectField {
+            prompt: "Select your gender:".to_string(),
+            options: vec![("M".to_string(), "Male".to_string())],
+            value: Some("M".to_string()),
+            details: FieldDetails::default(),
+            provider: None,
+            policy: FieldPolicy::default(),
+        });
+        form.fields.get_mut(&1).unwrap().1 = Box::new(MultiselectField {
+            prompt: "Select tags:".to_string(),
+            options: Vec::new(),
+            value: vec!["a,b".to_string(), "\"c\"".to_string()],
+            limit: None,
+            details: FieldDetails::default(),
+            provider: None,
+            policy: FieldPolicy::default(),
+        });
+
+        assert_eq!(form.get_value::<String>("gender"), Ok("M".to_string()));
+        assert_eq!(
+            form.get_value_vec::<String>("tags"),
+            Ok(vec!["a,b".to_string(), "\"c\"".to_string()])
+        );
+        assert!(form.get_value::<String>("tags").is_err());
+    }
+
+    #[test]
+    fn test_select_without_from_str() {
+        #[derive(Debug, Clone, Default, PartialEq)]
+        enum Size {
+            #[default]
+            Small,
+            Large,
+        }
+
+        let mut form = FormBuilder::new()
+            .add_select(
+                "size",
+                "Select size:",
+                vec![(Size::Small, "Small"), (Size::Large, "Large")],
+            )
+            .build();
+        assert!(form.get_value::<Size>("size").is_err());
+
+        form.fields.get_mut(&0).unwrap().1 = Box::new(SelectField {
+            prompt: "Select size:".to_string(),
+            options: Vec::new(),
+            value: Some(Size::Large),
+            details: FieldDetails::default(),
+            provider: None,
+            policy: FieldPolicy::default(),
+        });
+        assert_eq!(form.get_value::<Size>("size"), Ok(Size::Large));
+        assert_eq!(form.get_value_vec::<Size>("size"), Ok(vec![Size::Large]));
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let mut form = FormBuilder::new()
+            .add_optional_field::<u32>("width", "Enter width (optional):", None)
+            .add_field::<Optional<u32>>("height", "Enter height (optional):", None)
+            .add_field::<u32>("depth", "Enter depth:", None)
+            .build();
+        assert!(form.get_optional::<u32>("width").is_err());
+
+        let mut width = Field::<Option<u32>>::optional("Enter width (optional):", None);
+        width.value = Some((width.parse)("").unwrap());
+        form.fields.get_mut(&0).unwrap().1 = Box::new(width);
+        let mut height = Field::<Optional<u32>>::new("Enter height (optional):", None);
+        height.value = Some((height.parse)("").unwrap());
+        form.fields.get_mut(&1).unwrap().1 = Box::new(height);
+        let mut depth = Field::<u32>::new("Enter depth:", None);
+        depth.value = Some((depth.parse)("0").unwrap());
+        form.fields.get_mut(&2).unwrap().1 = Box::new(depth);
+
+        assert_eq!(form.get_optional::<u32>("width"), Ok(None));
+        assert_eq!(form.get_optional::<u32>("height"), Ok(None));
+        assert_eq!(form.get_optional::<u32>("depth"), Ok(Some(0)));
+        assert_eq!(form.get_value::<Option<u32>>("width"), Ok(None));
+        assert_eq!(form.get_value::<u32>("width"), Ok(0));
+        assert_eq!(form.get_value::<u32>("height"), Ok(0));
+        assert_eq!(form.get_value_vec::<u32>("height"), Ok(Vec::new()));
+        assert!(form.get_optional::<String>("depth").is_err());
+    }
+
+    /// Builds an address sub-form for testing.
+    ///
+    /// # Returns
+    ///
+    /// * A `Form` with street and city fields.
+    fn address_form() -> Form {
+        FormBuilder::new()
+            .add_field::<String>("street", "Enter street:", None)
+            .add_field::<String>("city", "Enter city:", None)
+            .build()
+    }
+
+    #[test]
+    fn test_add_section() {
+        let mut work = address_form();
+        work.fields.get_mut(&1).unwrap().1 = Box::new(Field {
+            value: Some("Berlin".to_string()),
+            ..Field::<String>::new("Enter city:", None)
+        });
+
+        let form = FormBuilder::new()
+            .add_field::<String>("name", "Enter name:", None)
+            .add_section("home", "Home address", None, address_form())
+            .add_section("work", "Work address", Some("Where do you work?"), work)
+            .build();
+        assert_eq!(form.fields.len(), 3);
+
+        assert_eq!(
+            form.get_value::<String>("work.city"),
+            Ok("Berlin".to_string())
+        );
+        assert!(form.get_value::<String>("home.city").is_err());
+        assert!(form.get_value::<String>("work.zip").is_err());
+        assert!(form.get_value::<String>("name.city").is_err());
+
+        let values = form.values();
+        assert_eq!(values.get("name"), Some(&Value::Null));
+        assert_eq!(values.get("home.city"), Some(&Value::Null));
+        assert_eq!(
+            values.get("work.city"),
+            Some(&Value::String("Berlin".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_field_trait_defaults() {
+        /// A field implementing only the required methods of `FieldTrait`.
+        struct Answer(Option<String>);
+
+        impl FieldTrait for Answer {
+            fn fill(&mut self) -> Result<(), String> {
+                self.0 = Some("42".to_string());
+                Ok(())
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn get_value(&self) -> Result<String, String> {
+                self.0
+                    .clone()
+                    .ok_or_else(|| "Field has no value".to_string())
+            }
+        }
+
+        let mut form = FormBuilder::new().build();
+        form.fields
+            .insert(0, ("answer".to_string(), Box::new(Answer(None))));
+        let field = form.find_field("answer").unwrap();
+        assert_eq!(field.prompt(), "");
+        assert_eq!(field.kind(), FieldKind::Input(ValueKind::String));
+        assert_eq!(field.value(), Value::Null);
+
+        assert_eq!(form.fill(), Ok(()));
+        assert_eq!(
+            form.values().get("answer"),
+            Some(&Value::String("42".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_field_introspection() {
+        let form = FormBuilder::new()
+            .add_field::<u32>("age", "Enter age:", None)
+            .add_select(
+                "gender",
+                "Select your gender:",
+                vec![(1u32, "Male"), (2u32, "Female")],
+            )
+            .add_multiselect(
+                "hobbies",
+                "Select your hobbies:",
+                vec![("reading".to_string(), "Reading")],
+                None,
+            )
+            .add_section("address", "Address", None, address_form())
+            .build();
+
+        let fields: Vec<(&str, &str, FieldKind)> = form
+            .fields
+            .values()
+            .map(|(name, field)| (name.as_str(), field.prompt(), field.kind()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("age", "Enter age:", FieldKind::Input(ValueKind::Integer)),
+                ("gender", "Select your gender:", FieldKind::Select),
+                ("hobbies", "Select your hobbies:", FieldKind::Multiselect),
+                ("address", "Address", FieldKind::Section),
+            ]
+        );
+        assert_eq!(
+            form.find_field("gender").unwrap().options(),
+            vec![
+                (Value::Integer(1), "Male".to_string()),
+                (Value::Integer(2), "Female".to_string())
+            ]
+        );
+        assert_eq!(
+            form.find_field("hobbies").unwrap().options(),
+            vec![(Value::String("reading".to_string()), "Reading".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_transformers() {
+        let build = || {
+            FormBuilder::new()
+                .add_field::<String>("email", "Enter email:", Some(setup_email_validator()))
+                .add_optional_field::<f64>("price", "Enter price (optional):", None)
+                .transform("email", Transformer::collapse_whitespace())
+                .transform("email", Transformer::lowercase())
+                .after_parse("email", |email: String| email.replace("-spam", ""))
+                .after_parse("price", |price: f64| (price * 100.0).round() / 100.0)
+                .build()
+        };
+
+        let mut form = build();
+        let (result, transcript) = Script::new()
+            .line(" Jane-Spam@Example.COM ")
+            .line("1.0051")
+            .fill(&mut form);
+        assert_eq!(result, Ok(()));
+        assert!(transcript.unused.is_empty());
+        assert_eq!(
+            form.get_value::<String>("email"),
+            Ok("jane@example.com".to_string())
+        );
+        assert_eq!(form.get_optional::<f64>("price"), Ok(Some(1.01)));
+
+        let mut form = build();
+        let (result, _) = Script::new()
+            .line("jane@example.com")
+            .enter()
+            .fill(&mut form);
+        assert_eq!(result, Ok(()));
+        assert_eq!(form.get_optional::<f64>("price"), Ok(None));
+    }
+
+    #[test]
+    #[should_panic(expected = "Field 'missing' has not been added")]
+    fn test_transform_unknown_field() {
+        let _ = FormBuilder::new().transform("missing", Transformer::trim());
+    }
+
+    #[test]
+    #[should_panic(expected = "Field 'size' does not read text input")]
+    fn test_transform_select_field() {
+        let _ = FormBuilder::new()
+            .add_select("size", "Select size:", vec![(1u8, "Small")])
+            .transform("size", Transformer::trim());
+    }
+
+    #[test]
+    fn test_field_details() {
+        let form = FormBuilder::new()
+            .add_field::<String>(
+                "email",
+                "Enter email:",
+                Some(Validator::named(NamedRule::Email, None)),
+            )
+            .add_select(
+                "plan",
+                "Select plan:",
+                vec![("free", "Free"), ("pro", "Pro"), ("team", "Team")],
+            )
+            .help("email", "Used for the login link.")
+            .placeholder("email", "jane@example.com")
+            .help("plan", "Plans can be changed later.")
+            .describe_option("plan", "team", "Shared billing")
+            .describe_option("plan", "free", "Up to three projects")
+            .build();
+
+        let email = &form.fields[&0].1;
+        assert_eq!(
+            email.details(),
+            Some(&FieldDetails {
+                help: Some("Used for the login link.".to_string()),
+                placeholder: Some("jane@example.com".to_string()),
+                option_descriptions: Vec::new(),
+            })
+        );
+        assert_eq!(email.requirements(), vec!["Must be a valid email address"]);
+
+        let plan = &form.fields[&1].1;
+        assert_eq!(
+            plan.details().unwrap().option_descriptions,
+            vec![
+                (
+                    Value::String("team".to_string()),
+                    "Shared billing".to_string()
+                ),
+                (
+                    Value::String("free".to_string()),
+                    "Up to three projects".to_string()
+                )
+            ]
+        );
+        assert!(plan.requirements().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Field 'plan' has no option 'gold'")]
+    fn test_describe_unknown_option() {
+        let _ = FormBuilder::new()
+            .add_select("plan", "Select plan:", vec![("free", "Free")])
+            .describe_option("plan", "gold", "Everything");
+    }
+
+    #[test]
+    fn test_provider_option_details() {
+        let mut form = FormBuilder::new()
+            .add_select_with("region", "Select region:", |_: &ValueMap| {
+                Ok(vec![
+                    ("BY", "Bavaria".to_string()),
+                    ("BE", "Berlin".to_string()),
+                ])
+            })
+            .describe_option("region", "BE", "The capital")
+            .describe_option("region", "HH", "Not loaded")
+            .add_multiselect_with(
+                "tags",
+                "Select tags:",
+                |_: &ValueMap| Ok(vec![(1u8, "New".to_string()), (2u8, "Old".to_string())]),
+                None,
+            )
+            .default_option("tags", 2u8)
+            .build();
+
+        let (result, transcript) = Script::new().down().enter().space().enter().fill(&mut form);
+        assert_eq!(result, Ok(()));
+        assert_eq!(form.get_value::<&str>("region"), Ok("BE"));
+        assert!(
+            transcript
+                .frames
+                .iter()
+                .any(|frame| frame.contains("The capital"))
+        );
+        assert!(
+            !transcript
+                .frames
+                .iter()
+                .any(|frame| frame.contains("Not loaded"))
+        );
+
+        let (_, field) = form.fields.get_mut(&1).unwrap();
+        let default = field.policy_mut().unwrap().default.clone().unwrap();
+        field.restore(&default, &FillContext::default()).unwrap();
+        assert_eq!(form.get_value_vec::<u8>("tags"), Ok(vec![2]));
+        let (_, field) = form.fields.get_mut(&1).unwrap();
+        let error = field
+            .restore(
+                &Value::List(vec![Value::Integer(3)]),
+                &FillContext::default(),
+            )
+            .unwrap_err();
+        assert!(error.contains("'3' is not an option"), "{}", error);
+    }
+
+    #[test]
+    #[should_panic(expected = "Field 'plan' does not read text input")]
+    fn test_placeholder_select_field() {
+        let _ = FormBuilder::new()
+            .add_select("plan", "Select plan:", vec![("free", "Free")])
+            .placeholder("plan", "Free");
+    }
+
+    #[test]
+    fn test_option_providers() {
+        let form = FormBuilder::new()
+            .add_select(
+                "country",
+                "Select country:",
+                vec![("PL", "Poland"), ("DE", "Germany")],
+            )
+            .add_select_with(
+                "region",
+                "Select region:",
+                |answers: &ValueMap| match answers.get("country") {
+                    Some(Value::String(country)) if country == "PL" => {
+                        Ok(vec![("MZ", "Masovia".to_string())])
+                    }
+                    Some(Value::String(country)) if country == "DE" => Ok(vec![
+                        ("BY", "Bavaria".to_string()),
+                        ("BE", "Berlin".to_string()),
+                    ]),
+                    _ => Err("Select a country first".to_string()),
+                },
+            )
+            .add_multiselect_with(
+                "tags",
+                "Select tags:",
+                |_: &ValueMap| Ok(vec![(1u8, "New".to_string())]),
+                None,
+            )
+            .build();
+
+        let region = form.fields[&1]
+            .1
+            .as_any()
+            .downcast_ref::<SelectField<&str>>()
+            .unwrap();
+        assert!(region.options.is_empty());
+        let provider = region.provider.as_ref().unwrap();
+        let mut answers = ValueMap::new();
+        assert!(provider.options(&answers).is_err());
+        answers.insert("country", Value::String("DE".to_string()));
+        assert_eq!(provider.options(&answers).unwrap().len(), 2);
+
+        let tags = form.fields[&2]
+            .1
+            .as_any()
+            .downcast_ref::<MultiselectField<u8>>()
+            .unwrap();
+        assert!(tags.provider.is_some());
+        assert!(format!("{:?}", tags).contains("provider: true"));
+    }
+
+    fn signup_form() -> Form {
+        FormBuilder::new()
+            .add_field::<String>("name", "Enter name:", None)
+            .add_field::<String>("password", "Enter password:", None)
+            .secret("password")
+            .add_optional_field::<u32>("age", "Enter age (optional):", None)
+            .add_select("plan", "Select plan:", vec![(1u8, "Free"), (2u8, "Pro")])
+            .add_section("home", "Home address", None, address_form())
+            .add_multiselect(
+                "tags",
+                "Select tags:",
+                vec![("a".to_string(), "A"), ("b".to_string(), "B")],
+                None,
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_drafts() {
+        let path =
+            std::env::temp_dir().join(format!("form_builder_draft_{}.json", std::process::id()));
+        let mut form = signup_form();
+        form.autosave = Some(path.clone());
+        let (result, _) = Script::new()
+            .line("Ada")
+            .line("hunter2")
+            .enter()
+            .down()
+            .enter()
+            .line("Main St")
+            .line("Springfield")
+            .fill(&mut form);
+        assert_eq!(
+            result,
+            Err(FillError::Input {
+                field: "tags".to_string(),
+                error: InputError::Eof,
+            })
+        );
+        assert!(!form.is_complete());
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("hunter2"));
+        assert!(text.contains(&form.fingerprint()));
+
+        let mut resumed = signup_form();
+        resumed.autosave = Some(path.clone());
+        resumed.load_draft(&path).unwrap();
+        assert_eq!(resumed.get_value::<String>("name"), Ok("Ada".to_string()));
+        assert_eq!(resumed.get_optional::<u32>("age"), Ok(None));
+        assert_eq!(resumed.get_value::<u8>("plan"), Ok(2));
+        assert_eq!(
+            resumed.get_value::<String>("home.street"),
+            Ok("Main St".to_string())
+        );
+        assert!(resumed.find_field("password").unwrap().value().is_null());
+        let draft = resumed.draft_answers();
+        let filled: Vec<&str> = draft.iter().map(|(name, _)| name).collect();
+        assert_eq!(filled, vec!["name", "age", "plan", "home"]);
+
+        let mut changed = FormBuilder::new()
+            .add_field::<String>("name", "Enter name:", None)
+            .build();
+        assert_ne!(changed.fingerprint(), signup_form().fingerprint());
+        assert!(
+            changed
+                .load_draft(&path)
+                .unwrap_err()
+                .contains("different form definition")
+        );
+
+        let (result, transcript) = Script::new()
+            .line("hunter2")
+            .space()
+            .enter()
+            .fill(&mut resumed);
+        assert_eq!(result, Ok(()));
+        assert_eq!(transcript.frames[0], "Enter password: ");
+        assert_eq!(resumed.get_value::<String>("name"), Ok("Ada".to_string()));
+        assert_eq!(
+            resumed.get_value_vec::<String>("tags"),
+            Ok(vec!["a".to_string()])
+        );
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_restore_invalid_answer() {
+        let mut answers = ValueMap::new();
+        answers.insert("plan", Value::Integer(3));
+        let error = signup_form()
+            .restore_answers(&answers, &FillContext::default())
+            .unwrap_err();
+        assert_eq!(error, "'3' is not an option of field 'plan'");
+    }
+
+    #[test]
+    fn test_retry_policies_and_defaults() {
+        let mut form = FormBuilder::new()
+            .add_field::<u32>("age", "Enter age:", None)
+            .retry(
+                "age",
+                RetryPolicy::attempts(2).on_exhausted(Fallback::Default),
+            )
+            .default_value("age", "30")
+            .add_select("plan", "Select plan:", vec![(1u8, "Free"), (2u8, "Pro")])
+            .default_option("plan", 2u8)
+            .add_multiselect(
+                "tags",
+                "Select tags:",
+                vec![("a", "A"), ("b", "B"), ("c", "C")],
+                None,
+            )
+            .default_option("tags", "a")
+            .default_option("tags", "c")
+            .retry_policy(RetryPolicy::attempts(3).on_exhausted(Fallback::Skip))
+            .build();
+        assert_eq!(
+            form.retry,
+            Some(RetryPolicy::attempts(3).on_exhausted(Fallback::Skip))
+        );
+
+        let context = FillContext::default();
+        let policies: Vec<FieldPolicy> = form
+            .fields
+            .values_mut()
+            .map(|(_, field)| field.policy_mut().unwrap().clone())
+            .collect();
+        assert_eq!(policies[0].retry(&context).max_attempts, Some(2));
+        assert_eq!(policies[1].retry, None);
+        assert_eq!(policies[1].default, Some(Value::Integer(2)));
+        assert_eq!(
+            policies[2].default,
+            Some(Value::List(vec![
+                Value::String("a".to_string()),
+                Value::String("c".to_string())
+            ]))
+        );
+
+        for (key, policy) in policies.iter().enumerate() {
+            let (_, field) = form.fields.get_mut(&(key as u32)).unwrap();
+            let default = policy.default.clone().unwrap();
+            field.restore(&default, &context).unwrap();
+        }
+        assert_eq!(form.get_value::<u32>("age"), Ok(30));
+        assert_eq!(form.get_value::<u8>("plan"), Ok(2));
+        assert_eq!(form.get_value_vec::<&str>("tags"), Ok(vec!["a", "c"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Field 'home' does not prompt the user directly")]
+    fn test_retry_section() {
+        let _ = FormBuilder::new()
+            .add_section("home", "Home address", None, address_form())
+            .retry("home", RetryPolicy::attempts(1));
+    }
+
+    #[test]
+    fn test_skipped_fields_in_drafts() {
+        let path =
+            std::env::temp_dir().join(format!("form_builder_skipped_{}.json", std::process::id()));
+        let build = || {
+            FormBuilder::new()
+                .add_field::<u32>("age", "Enter age:", None)
+                .retry("age", RetryPolicy::attempts(1).on_exhausted(Fallback::Skip))
+                .add_field::<String>("name", "Enter name:", None)
+                .autosave(&path)
+                .build()
+        };
+
+        let mut form = build();
+        let (result, _) = Script::new().line("old").fill(&mut form);
+        assert_eq!(
+            result,
+            Err(FillError::Input {
+                field: "name".to_string(),
+                error: InputError::Eof,
+            })
+        );
+        assert!(form.find_field("age").unwrap().value().is_null());
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("\"age\":null")
+        );
+
+        let mut resumed = build();
+        resumed.load_draft(&path).unwrap();
+        assert!(resumed.find_field("age").unwrap().value().is_null());
+        let (result, transcript) = Script::new().line("Ada").fill(&mut resumed);
+        assert_eq!(result, Ok(()));
+        assert_eq!(transcript.frames, vec!["Enter name: "]);
+        assert_eq!(resumed.get_value::<String>("name"), Ok("Ada".to_string()));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let timeout = TimeoutPolicy::after(Duration::from_secs(10)).on_timeout(Fallback::Default);
+        let mut form = FormBuilder::new()
+            .add_field::<u32>("age", "Enter age:", None)
+            .timeout("age", timeout.with_countdown())
+            .add_select("plan", "Select plan:", vec![(1u8, "Free"), (2u8, "Pro")])
+            .timeout_policy(timeout)
+            .build();
+        assert_eq!(form.timeout, Some(timeout));
+
+        let context = FillContext {
+            timeout: form.timeout,
+            ..FillContext::default()
+        };
+        let policies: Vec<Option<TimeoutPolicy>> = form
+            .fields
+            .values_mut()
+            .map(|(_, field)| field.policy_mut().unwrap().timeout(&context))
+            .collect();
+        assert_eq!(
+            policies,
+            vec![Some(timeout.with_countdown()), Some(timeout)]
+        );
+    }
+
+    #[test]
+    fn test_hooks() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let hooks = |log: &Rc<RefCell<Vec<String>>>| {
+            let (before, after, complete, cancel) =
+                (log.clone(), log.clone(), log.clone(), log.clone());
+            Hooks::new()
+                .before_field(move |name, answers| {
+                    before
+                        .borrow_mut()
+                        .push(format!("before {} {}", name, answers.len()));
+                    match name {
+                        "name" => Flow::Goto("plan".to_string()),
+                        "plan" => Flow::Skip,
+                        "home" => Flow::Cancel,
+                        _ => Flow::Goto("missing".to_string()),
+                    }
+                })
+                .after_field(move |name, _, _| {
+                    after.borrow_mut().push(format!("after {}", name));
+                    Flow::Continue
+                })
+                .on_complete(move |answers| {
+                    complete
+                        .borrow_mut()
+                        .push(format!("complete {}", answers.len()));
+                })
+                .on_cancel(move |name, _| cancel.borrow_mut().push(format!("cancel {}", name)))
+        };
+
+        let mut form = signup_form();
+        form.hooks = hooks(&log);
+        assert_eq!(form.fill(), Err(FillError::Cancelled("home".to_string())));
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "before name 0",
+                "before plan 3",
+                "before home 4",
+                "cancel home"
+            ]
+        );
+        assert!(!form.is_complete());
+
+        log.borrow_mut().clear();
+        let mut form = FormBuilder::new()
+            .add_field::<String>("plan", "Enter plan:", None)
+            .add_field::<String>("other", "Enter other:", None)
+            .hooks(hooks(&log))
+            .build();
+        assert_eq!(
+            form.fill(),
+            Err(FillError::Other(
+                "Cannot go to unknown field 'missing'".to_string()
+            ))
+        );
+
+        log.borrow_mut().clear();
+        let mut form = FormBuilder::new()
+            .add_field::<String>("plan", "Enter plan:", None)
+            .hooks(hooks(&log))
+            .build();
+        assert_eq!(form.fill(), Ok(()));
+        assert!(form.is_complete());
+        assert_eq!(*log.borrow(), vec!["before plan 0", "complete 1"]);
+    }
+
+    #[test]
+    fn test_skipped_by_hooks_in_drafts() {
+        let path =
+            std::env::temp_dir().join(format!("form_builder_hooks_{}.json", std::process::id()));
+        let mut form = signup_form();
+        form.autosave = Some(path.clone());
+        form.hooks = Hooks::new().before_field(|name, _| match name {
+            "age" => Flow::Skip,
+            "home" => Flow::Goto("tags".to_string()),
+            _ => Flow::Continue,
+        });
+        let (result, _) = Script::new()
+            .line("Ada")
+            .line("hunter2")
+            .enter()
+            .fill(&mut form);
+        assert_eq!(
+            result,
+            Err(FillError::Input {
+                field: "tags".to_string(),
+                error: InputError::Eof,
+            })
+        );
+        let draft = form.draft_answers();
+        assert_eq!(draft.get("age"), Some(&Value::Null));
+        assert_eq!(draft.get("home"), Some(&Value::Null));
+
+        let mut resumed = signup_form();
+        resumed.autosave = Some(path.clone());
+        resumed.load_draft(&path).unwrap();
+        assert_eq!(resumed.get_value::<u8>("plan"), Ok(1));
+        assert!(resumed.find_field("age").unwrap().value().is_null());
+        assert_eq!(resumed.draft_answers(), draft);
+        let (result, transcript) = Script::new()
+            .line("hunter2")
+            .space()
+            .enter()
+            .fill(&mut resumed);
+        assert_eq!(result, Ok(()));
+        assert_eq!(transcript.frames[0], "Enter password: ");
+        assert!(resumed.find_field("home.street").unwrap().value().is_null());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_renderer() {
+        let form = FormBuilder::new()
+            .add_field::<u32>("age", "Enter age:", None)
+            .renderer(PlainRenderer)
+            .build();
+        let context = FillContext {
+            renderer: form.renderer.clone(),
+            ..FillContext::default()
+        };
+        let field_details = FieldDetails::default();
+        let details =
+            FieldPolicy::default().prompt_details(&context, field_details.prompt_details(&[], &[]));
+        let frame = Frame::new(PromptKind::Text, "Enter age:");
+        assert_eq!(details.renderer.unwrap().render(&frame), "Enter age: ");
+    }
+
+    #[test]
+    fn test_prompt_output() {
+        let mut form = FormBuilder::new()
+            .add_section(
+                "home",
+                "Home",
+                None,
+                FormBuilder::new()
+                    .add_field::<String>("street", "Enter street:", None)
+                    .build(),
+            )
+            .prompt_output(PromptOutput::Stderr)
+            .build();
+        assert_eq!(form.prompt_output, Some(PromptOutput::Stderr));
+        let context = FillContext {
+            output: PromptOutput::Stderr,
+            ..FillContext::default()
+        };
+        let field_details = FieldDetails::default();
+        let details =
+            FieldPolicy::default().prompt_details(&context, field_details.prompt_details(&[], &[]));
+        assert_eq!(details.output, PromptOutput::Stderr);
+
+        let (result, transcript) = Script::new().line("Main St").fill(&mut form);
+        assert_eq!(result, Ok(()));
+        assert_eq!(transcript.frames, vec!["\n== Home ==\n", "Enter street: "]);
+    }
+
+    #[test]
+    fn test_validation_error_hook() {
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let log = errors.clone();
+        let hooks = Hooks::new().on_validation_error(move |name, message, answers| {
+            log.borrow_mut()
+                .push(format!("{}: {} ({})", name, message, answers.len()));
+        });
+
+        let mut context = FillContext {
+            on_validation_error: hooks.on_validation_error.clone(),
+            ..FillContext::default()
+        };
+        context
+            .answers
+            .insert("name", Value::String("Ann".to_string()));
+        context.report_error("age", "Invalid input. Please try again.");
+        FillContext::default().report_error("age", "Ignored");
+        assert_eq!(
+            *errors.borrow(),
+            vec!["age: Invalid input. Please try again. (1)"]
+        );
+    }
+
+    #[test]
+    fn test_fill_from_answers() {
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let log = errors.clone();
+        let hooks = Hooks::new()
+            .after_field(|name, value, _| {
+                if name == "country" && *value == Value::String("NL".to_string()) {
+                    Flow::Skip
+                } else {
+                    Flow::Continue
+                }
+            })
+            .on_validation_error(move |name, message, _| {
+                log.borrow_mut().push(format!("{}: {}", name, message));
+            });
+        let build = || {
+            FormBuilder::new()
+                .add_field::<String>("country", "Enter country:", None)
+                .transform("country", Transformer::uppercase())
+                .add_field::<String>("state", "Enter state:", None)
+                .add_section(
+                    "home",
+                    "Home",
+                    None,
+                    FormBuilder::new()
+                        .add_field::<u32>("number", "Enter number:", None)
+                        .build(),
+                )
+                .add_repeat(
+                    "phones",
+                    "Phones",
+                    || {
+                        FormBuilder::new()
+                            .add_field::<String>("phone", "Enter phone:", None)
+                            .build()
+                    },
+                    0,
+                    Some(1),
+                )
+        };
+
+        let mut form = build().hooks(hooks).build();
+        let mut answers = ValueMap::new();
+        answers.insert("country", Value::String(" nl".to_string()));
+        answers.insert("state", Value::String("Utrecht".to_string()));
+        let mut home = ValueMap::new();
+        home.insert("number", Value::Integer(12));
+        answers.insert("home", Value::Record(home));
+        assert_eq!(form.fill_from_answers(&answers), Ok(()));
+        assert_eq!(form.get_value::<String>("country"), Ok("NL".to_string()));
+        assert!(form.find_field("state").unwrap().value().is_null());
+        assert_eq!(form.get_value::<u32>("home.number"), Ok(12));
+        assert_eq!(
+            form.find_field("phones").unwrap().value(),
+            Value::List(Vec::new())
+        );
+
+        answers.insert("home", Value::String("12".to_string()));
+        assert_eq!(
+            form.fill_from_answers(&answers),
+            Err(FillError::Input {
+                field: "home".to_string(),
+                error: InputError::Invalid("The answer must be an object".to_string()),
+            })
+        );
+        assert_eq!(*errors.borrow(), vec!["home: The answer must be an object"]);
+
+        let mut form = build().build();
+        answers.insert("home", Value::Null);
+        assert_eq!(
+            form.fill_from_answers(&answers),
+            Err(FillError::Input {
+                field: "number".to_string(),
+                error: InputError::Invalid("Missing answer".to_string()),
+            })
+        );
+        answers.insert("phones", Value::List(vec![Value::Null, Value::Null]));
+        answers.insert("nickname", Value::Null);
+        assert_eq!(
+            build().build().fill_from_answers(&answers),
+            Err(FillError::Other(
+                "Unknown field 'nickname' in the answers".to_string()
+            ))
+        );
+    }
 }
@@ -0,0 +1,98 @@
+use crate::field::{Field, FieldTrait};
+use crate::multiselect_field::MultiselectField;
+use crate::select_field::SelectField;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The id given to the next form builder.
+static NEXT_FORM_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Returns a new id, identifying a form and the handles of its fields.
+pub(crate) fn next_form_id() -> u64 {
+    NEXT_FORM_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A typed token for reading the value of one field of a form.
+///
+/// Handles are returned by the `add_typed_*` methods of `FormBuilder` and are
+/// passed to `Form::get`. A handle only refers to a field of the form built
+/// by the builder that returned it.
+pub struct FieldHandle<T> {
+    /// The id of the form the field belongs to.
+    pub(crate) form: u64,
+    /// The order of the field in the form.
+    pub(crate) order: u32,
+    /// Reads the value of type `T` from the field, or `None` if the field has another type.
+    pub(crate) extract: fn(&dyn FieldTrait) -> Option<Option<T>>,
+    /// Marks the type of the value read through the handle.
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> FieldHandle<T> {
+    /// Creates a new `FieldHandle` for the field at `order`.
+    ///
+    /// # Parameters
+    ///
+    /// * `form` - The id of the form the field belongs to.
+    /// * `order` - The order of the field in the form.
+    /// * `extract` - A function reading the value of the field.
+    ///
+    /// # Returns
+    ///
+    /// * A new `FieldHandle` instance.
+    pub(crate) fn new(
+        form: u64,
+        order: u32,
+        extract: fn(&dyn FieldTrait) -> Option<Option<T>>,
+    ) -> Self {
+        Self {
+            form,
+            order,
+            extract,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for FieldHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FieldHandle<T> {}
+
+impl<T> std::fmt::Debug for FieldHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FieldHandle")
+            .field("form", &self.form)
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+/// Reads the value of a `Field<T>`, or returns `None` if the field has another type.
+pub(crate) fn extract_field<T: 'static + Clone>(field: &dyn FieldTrait) -> Option<Option<T>> {
+    field
+        .as_any()
+        .downcast_ref::<Field<T>>()
+        .map(|field| field.value.clone())
+}
+
+/// Reads the value of a `SelectField<T>`, or returns `None` if the field has another type.
+pub(crate) fn extract_select<T: 'static + Clone>(field: &dyn FieldTrait) -> Option<Option<T>> {
+    field
+        .as_any()
+        .downcast_ref::<SelectField<T>>()
+        .map(|field| field.value.clone())
+}
+
+/// Reads the values of a `MultiselectField<T>`, or returns `None` if the field has another type.
+pub(crate) fn extract_multiselect<T: 'static + Clone>(
+    field: &dyn FieldTrait,
+) -> Option<Option<Vec<T>>> {
+    field
+        .as_any()
+        .downcast_ref::<MultiselectField<T>>()
+        .map(|field| field.get_value().ok())
+}
@@ -0,0 +1,223 @@
+use crate::value::{Value, ValueMap};
+use std::cell::RefCell;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+/// Where filling a form continues after a hook has run.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Flow {
+    /// Filling continues with the next field.
+    #[default]
+    Continue,
+    /// The next field is not prompted for and keeps its current value.
+    Skip,
+    /// Filling continues at the named field, asking again for every field after it.
+    Goto(String),
+    /// Filling stops with an error.
+    Cancel,
+}
+
+/// A hook run before a field is filled.
+type FieldHook = Box<dyn FnMut(&str, &ValueMap) -> Flow>;
+
+/// A hook run after a field is filled.
+type AnswerHook = Box<dyn FnMut(&str, &Value, &ValueMap) -> Flow>;
+
+/// A hook run when filling a form stops early.
+type CancelHook = Box<dyn FnMut(&str, &ValueMap)>;
+
+/// A hook run once every field has been filled.
+type CompleteHook = Box<dyn FnMut(&ValueMap)>;
+
+/// A hook run for each invalid input.
+type ValidationHook = dyn FnMut(&str, &str, &ValueMap);
+
+/// A hook receiving the validation errors of fields, shared with the fields being filled.
+#[derive(Clone)]
+pub(crate) struct ErrorHook(Rc<RefCell<ValidationHook>>);
+
+impl ErrorHook {
+    /// Runs the hook.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field with invalid input.
+    /// * `message` - The error message shown to the user.
+    /// * `answers` - The values of the fields filled so far.
+    pub(crate) fn call(&self, name: &str, message: &str, answers: &ValueMap) {
+        (self.0.borrow_mut())(name, message, answers);
+    }
+}
+
+impl Debug for ErrorHook {
+    /// Formats the hook without its closure.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ErrorHook")
+    }
+}
+
+/// Callbacks run while a form is filled.
+///
+/// Each hook receives the name of the field and the values of the fields
+/// filled so far. The hooks run before and after a field return a `Flow`,
+/// which lets them skip fields, jump to another field or cancel filling.
+#[derive(Default)]
+pub struct Hooks {
+    /// The hook run before each field is filled.
+    before_field: Option<FieldHook>,
+    /// The hook run after each field is filled.
+    after_field: Option<AnswerHook>,
+    /// The hook run for each invalid input, including that of fields in sections.
+    pub(crate) on_validation_error: Option<ErrorHook>,
+    /// The hook run once every field has been filled.
+    on_complete: Option<CompleteHook>,
+    /// The hook run when filling stops before the form is complete.
+    on_cancel: Option<CancelHook>,
+}
+
+impl Hooks {
+    /// Creates an empty set of hooks.
+    ///
+    /// # Returns
+    ///
+    /// * A new `Hooks` instance without any hook.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the hook run before each field is filled.
+    ///
+    /// Fields restored from a draft are not filled again, so the hook does not
+    /// run for them.
+    ///
+    /// # Parameters
+    ///
+    /// * `hook` - A closure taking the field name and the answers so far, returning the `Flow`.
+    ///
+    /// # Returns
+    ///
+    /// * The `Hooks` instance with the hook set.
+    pub fn before_field<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&str, &ValueMap) -> Flow + 'static,
+    {
+        self.before_field = Some(Box::new(hook));
+        self
+    }
+
+    /// Sets the hook run after each field is filled.
+    ///
+    /// # Parameters
+    ///
+    /// * `hook` - A closure taking the field name, its value and the answers
+    ///   including that value, returning the `Flow`.
+    ///
+    /// # Returns
+    ///
+    /// * The `Hooks` instance with the hook set.
+    pub fn after_field<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&str, &Value, &ValueMap) -> Flow + 'static,
+    {
+        self.after_field = Some(Box::new(hook));
+        self
+    }
+
+    /// Sets the hook run whenever the user enters invalid input.
+    ///
+    /// # Parameters
+    ///
+    /// * `hook` - A closure taking the field name, the error message and the answers so far.
+    ///
+    /// # Returns
+    ///
+    /// * The `Hooks` instance with the hook set.
+    pub fn on_validation_error<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&str, &str, &ValueMap) + 'static,
+    {
+        self.on_validation_error = Some(ErrorHook(Rc::new(RefCell::new(hook))));
+        self
+    }
+
+    /// Sets the hook run once every field has been filled.
+    ///
+    /// # Parameters
+    ///
+    /// * `hook` - A closure taking the answers of the form.
+    ///
+    /// # Returns
+    ///
+    /// * The `Hooks` instance with the hook set.
+    pub fn on_complete<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&ValueMap) + 'static,
+    {
+        self.on_complete = Some(Box::new(hook));
+        self
+    }
+
+    /// Sets the hook run when filling stops early, because a hook returned
+    /// `Flow::Cancel` or a field could not be filled.
+    ///
+    /// # Parameters
+    ///
+    /// * `hook` - A closure taking the name of the field filling stopped at and the answers so far.
+    ///
+    /// # Returns
+    ///
+    /// * The `Hooks` instance with the hook set.
+    pub fn on_cancel<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&str, &ValueMap) + 'static,
+    {
+        self.on_cancel = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs the hook set with `before_field`, continuing if there is none.
+    pub(crate) fn run_before_field(&mut self, name: &str, answers: &ValueMap) -> Flow {
+        self.before_field
+            .as_mut()
+            .map_or(Flow::Continue, |hook| hook(name, answers))
+    }
+
+    /// Runs the hook set with `after_field`, continuing if there is none.
+    pub(crate) fn run_after_field(
+        &mut self,
+        name: &str,
+        value: &Value,
+        answers: &ValueMap,
+    ) -> Flow {
+        self.after_field
+            .as_mut()
+            .map_or(Flow::Continue, |hook| hook(name, value, answers))
+    }
+
+    /// Runs the hook set with `on_complete`, if any.
+    pub(crate) fn run_on_complete(&mut self, answers: &ValueMap) {
+        if let Some(hook) = self.on_complete.as_mut() {
+            hook(answers);
+        }
+    }
+
+    /// Runs the hook set with `on_cancel`, if any.
+    pub(crate) fn run_on_cancel(&mut self, name: &str, answers: &ValueMap) {
+        if let Some(hook) = self.on_cancel.as_mut() {
+            hook(name, answers);
+        }
+    }
+}
+
+impl Debug for Hooks {
+    /// Formats the hooks, showing which of them are set.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("before_field", &self.before_field.is_some())
+            .field("after_field", &self.after_field.is_some())
+            .field("on_validation_error", &self.on_validation_error.is_some())
+            .field("on_complete", &self.on_complete.is_some())
+            .field("on_cancel", &self.on_cancel.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,411 @@
+use crate::field::FieldTrait;
+use crate::form::Form;
+use crate::repeat_field::RepeatField;
+use crate::section_field::SectionField;
+use crate::validation::NamedRule;
+use crate::value::{FieldKind, ValueKind};
+
+/// Writes a form as an HTML `<form>` element.
+///
+/// Each field becomes a `<label>` with its prompt followed by a matching
+/// input: text, password and number inputs, checkboxes for `bool` fields and
+/// `<select>` lists for select and multiselect fields. Sections and
+/// repeatable groups become `<fieldset>` elements, with the fields of a
+/// repeatable group written once as its first entry.
+///
+/// # Parameters
+///
+/// * `form` - The form to be written.
+///
+/// # Returns
+///
+/// * The HTML text, indented by two spaces per level.
+pub(crate) fn write(form: &Form) -> String {
+    let mut output = String::from("<form>\n");
+    write_fields(form, "", 1, &mut output);
+    output.push_str("</form>\n");
+    output
+}
+
+/// Appends the fields of a form to `output`, prefixing their names with `prefix`.
+fn write_fields(form: &Form, prefix: &str, depth: usize, output: &mut String) {
+    for (name, field) in form.fields.values() {
+        write_field(
+            field.as_ref(),
+            &format!("{}{}", prefix, name),
+            depth,
+            output,
+        );
+    }
+}
+
+/// Appends a line indented to `depth` to `output`.
+fn push_line(output: &mut String, depth: usize, line: &str) {
+    output.push_str(&"  ".repeat(depth));
+    output.push_str(line);
+    output.push('\n');
+}
+
+/// Appends a field and its label to `output`.
+fn write_field(field: &dyn FieldTrait, name: &str, depth: usize, output: &mut String) {
+    let id = escape(name);
+    let prompt = escape(field.prompt());
+    let title = field
+        .details()
+        .and_then(|details| details.help.as_deref())
+        .map_or_else(String::new, |help| format!(" title=\"{}\"", escape(help)));
+    match field.kind() {
+        FieldKind::Section => {
+            push_line(output, depth, &format!("<fieldset name=\"{}\">", id));
+            push_line(output, depth + 1, &format!("<legend>{}</legend>", prompt));
+            if let Some(section) = field.as_any().downcast_ref::<SectionField>() {
+                if let Some(description) = &section.description {
+                    push_line(
+                        output,
+                        depth + 1,
+                        &format!("<p>{}</p>", escape(description)),
+                    );
+                }
+                write_fields(&section.form, &format!("{}.", name), depth + 1, output);
+            }
+            push_line(output, depth, "</fieldset>");
+        }
+        FieldKind::Repeat => {
+            push_line(output, depth, &format!("<fieldset name=\"{}\">", id));
+            push_line(output, depth + 1, &format!("<legend>{}</legend>", prompt));
+            if let Some(repeat) = field.as_any().downcast_ref::<RepeatField>() {
+                write_fields(
+                    &(repeat.template)(),
+                    &format!("{}[0].", name),
+                    depth + 1,
+                    output,
+                );
+            }
+            push_line(output, depth, "</fieldset>");
+        }
+        kind @ (FieldKind::Select | FieldKind::Multiselect) => {
+            push_line(
+                output,
+                depth,
+                &format!("<label for=\"{}\">{}</label>", id, prompt),
+            );
+            let multiple = if kind == FieldKind::Multiselect {
+                " multiple"
+            } else {
+                ""
+            };
+            let required = if field.is_required() { " required" } else { "" };
+            push_line(
+                output,
+                depth,
+                &format!(
+                    "<select id=\"{}\" name=\"{}\"{}{}{}>",
+                    id, id, multiple, required, title
+                ),
+            );
+            let descriptions = field
+                .details()
+                .map_or(&[][..], |details| &details.option_descriptions[..]);
+            for (value, label) in field.options() {
+                let description = descriptions
+                    .iter()
+                    .find(|(option, _)| *option == value)
+                    .map_or_else(String::new, |(_, description)| {
+                        format!(" title=\"{}\"", escape(description))
+                    });
+                push_line(
+                    output,
+                    depth + 1,
+                    &format!(
+                        "<option value=\"{}\"{}>{}</option>",
+                        escape(&value.to_string()),
+                        description,
+                        escape(&label)
+                    ),
+                );
+            }
+            push_line(output, depth, "</select>");
+        }
+        FieldKind::Input(kind) => {
+            push_line(
+                output,
+                depth,
+                &format!("<label for=\"{}\">{}</label>", id, prompt),
+            );
+            push_line(
+                output,
+                depth,
+                &format!(
+                    "<input {} id=\"{}\" name=\"{}\"{}{}>",
+                    input_attributes(field, kind),
+                    id,
+                    id,
+                    constraint_attributes(field, kind),
+                    title
+                ),
+            );
+        }
+    }
+}
+
+/// Formats the type of the input of a field, with the step of number inputs.
+fn input_attributes(field: &dyn FieldTrait, kind: ValueKind) -> &'static str {
+    match kind {
+        _ if field.is_secret() => "type=\"password\"",
+        ValueKind::Integer => "type=\"number\" step=\"1\"",
+        ValueKind::Float => "type=\"number\" step=\"any\"",
+        ValueKind::Bool => "type=\"checkbox\" value=\"true\"",
+        ValueKind::Char => "type=\"text\" maxlength=\"1\"",
+        ValueKind::String | ValueKind::Other => "type=\"text\"",
+    }
+}
+
+/// Formats the `required`, `pattern`, `min`, `max` and `placeholder`
+/// attributes of the input of a field, derived from its validator.
+///
+/// Checkboxes are never required, since an unchecked box means `false`.
+fn constraint_attributes(field: &dyn FieldTrait, kind: ValueKind) -> String {
+    let mut attributes = String::new();
+    let is_number = !field.is_secret() && matches!(kind, ValueKind::Integer | ValueKind::Float);
+    let is_text = field.is_secret() || !(is_number || kind == ValueKind::Bool);
+    if field.is_required() && kind != ValueKind::Bool {
+        attributes.push_str(" required");
+    }
+    if let Some(validator) = field.validator() {
+        let rules = validator.named_rules();
+        if is_text {
+            let patterns: Vec<String> = rules
+                .clone()
+                .filter_map(NamedRule::regex)
+                .map(str::to_string)
+                .chain(validator.pattern_regexes())
+                .map(|regex| html_pattern(&regex))
+                .collect();
+            if let Some(pattern) = combine_patterns(&patterns) {
+                attributes.push_str(&format!(" pattern=\"{}\"", escape(&pattern)));
+            }
+        }
+        if is_number {
+            let range =
+                rules
+                    .filter_map(NamedRule::range)
+                    .reduce(|(min, max), (other_min, other_max)| {
+                        (min.max(other_min), max.min(other_max))
+                    });
+            if let Some((min, max)) = range {
+                attributes.push_str(&format!(" min=\"{}\" max=\"{}\"", min, max));
+            }
+        }
+    }
+    if let Some(placeholder) = field
+        .details()
+        .and_then(|details| details.placeholder.as_deref())
+        .filter(|_| is_text || is_number)
+    {
+        attributes.push_str(&format!(" placeholder=\"{}\"", escape(placeholder)));
+    }
+    attributes
+}
+
+/// Combines patterns into one that every value matching all of them matches,
+/// checking all but the last with lookaheads.
+fn combine_patterns(patterns: &[String]) -> Option<String> {
+    let (last, others) = patterns.split_last()?;
+    if others.is_empty() {
+        return Some(last.clone());
+    }
+    let lookaheads: String = others
+        .iter()
+        .map(|pattern| format!("(?=(?:{})$)", pattern))
+        .collect();
+    Some(format!("{}(?:{})", lookaheads, last))
+}
+
+/// Converts an anchored regular expression to the syntax of the HTML `pattern` attribute.
+///
+/// Browsers anchor patterns themselves and compile them with the `v` flag,
+/// which needs `-` at the edges of a character class and `/` within one escaped.
+fn html_pattern(regex: &str) -> String {
+    let regex = regex.strip_prefix('^').unwrap_or(regex);
+    let regex = match regex.strip_suffix('$') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => regex,
+    };
+    let chars: Vec<char> = regex.chars().collect();
+    let mut pattern = String::new();
+    let mut in_class = false;
+    let mut class_start = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let at_class_start = class_start;
+        class_start = false;
+        match c {
+            '\\' => {
+                pattern.push(c);
+                if let Some(next) = chars.get(i + 1) {
+                    pattern.push(*next);
+                    i += 1;
+                }
+            }
+            '[' if !in_class => {
+                pattern.push(c);
+                in_class = true;
+                class_start = true;
+                if chars.get(i + 1) == Some(&'^') {
+                    pattern.push('^');
+                    i += 1;
+                }
+            }
+            ']' if in_class => {
+                pattern.push(c);
+                in_class = false;
+            }
+            '-' if in_class && (at_class_start || chars.get(i + 1) == Some(&']')) => {
+                pattern.push_str("\\-");
+            }
+            '/' if in_class => pattern.push_str("\\/"),
+            _ => pattern.push(c),
+        }
+        i += 1;
+    }
+    pattern
+}
+
+/// Escapes text for use in HTML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_builder::FormBuilder;
+    use crate::optional::Optional;
+    use crate::validation::Validator;
+
+    #[test]
+    fn test_write_inputs() {
+        let form = FormBuilder::new()
+            .add_field::<String>(
+                "email",
+                "Email <work>:",
+                Some(Validator::named(NamedRule::NotEmpty, None).builtin(NamedRule::Email, None)),
+            )
+            .add_field::<String>("nickname", "Nickname:", None)
+            .add_field::<u32>("age", "Age:", None)
+            .add_field::<Optional<f64>>(
+                "latitude",
+                "Latitude:",
+                Some(Validator::named(NamedRule::Latitude, None)),
+            )
+            .add_field::<bool>("subscribe", "Subscribe:", None)
+            .add_field::<char>("initial", "Initial:", None)
+            .build();
+
+        assert_eq!(
+            write(&form),
+            "<form>\n\
+             \x20 <label for=\"email\">Email &lt;work&gt;:</label>\n\
+             \x20 <input type=\"text\" id=\"email\" name=\"email\" required pattern=\"[\\w\\.\\-]+@[\\w\\.\\-]+\\.\\w+\">\n\
+             \x20 <label for=\"nickname\">Nickname:</label>\n\
+             \x20 <input type=\"text\" id=\"nickname\" name=\"nickname\">\n\
+             \x20 <label for=\"age\">Age:</label>\n\
+             \x20 <input type=\"number\" step=\"1\" id=\"age\" name=\"age\" required>\n\
+             \x20 <label for=\"latitude\">Latitude:</label>\n\
+             \x20 <input type=\"number\" step=\"any\" id=\"latitude\" name=\"latitude\" required min=\"-90\" max=\"90\">\n\
+             \x20 <label for=\"subscribe\">Subscribe:</label>\n\
+             \x20 <input type=\"checkbox\" value=\"true\" id=\"subscribe\" name=\"subscribe\">\n\
+             \x20 <label for=\"initial\">Initial:</label>\n\
+             \x20 <input type=\"text\" maxlength=\"1\" id=\"initial\" name=\"initial\" required>\n\
+             </form>\n"
+        );
+    }
+
+    #[test]
+    fn test_write_selects_and_sections() {
+        let form = FormBuilder::new()
+            .add_select("plan", "Select plan", vec![(1u8, "Free"), (2u8, "Pro")])
+            .add_multiselect(
+                "tags",
+                "Select tags",
+                vec![("a", "A & B"), ("c", "C")],
+                None,
+            )
+            .add_section(
+                "address",
+                "Address",
+                Some("Where we send letters"),
+                FormBuilder::new()
+                    .add_field::<String>("city", "City:", None)
+                    .build(),
+            )
+            .add_repeat(
+                "phones",
+                "Phone numbers",
+                || {
+                    FormBuilder::new()
+                        .add_field::<String>("number", "Number:", None)
+                        .build()
+                },
+                0,
+                None,
+            )
+            .build();
+
+        assert_eq!(
+            write(&form),
+            "<form>\n\
+             \x20 <label for=\"plan\">Select plan</label>\n\
+             \x20 <select id=\"plan\" name=\"plan\">\n\
+             \x20   <option value=\"1\">Free</option>\n\
+             \x20   <option value=\"2\">Pro</option>\n\
+             \x20 </select>\n\
+             \x20 <label for=\"tags\">Select tags</label>\n\
+             \x20 <select id=\"tags\" name=\"tags\" multiple required>\n\
+             \x20   <option value=\"a\">A &amp; B</option>\n\
+             \x20   <option value=\"c\">C</option>\n\
+             \x20 </select>\n\
+             \x20 <fieldset name=\"address\">\n\
+             \x20   <legend>Address</legend>\n\
+             \x20   <p>Where we send letters</p>\n\
+             \x20   <label for=\"address.city\">City:</label>\n\
+             \x20   <input type=\"text\" id=\"address.city\" name=\"address.city\">\n\
+             \x20 </fieldset>\n\
+             \x20 <fieldset name=\"phones\">\n\
+             \x20   <legend>Phone numbers</legend>\n\
+             \x20   <label for=\"phones[0].number\">Number:</label>\n\
+             \x20   <input type=\"text\" id=\"phones[0].number\" name=\"phones[0].number\">\n\
+             \x20 </fieldset>\n\
+             </form>\n"
+        );
+    }
+
+    #[test]
+    fn test_html_pattern() {
+        assert_eq!(
+            html_pattern(r"^[a-z0-9]+(-[a-z0-9]+)*$"),
+            r"[a-z0-9]+(-[a-z0-9]+)*"
+        );
+        assert_eq!(
+            html_pattern(r"^(http|https)://[^\s/$.?#].[^\s]*$"),
+            r"(http|https)://[^\s\/$.?#].[^\s]*"
+        );
+        assert_eq!(html_pattern(r"^[-+]?\d+\$$"), r"[\-+]?\d+\$");
+        assert_eq!(
+            combine_patterns(&["a+".to_string(), "[ab]+".to_string()]),
+            Some("(?=(?:a+)$)(?:[ab]+)".to_string())
+        );
+    }
+}
@@ -1,9 +1,19 @@
+use crate::render::{AnsiRenderer, Frame, FrameOption, PromptKind, Renderer, countdown_prefix};
+use crate::transform::{Transformer, apply_transformers};
 use crate::validation::Validator;
-use libc::{tcgetattr, tcsetattr, ECHO, ICANON, TCSANOW};
-use std::fmt::Debug;
-use std::io::{self, stdin, Read, Write};
-use std::os::unix::io::AsRawFd;
+use crate::value::Value;
+use libc::{
+    ECHO, ICANON, ISIG, POLLIN, STDERR_FILENO, STDIN_FILENO, STDOUT_FILENO, TCSANOW, c_void,
+    isatty, poll, pollfd, tcflag_t, tcgetattr, tcsetattr, termios,
+};
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 /// Reads input from the user and validates it using the provided validator.
 ///
@@ -25,33 +35,530 @@ where
     T: FromStr,
     T::Err: Debug,
 {
-    use std::io::{self, Write};
+    read_input_with(prompt, validator, parse_from_str::<T>)
+}
+
+/// Reads input from the user, validates it and converts it with the provided parser.
+///
+/// # Arguments
+///
+/// * `prompt` - The prompt message to be displayed to the user.
+/// * `validator` - An optional `Validator` instance to validate the input.
+/// * `parse` - A function converting the validated input to a value.
+///
+/// # Returns
+///
+/// * `Ok(T)` if the input is successfully read, validated and parsed.
+/// * `Err(String)` if there is an error reading or parsing the input.
+pub fn read_input_with<T>(
+    prompt: &str,
+    validator: Option<&Validator>,
+    parse: fn(&str) -> Result<T, String>,
+) -> Result<T, String> {
+    read_input_with_details(prompt, validator, &PromptDetails::default(), parse)
+}
+
+/// An error ending a prompt without an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    /// Stdin was closed before an answer was read.
+    Eof,
+    /// The maximum number of attempts was used up without valid input.
+    AttemptsExhausted(usize),
+    /// The prompt was not answered in time.
+    TimedOut,
+    /// The user pressed Ctrl-C.
+    Interrupted,
+    /// The input could not be parsed and the prompt does not retry.
+    Invalid(String),
+    /// Reading from or writing to the terminal failed.
+    Io(String),
+}
+
+impl Display for InputError {
+    /// Formats the error as a message for the user.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Eof => write!(f, "Unexpected end of input"),
+            InputError::AttemptsExhausted(attempts) => {
+                write!(f, "No valid input after {} attempts", attempts)
+            }
+            InputError::TimedOut => write!(f, "No answer in time"),
+            InputError::Interrupted => write!(f, "Interrupted"),
+            InputError::Invalid(message) => write!(f, "{}", message),
+            InputError::Io(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+impl From<String> for InputError {
+    /// Wraps the message of a terminal error.
+    fn from(message: String) -> Self {
+        InputError::Io(message)
+    }
+}
+
+impl From<InputError> for String {
+    /// Converts the error to its message.
+    fn from(error: InputError) -> Self {
+        error.to_string()
+    }
+}
+
+/// The stream prompts are written to when they are not written to the controlling terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PromptOutput {
+    /// Prompts are written to stdout.
+    #[default]
+    Stdout,
+    /// Prompts are written to stderr, keeping stdout free for the output of a program.
+    Stderr,
+}
+
+impl PromptOutput {
+    /// Returns the file descriptor of the stream.
+    fn fd(self) -> RawFd {
+        match self {
+            PromptOutput::Stdout => STDOUT_FILENO,
+            PromptOutput::Stderr => STDERR_FILENO,
+        }
+    }
+}
+
+/// What the user entered at a text prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Entry {
+    /// A line of text, without its line break.
+    Line(String),
+    /// F1 was pressed to ask for help.
+    Help,
+}
+
+/// A callback receiving the message of each failed attempt at valid input.
+#[derive(Clone, Copy)]
+pub struct ErrorCallback<'a>(pub &'a dyn Fn(&str));
+
+impl Debug for ErrorCallback<'_> {
+    /// Formats the callback without its closure.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ErrorCallback")
+    }
+}
+
+/// Counts the failed attempts of a prompt against its limit.
+struct Attempts<'a> {
+    /// The maximum number of attempts, or `None` for no limit.
+    limit: Option<usize>,
+    /// The number of failed attempts so far.
+    failed: usize,
+    /// The callback told about each failed attempt, if any.
+    on_error: Option<ErrorCallback<'a>>,
+    /// The renderer showing the error of the last attempt.
+    renderer: &'a dyn Renderer,
+}
+
+impl<'a> Attempts<'a> {
+    /// Creates a counter for a prompt.
+    fn new(details: &PromptDetails<'a>) -> Self {
+        Self {
+            limit: details.max_attempts,
+            failed: 0,
+            on_error: details.on_error,
+            renderer: details.renderer(),
+        }
+    }
+
+    /// Counts a failed attempt, showing its error with the next frame of the prompt.
+    ///
+    /// # Arguments
+    ///
+    /// * `terminal` - The terminal showing the error if the limit has been reached.
+    /// * `frame` - The frame of the prompt, which receives the error.
+    /// * `message` - The error message.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the user may try again.
+    /// * `Err(InputError::AttemptsExhausted)` if the limit has been reached, after showing the error.
+    fn fail(
+        &mut self,
+        terminal: &mut Terminal<'_>,
+        frame: &mut Frame<'_>,
+        message: String,
+    ) -> Result<(), InputError> {
+        if let Some(ErrorCallback(on_error)) = self.on_error {
+            on_error(&message);
+        }
+        self.failed += 1;
+        match self.limit {
+            Some(limit) if self.failed >= limit => {
+                let mut notice = Frame::new(PromptKind::Notice, frame.prompt);
+                notice.error = Some(message);
+                terminal.draw(&self.renderer.render(&notice))?;
+                Err(InputError::AttemptsExhausted(self.failed))
+            }
+            _ => {
+                frame.error = Some(message);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The time by which a prompt must be answered.
+#[derive(Debug, Clone, Copy)]
+struct Deadline {
+    /// The instant the prompt times out.
+    at: Instant,
+    /// Whether the seconds left are drawn while waiting.
+    countdown: bool,
+}
+
+impl Deadline {
+    /// Starts the timeout of a prompt.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Deadline)` if the prompt has a timeout, `None` otherwise.
+    fn start(details: &PromptDetails<'_>, terminal: &Terminal) -> Option<Self> {
+        details.timeout.map(|timeout| Self {
+            at: Instant::now() + timeout,
+            countdown: details.countdown && terminal.is_interactive(),
+        })
+    }
+
+    /// Returns the whole seconds left, rounded up.
+    fn seconds_left(&self) -> u64 {
+        let remaining = self.at.saturating_duration_since(Instant::now());
+        remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+    }
+
+    /// Returns how long to wait for input before the countdown is redrawn or the prompt times out.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Duration)` with the time to wait.
+    /// * `Err(InputError::TimedOut)` if the deadline has passed.
+    fn next_wait(&self) -> Result<Duration, InputError> {
+        let remaining = self.at.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(InputError::TimedOut);
+        }
+        if !self.countdown {
+            return Ok(remaining);
+        }
+        let fraction = Duration::new(0, remaining.subsec_nanos());
+        Ok(if fraction.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            fraction
+        })
+    }
+
+    /// Returns the seconds left to be shown in a frame.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(u64)` if the countdown is shown, `None` otherwise.
+    fn shown_seconds(deadline: Option<Self>) -> Option<u64> {
+        deadline
+            .filter(|deadline| deadline.countdown)
+            .map(|deadline| deadline.seconds_left())
+    }
+}
+
+/// Optional texts and settings of a prompt beyond the prompt message.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PromptDetails<'a> {
+    /// Help text shown when the user types `?` or presses F1.
+    pub help: Option<&'a str>,
+    /// The requirements of valid input, shown with the help text.
+    pub requirements: &'a [String],
+    /// An example shown dimmed in an empty text input on a terminal.
+    pub placeholder: Option<&'a str>,
+    /// Descriptions of the options of a select list, by option value.
+    pub descriptions: &'a [(Value, String)],
+    /// The transformers applied to text input before it is validated.
+    pub transformers: &'a [Transformer],
+    /// Whether text input is hidden while it is typed on a terminal.
+    pub secret: bool,
+    /// The maximum number of attempts at valid input, or `None` for no limit.
+    pub max_attempts: Option<usize>,
+    /// The time the user has to answer, or `None` to wait indefinitely.
+    pub timeout: Option<Duration>,
+    /// Whether the seconds left are shown while waiting for an answer on a terminal.
+    pub countdown: bool,
+    /// The callback told about each invalid input, if any.
+    pub on_error: Option<ErrorCallback<'a>>,
+    /// The renderer drawing the prompt, or `None` for `AnsiRenderer`.
+    pub renderer: Option<&'a dyn Renderer>,
+    /// The stream prompts are written to when stdin is used.
+    pub output: PromptOutput,
+}
+
+impl<'a> PromptDetails<'a> {
+    /// Checks whether there is help text or a requirement to show.
+    fn has_help(&self) -> bool {
+        self.help.is_some() || !self.requirements.is_empty()
+    }
+
+    /// Formats the help text and the requirements.
+    fn help_text(&self) -> String {
+        let mut text = String::new();
+        if let Some(help) = self.help {
+            text.push_str(help);
+            text.push('\n');
+        }
+        if !self.requirements.is_empty() {
+            text.push_str("Requirements:\n");
+            for requirement in self.requirements {
+                text.push_str(&format!("  - {}\n", requirement));
+            }
+        }
+        text
+    }
+
+    /// Returns the description of the option with the given value.
+    fn description(&self, value: &Value) -> Option<&'a str> {
+        self.descriptions
+            .iter()
+            .find(|(option, _)| option == value)
+            .map(|(_, description)| description.as_str())
+    }
+
+    /// Returns the renderer drawing the prompt.
+    fn renderer(&self) -> &'a dyn Renderer {
+        self.renderer.unwrap_or(&AnsiRenderer)
+    }
+
+    /// Creates a frame listing options with their descriptions.
+    fn frame<T>(
+        &self,
+        kind: PromptKind,
+        prompt: &'a str,
+        options: &'a [(T, String)],
+        selected: &[bool],
+    ) -> Frame<'a>
+    where
+        T: 'static + Debug,
+    {
+        Frame {
+            options: options
+                .iter()
+                .enumerate()
+                .map(|(i, (value, label))| FrameOption {
+                    label,
+                    description: self.description(&Value::of(value)),
+                    selected: selected.get(i).copied().unwrap_or(false),
+                })
+                .collect(),
+            help_available: self.has_help(),
+            ..Frame::new(kind, prompt)
+        }
+    }
+}
+
+/// Reads input from the user, transforms it, validates it and converts it with the provided parser.
+///
+/// Typing `?` shows the help text and requirements, if there are any, and
+/// asks again. Input failing validation is asked again, while input that
+/// cannot be parsed ends the prompt with an error unless `details` limits
+/// the attempts.
+///
+/// # Arguments
+///
+/// * `prompt` - The prompt message to be displayed to the user.
+/// * `validator` - An optional `Validator` instance to validate the input.
+/// * `details` - Help text, placeholder, transformers and attempt limit of the prompt.
+/// * `parse` - A function converting the validated input to a value.
+///
+/// # Returns
+///
+/// * `Ok(T)` if the input is successfully read, validated and parsed.
+/// * `Err(String)` if there is an error reading or parsing the input.
+pub fn read_input_with_details<T>(
+    prompt: &str,
+    validator: Option<&Validator>,
+    details: &PromptDetails<'_>,
+    parse: fn(&str) -> Result<T, String>,
+) -> Result<T, String> {
+    read_text_input(
+        prompt,
+        validator,
+        details,
+        parse,
+        details.max_attempts.is_some(),
+    )
+    .map(|(value, _)| value)
+    .map_err(String::from)
+}
+
+/// Reads input from the user until it passes validation and parsing.
+///
+/// Typing `?` shows the help text and requirements, if there are any, and
+/// asks again. Invalid input counts as a failed attempt.
+///
+/// # Arguments
+///
+/// * `prompt` - The prompt message to be displayed to the user.
+/// * `validator` - An optional `Validator` instance to validate the input.
+/// * `details` - Help text, placeholder, transformers and attempt limit of the prompt.
+/// * `parse` - A function converting the validated input to a value.
+///
+/// # Returns
+///
+/// * `Ok((T, String))` with the parsed value and the transformed input it was parsed from.
+/// * `Err(InputError)` if stdin is closed, the attempts are used up or reading fails.
+pub fn read_parsed_input<T>(
+    prompt: &str,
+    validator: Option<&Validator>,
+    details: &PromptDetails<'_>,
+    parse: fn(&str) -> Result<T, String>,
+) -> Result<(T, String), InputError> {
+    read_text_input(prompt, validator, details, parse, true)
+}
 
+/// Reads input from the user until it passes validation, and parses it.
+///
+/// # Arguments
+///
+/// * `prompt` - The prompt message to be displayed to the user.
+/// * `validator` - An optional `Validator` instance to validate the input.
+/// * `details` - Help text, placeholder, transformers and attempt limit of the prompt.
+/// * `parse` - A function converting the validated input to a value.
+/// * `retry_unparsed` - Whether input that cannot be parsed counts as a failed attempt instead of ending the prompt.
+///
+/// # Returns
+///
+/// * `Ok((T, String))` with the parsed value and the transformed input it was parsed from.
+/// * `Err(InputError)` if stdin is closed, the input cannot be parsed, the attempts are used up or reading fails.
+fn read_text_input<T>(
+    prompt: &str,
+    validator: Option<&Validator>,
+    details: &PromptDetails<'_>,
+    parse: fn(&str) -> Result<T, String>,
+    retry_unparsed: bool,
+) -> Result<(T, String), InputError> {
+    let mut terminal = Terminal::open(details.output);
+    let placeholder = details
+        .placeholder
+        .filter(|_| !details.secret && terminal.is_interactive());
+    let mut attempts = Attempts::new(details);
+    let deadline = Deadline::start(details, &terminal);
+    let mut frame = Frame {
+        placeholder,
+        ..Frame::new(PromptKind::Text, prompt)
+    };
+
+    // Entering `?` again while the help is shown answers with a literal `?`.
+    let mut help_shown = false;
     loop {
-        print!("{} ", prompt);
-        io::stdout()
-            .flush()
-            .map_err(|e| format!("Failed to flush stdout: {:?}", e))?;
-
-        let mut input = String::new();
-        stdin()
-            .read_line(&mut input)
-            .map_err(|e| format!("Failed to read line: {:?}", e))?;
-        let input = input.trim();
-
-        if let Some(validator) = validator {
-            if let Err(err) = validator.validate(input) {
-                eprintln!("{}", err);
+        let input = match terminal.read_frame_line(
+            details.renderer(),
+            &mut frame,
+            deadline,
+            details.secret,
+        )? {
+            Entry::Line(input) if input.trim() != "?" || help_shown || !details.has_help() => input,
+            Entry::Line(_) | Entry::Help if details.has_help() => {
+                frame.help = Some(format!(
+                    "{}Enter ? again to answer with a question mark.\n",
+                    details.help_text()
+                ));
+                help_shown = true;
                 continue;
             }
+            Entry::Line(_) | Entry::Help => continue,
+        };
+        help_shown = false;
+        let input = apply_transformers(details.transformers, input.trim());
+
+        if let Some(validator) = validator
+            && let Err(err) = validator.validate(&input)
+        {
+            attempts.fail(&mut terminal, &mut frame, err)?;
+            continue;
+        }
+
+        match parse(&input) {
+            Ok(value) => return Ok((value, input)),
+            Err(error) if !retry_unparsed => return Err(InputError::Invalid(error)),
+            Err(_) => attempts.fail(
+                &mut terminal,
+                &mut frame,
+                "Invalid input. Please try again.".to_string(),
+            )?,
+        }
+    }
+}
+
+/// Waits until `fd` has input to read.
+///
+/// # Returns
+///
+/// * `Ok(true)` if input is available, `Ok(false)` if `wait` elapsed first.
+/// * `Err(io::Error)` if waiting fails.
+fn wait_for_input(fd: RawFd, wait: Duration) -> io::Result<bool> {
+    let mut descriptor = pollfd {
+        fd,
+        events: POLLIN,
+        revents: 0,
+    };
+    let timeout = i32::try_from(wait.as_nanos().div_ceil(1_000_000)).unwrap_or(i32::MAX);
+    loop {
+        match unsafe { poll(&mut descriptor, 1, timeout) } {
+            -1 => {
+                let error = io::Error::last_os_error();
+                if error.kind() != io::ErrorKind::Interrupted {
+                    return Err(error);
+                }
+            }
+            0 => return Ok(false),
+            _ => return Ok(true),
         }
+    }
+}
+
+/// Parses a value with its `FromStr` implementation.
+///
+/// # Returns
+///
+/// * `Ok(T)` if the input is successfully parsed.
+/// * `Err(String)` with the `Debug` representation of the parse error otherwise.
+pub fn parse_from_str<T>(input: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    input.parse::<T>().map_err(|err| format!("{:?}", err))
+}
 
-        return input.parse::<T>().map_err(|err| format!("{:?}", err));
+/// Parses an optional value, treating empty input as `None`.
+///
+/// # Returns
+///
+/// * `Ok(None)` if the input is empty.
+/// * `Ok(Some(T))` if the input is successfully parsed.
+/// * `Err(String)` with the `Debug` representation of the parse error otherwise.
+pub fn parse_optional<T>(input: &str) -> Result<Option<T>, String>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    if input.is_empty() {
+        Ok(None)
+    } else {
+        parse_from_str(input).map(Some)
     }
 }
 
 /// Reads a selection from the user from a list of options.
 ///
+/// When no terminal is available, the options are printed as a numbered list
+/// and the choice is read as a line from stdin.
+///
 /// # Arguments
 ///
 /// * `prompt` - The prompt message to be displayed to the user.
@@ -59,7 +566,7 @@ where
 ///
 /// # Type Parameters
 ///
-/// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, `Debug`, and `FromStr` traits.
+/// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, and `Debug` traits.
 ///
 /// # Returns
 ///
@@ -67,40 +574,63 @@ where
 /// * `Err(String)` if there is an error reading the selection.
 pub fn read_select<T>(prompt: &str, options: &[(T, String)]) -> Result<T, String>
 where
-    T: Clone + PartialEq + Debug + FromStr,
-    T::Err: Debug,
+    T: 'static + Clone + PartialEq + Debug,
 {
-    let mut selected = 0;
+    read_select_with_details(prompt, options, &PromptDetails::default()).map_err(String::from)
+}
 
-    loop {
-        clear_screen();
-        println!("{}:", prompt);
+/// Reads a selection from the user from a list of options, with help text and option descriptions.
+///
+/// The description of the highlighted option is shown below the list, and
+/// `?` or F1 toggles the help text.
+///
+/// # Arguments
+///
+/// * `prompt` - The prompt message to be displayed to the user.
+/// * `options` - A list of options available for selection.
+/// * `details` - Help text, option descriptions and attempt limit of the prompt.
+///
+/// # Returns
+///
+/// * `Ok(T)` if the selection is successfully read.
+/// * `Err(InputError)` if stdin is closed, the attempts are used up or reading fails.
+pub fn read_select_with_details<T>(
+    prompt: &str,
+    options: &[(T, String)],
+    details: &PromptDetails<'_>,
+) -> Result<T, InputError>
+where
+    T: 'static + Clone + PartialEq + Debug,
+{
+    if options.is_empty() {
+        return Err(InputError::Io(
+            "No options available for selection".to_string(),
+        ));
+    }
 
-        for (i, (_, value)) in options.iter().enumerate() {
-            if i == selected {
-                println!("> {}", value);
-            } else {
-                println!("  {}", value);
-            }
-        }
-        io::stdout()
-            .flush()
-            .map_err(|e| format!("Failed to flush stdout: {:?}", e))?;
+    let mut terminal = Terminal::open(details.output);
+    if !terminal.reads_keys() {
+        return read_select_numbered(&mut terminal, prompt, options, details);
+    }
 
-        match read_key_raw()? {
-            Key::Up => {
-                if selected > 0 {
-                    selected -= 1;
-                }
-            }
-            Key::Down => {
-                if selected < options.len() - 1 {
-                    selected += 1;
-                }
-            }
+    let renderer = details.renderer();
+    let mut frame = details.frame(PromptKind::Select, prompt, options, &[]);
+    let deadline = Deadline::start(details, &terminal);
+
+    loop {
+        frame.seconds_left = Deadline::shown_seconds(deadline);
+        terminal.draw(&renderer.render(&frame))?;
+
+        let Some(key) = terminal.read_key_before(renderer, deadline)? else {
+            continue;
+        };
+        match key {
+            Key::Up => frame.cursor = frame.cursor.saturating_sub(1),
+            Key::Down if frame.cursor < options.len() - 1 => frame.cursor += 1,
+            Key::Help => frame.help = frame.help.take().xor(Some(details.help_text())),
             Key::Enter => {
-                clear_screen();
-                return Ok(options[selected].0.clone());
+                terminal.draw(&renderer.clear())?;
+                return Ok(options[frame.cursor].0.clone());
             }
             _ => {}
         }
@@ -109,6 +639,9 @@ where
 
 /// Reads multiple selections from the user from a list of options.
 ///
+/// When no terminal is available, the options are printed as a numbered list
+/// and the choices are read as a comma-separated line from stdin.
+///
 /// # Arguments
 ///
 /// * `prompt` - The prompt message to be displayed to the user.
@@ -117,7 +650,7 @@ where
 ///
 /// # Type Parameters
 ///
-/// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, `Debug`, and `FromStr` traits.
+/// * `T` - The type of the options. It must implement the `Clone`, `PartialEq`, and `Debug` traits.
 ///
 /// # Returns
 ///
@@ -129,67 +662,325 @@ pub fn read_multiselect<T>(
     limit: Option<usize>,
 ) -> Result<Vec<T>, String>
 where
-    T: Clone + PartialEq + Debug + FromStr,
-    T::Err: Debug,
+    T: 'static + Clone + PartialEq + Debug,
+{
+    read_multiselect_with_details(prompt, options, limit, &PromptDetails::default())
+        .map_err(String::from)
+}
+
+/// Reads multiple selections from the user from a list of options, with help text and option descriptions.
+///
+/// The description of the highlighted option is shown below the list, and
+/// `?` or F1 toggles the help text.
+///
+/// # Arguments
+///
+/// * `prompt` - The prompt message to be displayed to the user.
+/// * `options` - A list of options available for selection.
+/// * `limit` - An optional limit on the number of selections.
+/// * `details` - Help text, option descriptions and attempt limit of the prompt.
+///
+/// # Returns
+///
+/// * `Ok(Vec<T>)` if the selections are successfully read.
+/// * `Err(InputError)` if stdin is closed, the attempts are used up or reading fails.
+pub fn read_multiselect_with_details<T>(
+    prompt: &str,
+    options: &[(T, String)],
+    limit: Option<usize>,
+    details: &PromptDetails<'_>,
+) -> Result<Vec<T>, InputError>
+where
+    T: 'static + Clone + PartialEq + Debug,
 {
-    let mut selected = 0;
-    let mut selected_options = vec![false; options.len()];
+    if options.is_empty() {
+        return Err(InputError::Io(
+            "No options available for selection".to_string(),
+        ));
+    }
+
+    let mut terminal = Terminal::open(details.output);
+    if !terminal.reads_keys() {
+        return read_multiselect_numbered(&mut terminal, prompt, options, limit, details);
+    }
+
+    let renderer = details.renderer();
+    let mut frame = details.frame(PromptKind::Multiselect, prompt, options, &[]);
+    let deadline = Deadline::start(details, &terminal);
 
     loop {
-        clear_screen();
-        println!("{}:", prompt);
-        println!("Use Space to select/deselect, Enter to confirm");
-
-        for (i, (_, value)) in options.iter().enumerate() {
-            let marker = if selected_options[i] { "*" } else { " " };
-            if i == selected {
-                println!("> [{}] {}", marker, value);
-            } else {
-                println!("  [{}] {}", marker, value);
-            }
-        }
-        io::stdout()
-            .flush()
-            .map_err(|e| format!("Failed to flush stdout: {:?}", e))?;
-
-        match read_key_raw()? {
-            Key::Up => {
-                if selected > 0 {
-                    selected -= 1;
+        frame.seconds_left = Deadline::shown_seconds(deadline);
+        terminal.draw(&renderer.render(&frame))?;
+        frame.error = None;
+
+        let Some(key) = terminal.read_key_before(renderer, deadline)? else {
+            continue;
+        };
+        let count = frame
+            .options
+            .iter()
+            .filter(|option| option.selected)
+            .count();
+        let option = &mut frame.options[frame.cursor];
+        match key {
+            Key::Up => frame.cursor = frame.cursor.saturating_sub(1),
+            Key::Down if frame.cursor < options.len() - 1 => frame.cursor += 1,
+            Key::Help => frame.help = frame.help.take().xor(Some(details.help_text())),
+            Key::Space => match limit {
+                Some(limit) if !option.selected && count >= limit => {
+                    frame.error = Some(format!("Please choose at most {} options", limit));
                 }
-            }
-            Key::Down => {
-                if selected < options.len() - 1 {
-                    selected += 1;
+                _ => option.selected = !option.selected,
+            },
+            Key::Enter => {
+                if count == 0 {
+                    frame.error = Some("Please choose at least one option".to_string());
+                    continue;
                 }
+                terminal.draw(&renderer.clear())?;
+                return Ok(options
+                    .iter()
+                    .zip(&frame.options)
+                    .filter(|(_, option)| option.selected)
+                    .map(|((key, _), _)| key.clone())
+                    .collect());
             }
-            Key::Space => {
-                if selected_options[selected] {
-                    selected_options[selected] = false;
-                } else if limit.is_none()
-                    || selected_options.iter().filter(|&&x| x).count() < limit.unwrap()
-                {
-                    selected_options[selected] = true;
+            _ => {}
+        }
+    }
+}
+
+/// Reads a selection as a number typed on stdin.
+///
+/// # Arguments
+///
+/// * `terminal` - The terminal the prompt is read from.
+/// * `prompt` - The prompt message to be displayed to the user.
+/// * `options` - A list of options available for selection.
+/// * `details` - Help text, option descriptions and attempt limit of the prompt.
+///
+/// # Returns
+///
+/// * `Ok(T)` if the selection is successfully read.
+/// * `Err(InputError)` if stdin is closed, the attempts are used up or reading fails.
+fn read_select_numbered<T>(
+    terminal: &mut Terminal<'_>,
+    prompt: &str,
+    options: &[(T, String)],
+    details: &PromptDetails<'_>,
+) -> Result<T, InputError>
+where
+    T: 'static + Clone + Debug,
+{
+    print_numbered_options(terminal, prompt, options, details)?;
+    let mut attempts = Attempts::new(details);
+    let deadline = Deadline::start(details, terminal);
+    let hint = format!("Enter choice 1-{}:", options.len());
+    let mut frame = Frame::new(PromptKind::Text, &hint);
+
+    loop {
+        let line =
+            match terminal.read_frame_line(details.renderer(), &mut frame, deadline, false)? {
+                Entry::Line(line) if line.trim() != "?" => line,
+                _ if details.has_help() => {
+                    frame.help = Some(details.help_text());
+                    continue;
                 }
-            }
-            Key::Enter => {
-                let selected_keys: Vec<T> = options
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, _)| selected_options[*i])
-                    .map(|(_, (key, _))| key.clone())
-                    .collect();
-
-                if !selected_keys.is_empty() {
-                    clear_screen();
-                    return Ok(selected_keys);
+                Entry::Line(line) => line,
+                Entry::Help => continue,
+            };
+        let line = line.trim();
+        match parse_choice(line, options.len()) {
+            Ok(index) => return Ok(options[index].0.clone()),
+            Err(err) => attempts.fail(terminal, &mut frame, err)?,
+        }
+    }
+}
+
+/// Reads multiple selections as comma-separated numbers typed on stdin.
+///
+/// # Arguments
+///
+/// * `terminal` - The terminal the prompt is read from.
+/// * `prompt` - The prompt message to be displayed to the user.
+/// * `options` - A list of options available for selection.
+/// * `limit` - An optional limit on the number of selections.
+/// * `details` - Help text, option descriptions and attempt limit of the prompt.
+///
+/// # Returns
+///
+/// * `Ok(Vec<T>)` if the selections are successfully read.
+/// * `Err(InputError)` if stdin is closed, the attempts are used up or reading fails.
+fn read_multiselect_numbered<T>(
+    terminal: &mut Terminal<'_>,
+    prompt: &str,
+    options: &[(T, String)],
+    limit: Option<usize>,
+    details: &PromptDetails<'_>,
+) -> Result<Vec<T>, InputError>
+where
+    T: 'static + Clone + Debug,
+{
+    print_numbered_options(terminal, prompt, options, details)?;
+
+    let hint = match limit {
+        Some(limit) => format!("Enter choices as 1,3,4 (at most {}):", limit),
+        None => "Enter choices as 1,3,4:".to_string(),
+    };
+    let mut attempts = Attempts::new(details);
+    let deadline = Deadline::start(details, terminal);
+    let mut frame = Frame::new(PromptKind::Text, &hint);
+
+    loop {
+        let line =
+            match terminal.read_frame_line(details.renderer(), &mut frame, deadline, false)? {
+                Entry::Line(line) if line.trim() != "?" => line,
+                _ if details.has_help() => {
+                    frame.help = Some(details.help_text());
+                    continue;
                 }
+                Entry::Line(line) => line,
+                Entry::Help => continue,
+            };
+        let line = line.trim();
+        match parse_choices(line, options.len(), limit) {
+            Ok(indices) => {
+                return Ok(indices
+                    .into_iter()
+                    .map(|index| options[index].0.clone())
+                    .collect());
             }
-            _ => {}
+            Err(err) => attempts.fail(terminal, &mut frame, err)?,
+        }
+    }
+}
+
+/// Asks the user a yes/no question.
+///
+/// # Arguments
+///
+/// * `prompt` - The question to be displayed to the user.
+///
+/// # Returns
+///
+/// * `Ok(bool)` with the answer.
+/// * `Err(String)` if stdin cannot be read or is exhausted.
+pub fn read_confirm(prompt: &str) -> Result<bool, String> {
+    read_confirm_with_details(prompt, &PromptDetails::default()).map_err(String::from)
+}
+
+/// Asks the user a yes/no question with the renderer and output of a form.
+///
+/// # Arguments
+///
+/// * `prompt` - The question to be displayed to the user.
+/// * `details` - The renderer and output of the prompt.
+///
+/// # Returns
+///
+/// * `Ok(bool)` with the answer.
+/// * `Err(InputError)` if stdin cannot be read or is exhausted.
+pub fn read_confirm_with_details(
+    prompt: &str,
+    details: &PromptDetails<'_>,
+) -> Result<bool, InputError> {
+    let mut terminal = Terminal::open(details.output);
+    let renderer = details.renderer();
+    let mut frame = Frame::new(PromptKind::Text, prompt);
+    loop {
+        let Entry::Line(line) = terminal.read_frame_line(renderer, &mut frame, None, false)? else {
+            continue;
+        };
+        match parse_confirm(&line) {
+            Some(answer) => return Ok(answer),
+            None => frame.error = Some("Please answer 'y' or 'n'".to_string()),
         }
     }
 }
 
+/// Writes text shown between prompts, such as a section heading, where the prompts are written.
+///
+/// # Arguments
+///
+/// * `text` - The text, including its line breaks.
+/// * `output` - The stream prompts are written to when stdin is used.
+///
+/// # Returns
+///
+/// * `Ok(())` if the text is written.
+/// * `Err(String)` if writing fails.
+pub(crate) fn print_text(text: &str, output: PromptOutput) -> Result<(), String> {
+    Terminal::open(output).draw(text)
+}
+
+/// Parses a yes/no answer.
+///
+/// # Returns
+///
+/// * `Some(bool)` if the input is `y`, `yes`, `n` or `no` in any case, `None` otherwise.
+fn parse_confirm(input: &str) -> Option<bool> {
+    match input.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Prints the options of a select field as a numbered list, with their descriptions.
+fn print_numbered_options<T>(
+    terminal: &mut Terminal<'_>,
+    prompt: &str,
+    options: &[(T, String)],
+    details: &PromptDetails<'_>,
+) -> Result<(), String>
+where
+    T: 'static + Debug,
+{
+    let frame = details.frame(PromptKind::Numbered, prompt, options, &[]);
+    terminal.draw(&details.renderer().render(&frame))
+}
+
+/// Parses a one-based option number.
+///
+/// # Returns
+///
+/// * `Ok(usize)` with the zero-based index of the chosen option.
+/// * `Err(String)` if the input is not a number between 1 and `count`.
+fn parse_choice(input: &str, count: usize) -> Result<usize, String> {
+    match input.trim().parse::<usize>() {
+        Ok(choice) if (1..=count).contains(&choice) => Ok(choice - 1),
+        _ => Err(format!("Please enter a number between 1 and {}", count)),
+    }
+}
+
+/// Parses a comma-separated list of one-based option numbers.
+///
+/// # Returns
+///
+/// * `Ok(Vec<usize>)` with the zero-based indices of the chosen options, in option order.
+/// * `Err(String)` if any entry is invalid, nothing is chosen or `limit` is exceeded.
+fn parse_choices(input: &str, count: usize, limit: Option<usize>) -> Result<Vec<usize>, String> {
+    let mut chosen = vec![false; count];
+    for part in input
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        chosen[parse_choice(part, count)?] = true;
+    }
+
+    let indices: Vec<usize> = (0..count).filter(|&i| chosen[i]).collect();
+    if indices.is_empty() {
+        return Err("Please choose at least one option".to_string());
+    }
+    if let Some(limit) = limit
+        && indices.len() > limit
+    {
+        return Err(format!("Please choose at most {} options", limit));
+    }
+    Ok(indices)
+}
+
 /// An enum representing different key presses.
 pub enum Key {
     /// The up arrow key.
@@ -200,13 +991,402 @@ pub enum Key {
     Enter,
     /// The space key.
     Space,
+    /// The `?` key or F1, asking for help.
+    Help,
     /// Any other key.
     Other,
 }
 
+/// How interactive prompts reach the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteractionMode {
+    /// Stdin is a terminal and key presses are read from it directly.
+    Terminal,
+    /// Stdin is redirected, but the process has a controlling terminal at `/dev/tty`, which all prompts use.
+    ControllingTerminal,
+    /// No terminal is available, so options are chosen by number from stdin lines.
+    Numbered,
+}
+
+/// Detects how interactive prompts can reach the user.
+///
+/// # Returns
+///
+/// * The `InteractionMode` available to the current process.
+pub fn interaction_mode() -> InteractionMode {
+    detect_terminal().0
+}
+
+/// Detects how interactive prompts can reach the user.
+///
+/// # Returns
+///
+/// * The `InteractionMode`, with the controlling terminal in `ControllingTerminal` mode.
+fn detect_terminal() -> (InteractionMode, Option<&'static File>) {
+    if unsafe { isatty(STDIN_FILENO) } == 1 {
+        return (InteractionMode::Terminal, None);
+    }
+    match controlling_terminal() {
+        Some(file) => (InteractionMode::ControllingTerminal, Some(file)),
+        None => (InteractionMode::Numbered, None),
+    }
+}
+
+/// The controlling terminal of the process, opened on first use and shared by all prompts.
+static CONTROLLING_TERMINAL: OnceLock<Option<File>> = OnceLock::new();
+
+/// Returns the controlling terminal, opening `/dev/tty` for reading and writing the first time.
+///
+/// # Returns
+///
+/// * `Some(&File)` with the terminal, or `None` if the process has no controlling terminal.
+fn controlling_terminal() -> Option<&'static File> {
+    CONTROLLING_TERMINAL
+        .get_or_init(|| {
+            OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open("/dev/tty")
+                .ok()
+        })
+        .as_ref()
+}
+
+/// The terminal all prompts of the process are drawn on and read from.
+///
+/// In `ControllingTerminal` mode every prompt, text or select, uses
+/// `/dev/tty`, so redirected stdin is never mixed with terminal input.
+enum Terminal<'a> {
+    /// Stdin and stdout or stderr are used; `keys` tells whether stdin is a terminal sending key presses.
+    Stdio {
+        /// Whether stdin is a terminal.
+        keys: bool,
+        /// The stream prompts are written to.
+        output: PromptOutput,
+    },
+    /// The controlling terminal is used because stdin is redirected.
+    Device(&'a File),
+    /// The keystrokes of a running script are read and the frames are captured.
+    #[cfg(any(test, feature = "testing"))]
+    Scripted,
+}
+
+impl Terminal<'static> {
+    /// Opens the terminal for the current interaction mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The stream prompts are written to when stdin is used.
+    ///
+    /// # Returns
+    ///
+    /// * The running script, the controlling terminal if stdin is redirected
+    ///   and one exists, or stdin and the output stream otherwise.
+    fn open(output: PromptOutput) -> Self {
+        #[cfg(any(test, feature = "testing"))]
+        if crate::testing::is_active() {
+            return Terminal::Scripted;
+        }
+        match detect_terminal() {
+            (_, Some(file)) => Terminal::Device(file),
+            (mode, None) => Terminal::Stdio {
+                keys: mode == InteractionMode::Terminal,
+                output,
+            },
+        }
+    }
+}
+
+impl Terminal<'_> {
+    /// Checks whether key presses can be read, so select lists can be navigated.
+    ///
+    /// # Returns
+    ///
+    /// * `false` if there is no terminal and the numbered fallback should be used.
+    fn reads_keys(&self) -> bool {
+        !matches!(self, Terminal::Stdio { keys: false, .. })
+    }
+
+    /// Checks whether input and output both reach a terminal, so escape sequences can be used.
+    fn is_interactive(&self) -> bool {
+        match self {
+            Terminal::Stdio { keys, output } => *keys && unsafe { isatty(output.fd()) } == 1,
+            Terminal::Device(_) => true,
+            #[cfg(any(test, feature = "testing"))]
+            Terminal::Scripted => false,
+        }
+    }
+
+    /// Returns the file descriptor input is read from.
+    fn input_fd(&self) -> RawFd {
+        match self {
+            Terminal::Device(file) => file.as_raw_fd(),
+            _ => STDIN_FILENO,
+        }
+    }
+
+    /// Writes text to the terminal.
+    fn print(&mut self, text: &str) -> Result<(), String> {
+        let result = match self {
+            Terminal::Stdio {
+                output: PromptOutput::Stdout,
+                ..
+            } => io::stdout().write_all(text.as_bytes()),
+            Terminal::Stdio {
+                output: PromptOutput::Stderr,
+                ..
+            } => io::stderr().write_all(text.as_bytes()),
+            Terminal::Device(file) => file.write_all(text.as_bytes()),
+            #[cfg(any(test, feature = "testing"))]
+            Terminal::Scripted => {
+                crate::testing::record(text);
+                Ok(())
+            }
+        };
+        result.map_err(|e| format!("Failed to write to terminal: {:?}", e))
+    }
+
+    /// Flushes pending output to the terminal.
+    fn flush(&mut self) -> Result<(), String> {
+        let result = match self {
+            Terminal::Stdio {
+                output: PromptOutput::Stdout,
+                ..
+            } => io::stdout().flush(),
+            Terminal::Stdio {
+                output: PromptOutput::Stderr,
+                ..
+            } => io::stderr().flush(),
+            Terminal::Device(file) => file.flush(),
+            #[cfg(any(test, feature = "testing"))]
+            Terminal::Scripted => Ok(()),
+        };
+        result.map_err(|e| format!("Failed to flush terminal: {:?}", e))
+    }
+
+    /// Writes a frame to the terminal and flushes it.
+    fn draw(&mut self, text: &str) -> Result<(), String> {
+        self.print(text)?;
+        self.flush()
+    }
+
+    /// Reads a raw key press from the terminal before a deadline.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Key))` if a key is pressed.
+    /// * `Ok(None)` if the countdown should be redrawn.
+    /// * `Err(InputError)` if the deadline passes or reading fails.
+    fn read_key_before(
+        &mut self,
+        renderer: &dyn Renderer,
+        deadline: Option<Deadline>,
+    ) -> Result<Option<Key>, InputError> {
+        #[cfg(any(test, feature = "testing"))]
+        if let Terminal::Scripted = self {
+            return crate::testing::read_key().map(Some);
+        }
+        let fd = self.input_fd();
+        let Some(deadline) = deadline else {
+            return read_key_from(fd, None);
+        };
+        let key = match deadline.next_wait() {
+            Ok(wait) => read_key_from(fd, Some(wait))?,
+            Err(_) => None,
+        };
+        if key.is_none() && deadline.seconds_left() == 0 {
+            self.draw(&renderer.clear())?;
+            return Err(InputError::TimedOut);
+        }
+        Ok(key)
+    }
+
+    /// Draws a frame of a text prompt and reads one line before a deadline.
+    ///
+    /// The help text and error of the frame are shown once and then cleared.
+    /// On an interactive terminal, and for secret input on any terminal, the
+    /// line is edited in non-canonical mode, so F1 can be pressed, the
+    /// placeholder is cleared on the first keystroke and Ctrl-C ends the prompt
+    /// after the terminal attributes have been restored.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The renderer drawing the frame.
+    /// * `frame` - The frame of the prompt.
+    /// * `deadline` - The time by which the line has to be entered, if any.
+    /// * `secret` - Whether the line is read without echoing it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Entry)` with the line read, or `Entry::Help` if F1 was pressed.
+    /// * `Err(InputError)` if the input cannot be read or is exhausted, or the deadline passes.
+    fn read_frame_line(
+        &mut self,
+        renderer: &dyn Renderer,
+        frame: &mut Frame<'_>,
+        deadline: Option<Deadline>,
+        secret: bool,
+    ) -> Result<Entry, InputError> {
+        frame.seconds_left = Deadline::shown_seconds(deadline);
+        self.draw(&renderer.render(frame))?;
+        frame.help = None;
+        frame.error = None;
+
+        #[cfg(any(test, feature = "testing"))]
+        if let Some(entry) = crate::testing::read_line() {
+            return entry;
+        }
+
+        let fd = self.input_fd();
+        let read = if (secret || self.is_interactive())
+            && let Some(_mode) = TerminalMode::clear(fd, ICANON | ECHO | ISIG)
+        {
+            self.edit_line(deadline, frame.placeholder.is_some(), secret)
+        } else {
+            self.read_line(deadline).map(Entry::Line)
+        };
+        if read.is_err() {
+            self.draw("\n")?;
+        }
+        read
+    }
+
+    /// Reads a line one byte at a time, so no input after the line is consumed.
+    ///
+    /// While waiting, the countdown of the deadline is redrawn at the start of
+    /// the prompt line once a second.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - The time by which the line has to be entered, if any.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` with the line without its line break.
+    /// * `Err(InputError)` if the input is closed, the deadline passes or reading fails.
+    fn read_line(&mut self, deadline: Option<Deadline>) -> Result<String, InputError> {
+        let mut line = Vec::new();
+        loop {
+            match self.read_byte(deadline)? {
+                None if line.is_empty() => return Err(InputError::Eof),
+                None | Some(b'\n') => break,
+                Some(byte) => line.push(byte),
+            }
+        }
+        Ok(String::from_utf8_lossy(&line).into_owned())
+    }
+
+    /// Reads a line from a terminal in non-canonical mode, echoing and editing it.
+    ///
+    /// Backspace removes the last character, F1 asks for help, Ctrl-C
+    /// interrupts the prompt and other control keys are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - The time by which the line has to be entered, if any.
+    /// * `placeholder` - Whether a placeholder is drawn after the cursor, cleared on the first keystroke.
+    /// * `secret` - Whether the typed characters are hidden instead of echoed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Entry)` with the line without its line break, or `Entry::Help` if F1 was pressed.
+    /// * `Err(InputError)` if the input is closed or interrupted, the deadline passes or reading fails.
+    fn edit_line(
+        &mut self,
+        deadline: Option<Deadline>,
+        mut placeholder: bool,
+        secret: bool,
+    ) -> Result<Entry, InputError> {
+        let fd = self.input_fd();
+        let mut line = Vec::new();
+        let mut echoed = 0;
+        loop {
+            let byte = self.read_byte(deadline)?;
+            if placeholder {
+                self.draw(CLEAR_TO_END_OF_LINE)?;
+                placeholder = false;
+            }
+            match byte {
+                None | Some(EOT) if line.is_empty() => return Err(InputError::Eof),
+                Some(INTERRUPT) => return Err(InputError::Interrupted),
+                None | Some(b'\n' | b'\r') => {
+                    self.draw("\n")?;
+                    return Ok(Entry::Line(String::from_utf8_lossy(&line).into_owned()));
+                }
+                Some(ESC) => {
+                    if let Key::Help = decode_escape(fd) {
+                        self.draw("\n")?;
+                        return Ok(Entry::Help);
+                    }
+                }
+                Some(BACKSPACE | DELETE) => {
+                    // Remove the continuation bytes and the first byte of the last character.
+                    while let Some(byte) = line.pop() {
+                        if byte & 0xC0 != 0x80 {
+                            if !secret {
+                                self.draw("\x08 \x08")?;
+                            }
+                            break;
+                        }
+                    }
+                    echoed = line.len();
+                }
+                Some(byte) if byte < 0x20 => {}
+                Some(byte) if secret => line.push(byte),
+                Some(byte) => {
+                    line.push(byte);
+                    // Multi-byte characters are echoed once they are complete.
+                    if let Ok(text) = std::str::from_utf8(&line[echoed..]) {
+                        let text = text.to_string();
+                        self.draw(&text)?;
+                        echoed = line.len();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads one byte of input before a deadline.
+    ///
+    /// While waiting, the countdown of the deadline is redrawn at the start of
+    /// the prompt line once a second.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(u8))` with the byte read, or `Ok(None)` if the input is closed.
+    /// * `Err(InputError)` if the deadline passes or reading fails.
+    fn read_byte(&mut self, deadline: Option<Deadline>) -> Result<Option<u8>, InputError> {
+        let fd = self.input_fd();
+        loop {
+            if let Some(deadline) = deadline {
+                let wait = deadline.next_wait()?;
+                if !wait_for_input(fd, wait)
+                    .map_err(|e| format!("Failed to wait for input: {:?}", e))?
+                {
+                    if deadline.countdown && deadline.seconds_left() > 0 {
+                        self.draw(&format!(
+                            "\x1B7\r{}\x1B8",
+                            countdown_prefix(deadline.seconds_left())
+                        ))?;
+                    }
+                    continue;
+                }
+            }
+
+            match read_raw_byte(fd) {
+                Ok(byte) => return Ok(byte),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => {
+                    return Err(InputError::Io(format!("Failed to read line: {:?}", error)));
+                }
+            }
+        }
+    }
+}
+
 /// Clears the terminal screen.
 pub fn clear_screen() {
-    print!("\x1B[2J\x1B[1;1H");
+    print!("{}", AnsiRenderer.clear());
     if io::stdout().flush().is_err() {
         eprintln!("Failed to flush stdout");
     }
@@ -217,34 +1397,546 @@ pub fn clear_screen() {
 /// # Returns
 ///
 /// * `Ok(Key)` if the key press is successfully read.
-/// * `Err(String)` if there is an error reading the key press.
+/// * `Err(String)` if Ctrl-C is pressed or there is an error reading the key press.
 pub fn read_key_raw() -> Result<Key, String> {
-    let stdin_fd = stdin().as_raw_fd();
-    let mut termios = unsafe { std::mem::zeroed() };
-    if unsafe { tcgetattr(stdin_fd, &mut termios) } < 0 {
-        return Err("Failed to get terminal attributes".to_string());
+    read_key_from(STDIN_FILENO, None)
+        .map(|key| key.unwrap_or(Key::Other))
+        .map_err(String::from)
+}
+
+/// Reads a raw key press from the terminal behind `fd`, switching it to non-canonical mode.
+///
+/// Signals are disabled while waiting, so Ctrl-C is read as a key press and
+/// the terminal attributes are restored before it ends the prompt.
+///
+/// # Arguments
+///
+/// * `fd` - The file descriptor of the terminal.
+/// * `wait` - How long to wait for a key press, or `None` to wait indefinitely.
+///
+/// # Returns
+///
+/// * `Ok(Some(Key))` if the key press is successfully read.
+/// * `Ok(None)` if no key was pressed within `wait`.
+/// * `Err(InputError::Interrupted)` if Ctrl-C is pressed.
+/// * `Err(InputError::Eof)` if the input has ended or Ctrl-D is pressed.
+/// * `Err(InputError::Io)` if there is an error reading the key press.
+fn read_key_from(fd: RawFd, wait: Option<Duration>) -> Result<Option<Key>, InputError> {
+    let _mode = TerminalMode::clear(fd, ICANON | ECHO | ISIG)
+        .ok_or_else(|| "Failed to set terminal attributes".to_string())?;
+    if let Some(wait) = wait
+        && !wait_for_input(fd, wait).map_err(|_| "Failed to read from stdin".to_string())?
+    {
+        return Ok(None);
+    }
+    match read_raw_byte(fd) {
+        Ok(Some(INTERRUPT)) => Err(InputError::Interrupted),
+        Ok(None | Some(EOT)) => Err(InputError::Eof),
+        Ok(Some(byte)) => Ok(Some(decode_key(fd, byte))),
+        Err(_) => Err(InputError::Io("Failed to read from stdin".to_string())),
+    }
+}
+
+/// Decodes a key press from its first byte, reading the rest of escape sequences from `fd`.
+fn decode_key(fd: RawFd, byte: u8) -> Key {
+    match byte {
+        b'\n' => Key::Enter,
+        b' ' => Key::Space,
+        b'?' => Key::Help,
+        ESC => decode_escape(fd),
+        _ => Key::Other,
+    }
+}
+
+/// The byte sent when Ctrl-C is pressed while signals are disabled.
+const INTERRUPT: u8 = 0x03;
+
+/// The byte ending the input when Ctrl-D is pressed in non-canonical mode.
+const EOT: u8 = 0x04;
+
+/// The byte sent by the Backspace key of some terminals.
+const BACKSPACE: u8 = 0x08;
+
+/// The byte sent by the Backspace key of most terminals.
+const DELETE: u8 = 0x7F;
+
+/// The byte starting the escape sequences of special keys.
+const ESC: u8 = 0x1B;
+
+/// Clears the rest of the line after the cursor.
+const CLEAR_TO_END_OF_LINE: &str = "\x1B[K";
+
+/// How long to wait for the rest of an escape sequence after `ESC`.
+const ESCAPE_WAIT: Duration = Duration::from_millis(50);
+
+/// Reads one byte from `fd`.
+///
+/// # Returns
+///
+/// * `Ok(Some(u8))` with the byte read, or `Ok(None)` if the input is closed.
+/// * `Err(io::Error)` if reading fails.
+fn read_raw_byte(fd: RawFd) -> io::Result<Option<u8>> {
+    let mut byte = 0u8;
+    match unsafe { libc::read(fd, &mut byte as *mut u8 as *mut c_void, 1) } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => Ok(None),
+        _ => Ok(Some(byte)),
+    }
+}
+
+/// Decodes the rest of an escape sequence sent by a special key after `ESC`.
+///
+/// The arrow keys are sent as `ESC [ A` or `ESC O A`, and F1 as `ESC O P`
+/// or `ESC [ 1 1 ~`. The whole sequence is consumed, so none of its bytes
+/// are read as typed characters.
+///
+/// # Returns
+///
+/// * The decoded key, or `Key::Other` for other or incomplete sequences.
+fn decode_escape(fd: RawFd) -> Key {
+    let next = || match wait_for_input(fd, ESCAPE_WAIT) {
+        Ok(true) => read_raw_byte(fd).ok().flatten(),
+        _ => None,
+    };
+    match next() {
+        Some(b'O') => match next() {
+            Some(b'A') => Key::Up,
+            Some(b'B') => Key::Down,
+            Some(b'P') => Key::Help,
+            _ => Key::Other,
+        },
+        Some(b'[') => {
+            let mut parameters = Vec::new();
+            loop {
+                match next() {
+                    Some(last @ 0x40..=0x7E) => {
+                        break match (parameters.as_slice(), last) {
+                            ([], b'A') => Key::Up,
+                            ([], b'B') => Key::Down,
+                            (b"11", b'~') => Key::Help,
+                            _ => Key::Other,
+                        };
+                    }
+                    Some(byte) => parameters.push(byte),
+                    None => break Key::Other,
+                }
+            }
+        }
+        _ => Key::Other,
+    }
+}
+
+/// Changes the local flags of a terminal and restores its attributes when dropped,
+/// so they are restored even if reading panics.
+struct TerminalMode {
+    /// The file descriptor of the terminal.
+    fd: RawFd,
+    /// The attributes of the terminal before the change.
+    saved: termios,
+}
+
+impl TerminalMode {
+    /// Clears local flags of the terminal behind `fd`, such as `ECHO` or `ISIG`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(TerminalMode)` restoring the attributes when dropped.
+    /// * `None` if `fd` is not a terminal or its attributes cannot be changed.
+    fn clear(fd: RawFd, flags: tcflag_t) -> Option<Self> {
+        let mut saved: termios = unsafe { std::mem::zeroed() };
+        if unsafe { tcgetattr(fd, &mut saved) } < 0 {
+            return None;
+        }
+        let mut changed = saved;
+        changed.c_lflag &= !flags;
+        if unsafe { tcsetattr(fd, TCSANOW, &changed) } < 0 {
+            return None;
+        }
+        Some(Self { fd, saved })
+    }
+}
+
+impl Drop for TerminalMode {
+    /// Restores the saved attributes of the terminal.
+    fn drop(&mut self) {
+        unsafe { tcsetattr(self.fd, TCSANOW, &self.saved) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_builder::FormBuilder;
+    use crate::render::PlainRenderer;
+    use crate::testing::Script;
+    use crate::validation::NamedRule;
+    use std::io::Read;
+    use std::os::unix::io::FromRawFd;
+
+    /// Opens a pipe holding `bytes`, with its write end closed.
+    ///
+    /// # Returns
+    ///
+    /// * The read end of the pipe.
+    fn pipe_with(bytes: &[u8]) -> File {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let (reader, mut writer) =
+            unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+        writer.write_all(bytes).unwrap();
+        reader
+    }
+
+    /// Opens a pseudo-terminal.
+    ///
+    /// # Returns
+    ///
+    /// * The controlling end and the terminal end of the pseudo-terminal.
+    fn open_pty() -> (File, File) {
+        let (mut controller, mut device) = (0, 0);
+        let opened = unsafe {
+            libc::openpty(
+                &mut controller,
+                &mut device,
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                std::ptr::null(),
+            )
+        };
+        assert_eq!(opened, 0);
+        unsafe { (File::from_raw_fd(controller), File::from_raw_fd(device)) }
+    }
+
+    /// Returns the local flags of the terminal behind `fd`.
+    fn local_flags(fd: RawFd) -> tcflag_t {
+        let mut attributes: termios = unsafe { std::mem::zeroed() };
+        assert_eq!(unsafe { tcgetattr(fd, &mut attributes) }, 0);
+        attributes.c_lflag
+    }
+
+    /// Reads a line from a pseudo-terminal, typing `bytes` once echo and signals are off.
+    ///
+    /// # Returns
+    ///
+    /// * The entry read, and the local flags of the terminal afterwards and before.
+    fn type_on_pty(
+        bytes: &'static [u8],
+        secret: bool,
+    ) -> (Result<Entry, InputError>, tcflag_t, tcflag_t) {
+        let (mut controller, device) = open_pty();
+        let fd = device.as_raw_fd();
+        let flags = local_flags(fd);
+        let typist = std::thread::spawn(move || {
+            while local_flags(fd) & ISIG != 0 {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            controller.write_all(bytes).unwrap();
+            controller
+        });
+        let mut terminal = Terminal::Device(&device);
+        let mut frame = Frame::new(PromptKind::Text, "Password:");
+        let read = terminal.read_frame_line(&AnsiRenderer, &mut frame, None, secret);
+        let _controller = typist.join().unwrap();
+        (read, local_flags(fd), flags)
+    }
+
+    /// Opens a socket to be used as a terminal reading `bytes`, which stays open.
+    ///
+    /// # Returns
+    ///
+    /// * The socket of the terminal, and the other end receiving what is drawn.
+    fn socket_pair(bytes: &[u8]) -> (File, File) {
+        let mut fds = [0; 2];
+        let opened =
+            unsafe { libc::socketpair(libc::AF_UNIX, libc::SOCK_STREAM, 0, fds.as_mut_ptr()) };
+        assert_eq!(opened, 0);
+        let (device, mut peer) = unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+        peer.write_all(bytes).unwrap();
+        (device, peer)
+    }
+
+    /// Closes the socket of a terminal and returns everything drawn on it.
+    fn drawn(device: File, mut peer: File) -> String {
+        drop(device);
+        let mut text = String::new();
+        peer.read_to_string(&mut text).unwrap();
+        text
+    }
+
+    /// Creates a deadline passing after `millis` milliseconds, without a countdown.
+    fn deadline_in(millis: u64) -> Option<Deadline> {
+        Some(Deadline {
+            at: Instant::now() + Duration::from_millis(millis),
+            countdown: false,
+        })
+    }
+
+    /// Decodes the keys sent as `bytes`.
+    fn decode_all(bytes: &[u8]) -> Vec<Key> {
+        let reader = pipe_with(bytes);
+        let fd = reader.as_raw_fd();
+        let mut keys = Vec::new();
+        while let Ok(Some(byte)) = read_raw_byte(fd) {
+            keys.push(decode_key(fd, byte));
+        }
+        keys
+    }
+
+    #[test]
+    fn test_decode_keys() {
+        assert!(matches!(
+            decode_all(b"\x1BOP\x1B[11~\x1B[A\x1BOB")[..],
+            [Key::Help, Key::Help, Key::Up, Key::Down]
+        ));
+        assert!(matches!(
+            decode_all(b"PAB? \n")[..],
+            [
+                Key::Other,
+                Key::Other,
+                Key::Other,
+                Key::Help,
+                Key::Space,
+                Key::Enter
+            ]
+        ));
+        assert!(matches!(
+            decode_all(b"\x1B[5~x")[..],
+            [Key::Other, Key::Other]
+        ));
+    }
+
+    #[test]
+    fn test_hidden_line_restores_terminal() {
+        let (read, after, before) = type_on_pty(b"pw\x7Fd\n", true);
+        assert_eq!(read, Ok(Entry::Line("pd".to_string())));
+        assert_eq!(before & (ECHO | ISIG), ECHO | ISIG);
+        assert_eq!(after, before);
+
+        let (read, after, before) = type_on_pty(b"pw\x03", true);
+        assert_eq!(read, Err(InputError::Interrupted));
+        assert_eq!(after, before);
+
+        let (read, after, before) = type_on_pty(b"Ann\x03", false);
+        assert_eq!(read, Err(InputError::Interrupted));
+        assert_eq!(after, before);
+    }
+
+    #[test]
+    fn test_multibyte_lines() {
+        let (device, _peer) = socket_pair("zażółć\n".as_bytes());
+        let mut terminal = Terminal::Device(&device);
+        assert_eq!(terminal.read_line(None), Ok("zażółć".to_string()));
+
+        let (device, peer) = socket_pair("ñé\x7Fü\n".as_bytes());
+        let mut terminal = Terminal::Device(&device);
+        assert_eq!(
+            terminal.edit_line(None, false, false),
+            Ok(Entry::Line("ñü".to_string()))
+        );
+        assert_eq!(drawn(device, peer), "ñé\x08 \x08ü\n");
+
+        let (device, peer) = socket_pair("ñé\x7F\n".as_bytes());
+        let mut terminal = Terminal::Device(&device);
+        assert_eq!(
+            terminal.edit_line(None, false, true),
+            Ok(Entry::Line("ñ".to_string()))
+        );
+        assert_eq!(drawn(device, peer), "\n");
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let started = Instant::now();
+        let (device, _peer) = socket_pair(b"");
+        let mut terminal = Terminal::Device(&device);
+        assert_eq!(
+            terminal.read_line(deadline_in(50)),
+            Err(InputError::TimedOut)
+        );
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        let (device, _peer) = socket_pair(b"4");
+        let mut terminal = Terminal::Device(&device);
+        assert_eq!(
+            terminal.read_line(deadline_in(50)),
+            Err(InputError::TimedOut)
+        );
+
+        let (device, _peer) = socket_pair(b"42\n");
+        let mut terminal = Terminal::Device(&device);
+        assert_eq!(terminal.read_line(deadline_in(1000)), Ok("42".to_string()));
+
+        let (device, peer) = socket_pair(b"");
+        let mut terminal = Terminal::Device(&device);
+        let mut frame = Frame::new(PromptKind::Text, "Enter age:");
+        let read = terminal.read_frame_line(&PlainRenderer, &mut frame, deadline_in(50), false);
+        assert_eq!(read, Err(InputError::TimedOut));
+        assert_eq!(drawn(device, peer), "Enter age: \n");
+
+        let (_controller, device) = open_pty();
+        let fd = device.as_raw_fd();
+        let flags = local_flags(fd);
+        let mut terminal = Terminal::Device(&device);
+        let read = terminal.read_key_before(&PlainRenderer, deadline_in(50));
+        assert!(matches!(read, Err(InputError::TimedOut)));
+        assert_eq!(local_flags(fd), flags);
+    }
+
+    #[test]
+    fn test_key_at_end_of_input() {
+        let (mut controller, device) = open_pty();
+        let fd = device.as_raw_fd();
+        let typist = std::thread::spawn(move || {
+            while local_flags(fd) & ICANON != 0 {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            controller.write_all(&[EOT]).unwrap();
+            controller
+        });
+        let read = read_key_from(fd, None);
+        let _controller = typist.join().unwrap();
+        assert!(matches!(read, Err(InputError::Eof)));
+    }
+
+    #[test]
+    fn test_deadline_countdown() {
+        let deadline = Deadline {
+            at: Instant::now() + Duration::from_millis(2500),
+            countdown: true,
+        };
+        assert_eq!(deadline.seconds_left(), 3);
+        assert_eq!(Deadline::shown_seconds(Some(deadline)), Some(3));
+        assert!(deadline.next_wait().unwrap() <= Duration::from_millis(500));
+
+        let silent = Deadline {
+            countdown: false,
+            ..deadline
+        };
+        assert_eq!(Deadline::shown_seconds(Some(silent)), None);
+        assert!(silent.next_wait().unwrap() > Duration::from_secs(2));
+
+        let passed = Deadline {
+            at: Instant::now(),
+            countdown: true,
+        };
+        assert_eq!(passed.seconds_left(), 0);
+        assert_eq!(passed.next_wait(), Err(InputError::TimedOut));
+    }
+
+    #[test]
+    fn test_read_input_parse_errors() {
+        let validator = Validator::named(NamedRule::NotEmpty, None);
+        let (value, transcript) = Script::new()
+            .line("")
+            .line("old")
+            .line("42")
+            .run(|| read_input::<u32>("Enter age:", Some(&validator)));
+        assert_eq!(
+            value,
+            Err("ParseIntError { kind: InvalidDigit }".to_string())
+        );
+        assert_eq!(transcript.frames.len(), 2);
+        assert_eq!(transcript.unused.len(), 3);
+
+        let details = PromptDetails {
+            max_attempts: Some(2),
+            ..PromptDetails::default()
+        };
+        let (value, _) = Script::new()
+            .line("old")
+            .line("42")
+            .run(|| read_input_with_details("Enter age:", None, &details, parse_from_str::<u32>));
+        assert_eq!(value, Ok(42));
+    }
+
+    #[test]
+    fn test_help_and_question_mark() {
+        let build = || {
+            FormBuilder::new()
+                .add_field::<String>("answer", "Answer:", None)
+                .help("answer", "Any text.")
+                .build()
+        };
+
+        let mut form = build();
+        let (result, transcript) = Script::new().line("?").line("?").fill(&mut form);
+        assert_eq!(result, Ok(()));
+        assert_eq!(form.get_value::<String>("answer"), Ok("?".to_string()));
+        assert_eq!(
+            transcript.frames[1],
+            "Any text.\nEnter ? again to answer with a question mark.\nAnswer: "
+        );
+
+        let mut form = build();
+        let (result, transcript) = Script::new().help().line("Ann").fill(&mut form);
+        assert_eq!(result, Ok(()));
+        assert_eq!(form.get_value::<String>("answer"), Ok("Ann".to_string()));
+        assert!(transcript.frames[1].starts_with("Any text.\n"));
+
+        let mut form = FormBuilder::new()
+            .add_field::<String>("answer", "Answer:", None)
+            .build();
+        let (result, _) = Script::new().line("?").fill(&mut form);
+        assert_eq!(result, Ok(()));
+        assert_eq!(form.get_value::<String>("answer"), Ok("?".to_string()));
+    }
+
+    #[test]
+    fn test_parse_choice() {
+        assert_eq!(parse_choice("1", 3), Ok(0));
+        assert_eq!(parse_choice(" 3 ", 3), Ok(2));
+        assert!(parse_choice("0", 3).is_err());
+        assert!(parse_choice("4", 3).is_err());
+        assert!(parse_choice("two", 3).is_err());
     }
 
-    termios.c_lflag &= !(ICANON | ECHO);
-    if unsafe { tcsetattr(stdin_fd, TCSANOW, &termios) } < 0 {
-        return Err("Failed to set terminal attributes".to_string());
+    #[test]
+    fn test_parse_choices() {
+        assert_eq!(parse_choices("1,3", 5, None), Ok(vec![0, 2]));
+        assert_eq!(parse_choices("4, 1 ,4", 5, None), Ok(vec![0, 3]));
+        assert!(parse_choices("", 5, None).is_err());
+        assert!(parse_choices("1,6", 5, None).is_err());
     }
 
-    let mut buffer = [0; 1];
-    if stdin().read_exact(&mut buffer).is_err() {
-        return Err("Failed to read from stdin".to_string());
+    #[test]
+    fn test_parse_confirm() {
+        assert_eq!(parse_confirm("y"), Some(true));
+        assert_eq!(parse_confirm(" YES "), Some(true));
+        assert_eq!(parse_confirm("n"), Some(false));
+        assert_eq!(parse_confirm("No"), Some(false));
+        assert_eq!(parse_confirm("maybe"), None);
     }
 
-    termios.c_lflag |= ICANON | ECHO;
-    if unsafe { tcsetattr(stdin_fd, TCSANOW, &termios) } < 0 {
-        return Err("Failed to reset terminal attributes".to_string());
+    #[test]
+    fn test_parse_choices_limit() {
+        assert_eq!(parse_choices("2,5", 5, Some(2)), Ok(vec![1, 4]));
+        assert!(parse_choices("1,2,3", 5, Some(2)).is_err());
     }
 
-    match buffer[0] {
-        65 => Ok(Key::Up),
-        66 => Ok(Key::Down),
-        10 => Ok(Key::Enter),
-        32 => Ok(Key::Space),
-        _ => Ok(Key::Other),
+    #[test]
+    fn test_prompt_details() {
+        let requirements = vec!["Must not be empty".to_string()];
+        let descriptions = vec![(Value::Integer(2), "Unlimited seats".to_string())];
+        let details = PromptDetails {
+            help: Some("Your work email."),
+            requirements: &requirements,
+            descriptions: &descriptions,
+            ..PromptDetails::default()
+        };
+        assert!(details.has_help());
+        assert_eq!(
+            details.help_text(),
+            "Your work email.\nRequirements:\n  - Must not be empty\n"
+        );
+        assert_eq!(details.description(&Value::Integer(1)), None);
+        assert_eq!(
+            details.description(&Value::Integer(2)),
+            Some("Unlimited seats")
+        );
+        let options = [(1, "Free".to_string()), (2, "Pro".to_string())];
+        let frame = details.frame(PromptKind::Select, "Plan", &options, &[]);
+        assert_eq!(frame.options[0].description, None);
+        assert_eq!(frame.options[1].description, Some("Unlimited seats"));
+        assert!(!PromptDetails::default().has_help());
     }
 }
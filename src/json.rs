@@ -0,0 +1,441 @@
+use crate::value::{Value, ValueMap};
+
+/// Parses a JSON text into a `Value`.
+///
+/// Objects become `Value::Record`, arrays `Value::List`, numbers without a
+/// fraction or exponent that fit into an `i64` become `Value::Integer` and
+/// other numbers `Value::Float`.
+///
+/// # Parameters
+///
+/// * `input` - The JSON text.
+///
+/// # Returns
+///
+/// * `Ok(Value)` with the parsed value.
+/// * `Err(String)` if the text is not valid JSON.
+pub(crate) fn parse(input: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        input: input.as_bytes(),
+        position: 0,
+    };
+    let value = parser.parse_value(0)?;
+    parser.skip_whitespace();
+    if parser.position < parser.input.len() {
+        return Err(parser.error("Unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+/// Writes a `Value` as compact JSON text.
+///
+/// Records become objects with their entries in order, characters become
+/// strings and non-finite floats become `null`.
+///
+/// # Parameters
+///
+/// * `value` - The value to be written.
+///
+/// # Returns
+///
+/// * The JSON text.
+pub(crate) fn write(value: &Value) -> String {
+    let mut output = String::new();
+    write_value(value, &mut output);
+    output
+}
+
+/// Appends a value as JSON text to `output`.
+fn write_value(value: &Value, output: &mut String) {
+    match value {
+        Value::String(value) => write_string(value, output),
+        Value::Char(value) => write_string(&value.to_string(), output),
+        Value::Integer(value) => output.push_str(&value.to_string()),
+        Value::Float(value) if value.is_finite() => {
+            let text = value.to_string();
+            output.push_str(&text);
+            if !text.contains(['.', 'e']) {
+                output.push_str(".0");
+            }
+        }
+        Value::Float(_) | Value::Null => output.push_str("null"),
+        Value::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+        Value::List(values) => {
+            output.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write_value(value, output);
+            }
+            output.push(']');
+        }
+        Value::Record(values) => {
+            output.push('{');
+            for (i, (name, value)) in values.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write_string(name, output);
+                output.push(':');
+                write_value(value, output);
+            }
+            output.push('}');
+        }
+    }
+}
+
+/// Appends a string as a quoted JSON string to `output`.
+fn write_string(value: &str, output: &mut String) {
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+/// The maximum nesting depth of arrays and objects.
+const MAX_DEPTH: usize = 128;
+
+/// A recursive descent parser over the bytes of a JSON text.
+struct Parser<'a> {
+    /// The JSON text.
+    input: &'a [u8],
+    /// The position of the next byte to be read.
+    position: usize,
+}
+
+impl Parser<'_> {
+    /// Creates an error message pointing at the current position.
+    fn error(&self, message: &str) -> String {
+        format!("{} at position {}", message, self.position)
+    }
+
+    /// Returns the next byte without consuming it.
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    /// Skips spaces, tabs and line breaks.
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.position += 1;
+        }
+    }
+
+    /// Consumes the expected byte, or fails.
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected '{}'", byte as char)))
+        }
+    }
+
+    /// Consumes the expected keyword, or fails.
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, String> {
+        if self.input[self.position..].starts_with(keyword.as_bytes()) {
+            self.position += keyword.len();
+            Ok(value)
+        } else {
+            Err(self.error("Invalid literal"))
+        }
+    }
+
+    /// Parses any JSON value.
+    fn parse_value(&mut self, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("Nesting too deep"));
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.parse_object(depth),
+            Some(b'[') => self.parse_array(depth),
+            Some(b'"') => self.parse_string().map(Value::String),
+            Some(b't') => self.keyword("true", Value::Bool(true)),
+            Some(b'f') => self.keyword("false", Value::Bool(false)),
+            Some(b'n') => self.keyword("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(self.error("Unexpected character")),
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    /// Parses an object into a `Value::Record`.
+    fn parse_object(&mut self, depth: usize) -> Result<Value, String> {
+        self.expect(b'{')?;
+        let mut values = ValueMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(Value::Record(values));
+        }
+        loop {
+            self.skip_whitespace();
+            let name = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            let value = self.parse_value(depth + 1)?;
+            values.insert(&name, value);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(Value::Record(values));
+                }
+                _ => return Err(self.error("Expected ',' or '}'")),
+            }
+        }
+    }
+
+    /// Parses an array into a `Value::List`.
+    fn parse_array(&mut self, depth: usize) -> Result<Value, String> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Value::List(values));
+        }
+        loop {
+            values.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(Value::List(values));
+                }
+                _ => return Err(self.error("Expected ',' or ']'")),
+            }
+        }
+    }
+
+    /// Parses a string, resolving escape sequences.
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut result = String::new();
+        loop {
+            let start = self.position;
+            while matches!(self.peek(), Some(byte) if byte != b'"' && byte != b'\\' && byte >= 0x20)
+            {
+                self.position += 1;
+            }
+            // The input is a `&str` and the run stops at ASCII bytes, so it is valid UTF-8.
+            result.push_str(std::str::from_utf8(&self.input[start..self.position]).unwrap());
+            match self.peek() {
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(result);
+                }
+                Some(b'\\') => {
+                    self.position += 1;
+                    result.push(self.parse_escape()?);
+                }
+                Some(_) => return Err(self.error("Control character in string")),
+                None => return Err(self.error("Unterminated string")),
+            }
+        }
+    }
+
+    /// Parses the escape sequence following a backslash.
+    fn parse_escape(&mut self) -> Result<char, String> {
+        let byte = self
+            .peek()
+            .ok_or_else(|| self.error("Unterminated string"))?;
+        self.position += 1;
+        Ok(match byte {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let high = self.parse_hex()?;
+                if (0xD800..0xDC00).contains(&high) {
+                    if !self.input[self.position..].starts_with(b"\\u") {
+                        return Err(self.error("Unpaired surrogate"));
+                    }
+                    self.position += 2;
+                    let low = self.parse_hex()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(self.error("Unpaired surrogate"));
+                    }
+                    let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                    char::from_u32(code).ok_or_else(|| self.error("Invalid code point"))?
+                } else {
+                    char::from_u32(high).ok_or_else(|| self.error("Unpaired surrogate"))?
+                }
+            }
+            _ => return Err(self.error("Invalid escape sequence")),
+        })
+    }
+
+    /// Parses the four hex digits of a `\u` escape.
+    fn parse_hex(&mut self) -> Result<u32, String> {
+        let digits = self
+            .input
+            .get(self.position..self.position + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("Invalid unicode escape"))?;
+        self.position += 4;
+        Ok(u32::from_str_radix(digits, 16).unwrap())
+    }
+
+    /// Parses a number into a `Value::Integer` or `Value::Float`.
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let start = self.position;
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.position += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(self.error("Invalid number")),
+        }
+        let mut integer = true;
+        if self.peek() == Some(b'.') {
+            integer = false;
+            self.position += 1;
+            self.require_digits()?;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            integer = false;
+            self.position += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            self.require_digits()?;
+        }
+        let text = std::str::from_utf8(&self.input[start..self.position]).unwrap();
+        match text.parse::<i64>() {
+            Ok(value) if integer => Ok(Value::Integer(value)),
+            _ => text
+                .parse::<f64>()
+                .map(Value::Float)
+                .map_err(|_| self.error("Invalid number")),
+        }
+    }
+
+    /// Skips a run of digits.
+    fn skip_digits(&mut self) {
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.position += 1;
+        }
+    }
+
+    /// Skips a run of at least one digit, or fails.
+    fn require_digits(&mut self) -> Result<(), String> {
+        if !matches!(self.peek(), Some(b'0'..=b'9')) {
+            return Err(self.error("Invalid number"));
+        }
+        self.skip_digits();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let value = parse(
+            r#" {"name": "Jo\u00e9 \"J\"", "age": 42, "tags": [1.5, true, null], "empty": {}} "#,
+        )
+        .unwrap();
+        let Value::Record(values) = value else {
+            panic!("expected a record");
+        };
+        assert_eq!(
+            values.get("name"),
+            Some(&Value::String("Joé \"J\"".to_string()))
+        );
+        assert_eq!(values.get("age"), Some(&Value::Integer(42)));
+        assert_eq!(
+            values.get("tags"),
+            Some(&Value::List(vec![
+                Value::Float(1.5),
+                Value::Bool(true),
+                Value::Null
+            ]))
+        );
+        assert_eq!(values.get("empty"), Some(&Value::Record(ValueMap::new())));
+        assert_eq!(
+            parse("\"\\ud83d\\ude00\""),
+            Ok(Value::String("😀".to_string()))
+        );
+        assert_eq!(parse("-1e3"), Ok(Value::Float(-1000.0)));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for input in [
+            "",
+            "{",
+            "[1,]",
+            "{\"a\" 1}",
+            "01",
+            "1.",
+            "tru",
+            "\"a",
+            "[1] 2",
+            "'a'",
+            "\"\\x\"",
+            "\"\\ud83d\"",
+            "{a: 1}",
+        ] {
+            assert!(parse(input).is_err(), "{:?} should be invalid", input);
+        }
+        assert!(parse(&"[".repeat(200)).is_err());
+    }
+
+    #[test]
+    fn test_write() {
+        let mut values = ValueMap::new();
+        values.insert("name", Value::String("Jo \"J\"\n\u{1}".to_string()));
+        values.insert("initial", Value::Char('J'));
+        values.insert(
+            "numbers",
+            Value::List(vec![
+                Value::Integer(-3),
+                Value::Float(2.0),
+                Value::Float(0.5),
+                Value::Float(f64::NAN),
+            ]),
+        );
+        values.insert("student", Value::Bool(false));
+        values.insert("width", Value::Null);
+        let text = write(&Value::Record(values));
+        assert_eq!(
+            text,
+            r#"{"name":"Jo \"J\"\n\u0001","initial":"J","numbers":[-3,2.0,0.5,null],"student":false,"width":null}"#
+        );
+        let Ok(Value::Record(parsed)) = parse(&text) else {
+            panic!("expected a record");
+        };
+        assert_eq!(
+            parsed.get("numbers"),
+            Some(&Value::List(vec![
+                Value::Integer(-3),
+                Value::Float(2.0),
+                Value::Float(0.5),
+                Value::Null,
+            ]))
+        );
+    }
+}
@@ -143,21 +143,76 @@ pub mod form;
 /// Module containing the form builder.
 pub mod form_builder;
 
+/// Module containing typed handles to form fields.
+pub mod handle;
+
+/// Module containing the HTML export of forms.
+mod html;
+
+/// Module containing a parser for JSON text.
+mod json;
+
+/// Module containing hooks run while a form is filled.
+pub mod hooks;
+
 /// Module containing functions for reading input.
 pub mod input;
 
+/// Module containing traits for filling structs from forms.
+pub mod model;
+
 /// Module containing definitions for multiselect fields.
 pub mod multiselect_field;
 
 /// Module containing definitions for optional values.
 pub mod optional;
 
+/// Module containing retry policies, timeouts and fallbacks of fields.
+pub mod policy;
+
+/// Module containing option providers for select fields.
+pub mod provider;
+
+/// Module containing renderers drawing prompts.
+pub mod render;
+
+/// Module containing definitions for repeatable field groups.
+pub mod repeat_field;
+
+/// Module containing the JSON Schema export and import of forms.
+mod schema;
+
+/// Module containing definitions for form sections.
+pub mod section_field;
+
 /// Module containing definitions for select fields.
 pub mod select_field;
 
+/// Module containing a scripted test harness for forms.
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+/// Module containing input transformers.
+pub mod transform;
+
 /// Module containing validation methods.
 pub mod validation;
 
+/// Module containing the dynamic value model.
+pub mod value;
+
+pub use field::FillError;
 pub use form_builder::FormBuilder;
+pub use handle::FieldHandle;
+pub use hooks::{Flow, Hooks};
+pub use model::{FormField, FormModel, FormOptions};
 pub use optional::Optional;
-pub use validation::{ValidationMethods, Validator};
+pub use policy::{Fallback, RetryPolicy, TimeoutPolicy};
+pub use provider::OptionProvider;
+pub use render::{AnsiRenderer, Frame, PlainRenderer, Renderer};
+pub use transform::{Transformer, ValueTransformer};
+pub use validation::{CardBrand, NamedRule, Patterns, ValidationMethods, Validator};
+pub use value::{FieldKind, Value, ValueKind, ValueMap};
+
+#[cfg(feature = "derive")]
+pub use form_builder_derive::{Form, FormOptions};
@@ -0,0 +1,212 @@
+use crate::form::Form;
+use crate::form_builder::FormBuilder;
+use crate::multiselect_field::MultiselectField;
+use crate::select_field::SelectField;
+use crate::validation::Validator;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// A trait for structs that can be filled from a form.
+///
+/// It is usually implemented with `#[derive(Form)]`, available with the
+/// `derive` feature.
+pub trait FormModel: Sized {
+    /// Creates a `FormBuilder` with one field per struct field.
+    ///
+    /// # Returns
+    ///
+    /// * A `FormBuilder` describing the form of the struct.
+    fn form_builder() -> FormBuilder;
+
+    /// Creates the struct from the values of a filled form.
+    ///
+    /// # Parameters
+    ///
+    /// * `form` - A form built from `form_builder` and filled by the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if all values are successfully retrieved.
+    /// * `Err(String)` if any field is missing or has no value.
+    fn from_form(form: &Form) -> Result<Self, String>;
+
+    /// Builds the form, fills it by prompting the user and creates the struct.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the form is successfully filled.
+    /// * `Err(String)` if there is an error filling the form or reading its values.
+    fn fill() -> Result<Self, String> {
+        let mut form = Self::form_builder().build();
+        form.fill()?;
+        Self::from_form(&form)
+    }
+}
+
+/// A trait for types that can be used as fields of a `FormModel`.
+///
+/// It is implemented for the primitive types, `String`, `Option<T>` (as an
+/// optional field), `Vec<T>` of `FormOptions` (as a multiselect field) and
+/// enums deriving `FormOptions` (as a select field).
+pub trait FormField: Sized {
+    /// Adds a field for this type to the builder.
+    ///
+    /// # Parameters
+    ///
+    /// * `builder` - The builder to add the field to.
+    /// * `name` - The name of the field.
+    /// * `prompt` - The prompt message to be displayed to the user.
+    /// * `validator` - An optional `Validator` instance, ignored by select fields.
+    /// * `limit` - An optional limit on the number of selections, used by multiselect fields.
+    ///
+    /// # Returns
+    ///
+    /// * The `FormBuilder` instance with the added field.
+    fn add_to(
+        builder: FormBuilder,
+        name: &str,
+        prompt: &str,
+        validator: Option<Validator>,
+        limit: Option<usize>,
+    ) -> FormBuilder;
+
+    /// Reads the value of the field from a filled form.
+    ///
+    /// # Parameters
+    ///
+    /// * `form` - The filled form.
+    /// * `name` - The name of the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the value is successfully retrieved.
+    /// * `Err(String)` if the field is not found, has an incorrect type or has no value.
+    fn read_from(form: &Form, name: &str) -> Result<Self, String>;
+}
+
+/// A trait for types offering a fixed list of options, such as unit-only enums.
+///
+/// It is usually implemented with `#[derive(FormOptions)]`, available with
+/// the `derive` feature.
+pub trait FormOptions: Sized + 'static + Clone + PartialEq + Debug {
+    /// Returns the available options with their labels.
+    ///
+    /// # Returns
+    ///
+    /// * A list of options and the labels displayed to the user.
+    fn options() -> Vec<(Self, &'static str)>;
+}
+
+/// Implements `FormField` for types read with a plain input field.
+macro_rules! impl_form_field {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl FormField for $ty {
+                fn add_to(
+                    builder: FormBuilder,
+                    name: &str,
+                    prompt: &str,
+                    validator: Option<Validator>,
+                    _limit: Option<usize>,
+                ) -> FormBuilder {
+                    builder.add_field::<$ty>(name, prompt, validator)
+                }
+
+                fn read_from(form: &Form, name: &str) -> Result<Self, String> {
+                    form.get_value::<$ty>(name)
+                }
+            }
+        )*
+    };
+}
+
+impl_form_field!(
+    String, char, bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64,
+);
+
+impl<T> FormField for Option<T>
+where
+    T: 'static + FromStr + Debug + Clone,
+    T::Err: Debug,
+{
+    fn add_to(
+        builder: FormBuilder,
+        name: &str,
+        prompt: &str,
+        validator: Option<Validator>,
+        _limit: Option<usize>,
+    ) -> FormBuilder {
+        builder.add_optional_field::<T>(name, prompt, validator)
+    }
+
+    fn read_from(form: &Form, name: &str) -> Result<Self, String> {
+        form.get_optional::<T>(name)
+    }
+}
+
+impl<T> FormField for Vec<T>
+where
+    T: FormOptions,
+{
+    fn add_to(
+        builder: FormBuilder,
+        name: &str,
+        prompt: &str,
+        _validator: Option<Validator>,
+        limit: Option<usize>,
+    ) -> FormBuilder {
+        builder.add_multiselect(name, prompt, T::options(), limit)
+    }
+
+    fn read_from(form: &Form, name: &str) -> Result<Self, String> {
+        form.find_field(name)?
+            .as_any()
+            .downcast_ref::<MultiselectField<T>>()
+            .ok_or_else(|| format!("Field '{}' has incorrect type", name))?
+            .get_value()
+    }
+}
+
+/// Adds a select field for a `FormOptions` type to the builder.
+///
+/// Used by `#[derive(FormOptions)]` to implement `FormField` for enums.
+///
+/// # Parameters
+///
+/// * `builder` - The builder to add the field to.
+/// * `name` - The name of the field.
+/// * `prompt` - The prompt message to be displayed to the user.
+///
+/// # Returns
+///
+/// * The `FormBuilder` instance with the added select field.
+pub fn add_select_field<T>(builder: FormBuilder, name: &str, prompt: &str) -> FormBuilder
+where
+    T: FormOptions,
+{
+    builder.add_select(name, prompt, T::options())
+}
+
+/// Reads the value of a select field for a `FormOptions` type.
+///
+/// Used by `#[derive(FormOptions)]` to implement `FormField` for enums.
+///
+/// # Parameters
+///
+/// * `form` - The filled form.
+/// * `name` - The name of the field.
+///
+/// # Returns
+///
+/// * `Ok(T)` if the selected option is successfully retrieved.
+/// * `Err(String)` if the field is not found, has an incorrect type or has no value.
+pub fn read_select_field<T>(form: &Form, name: &str) -> Result<T, String>
+where
+    T: FormOptions,
+{
+    form.find_field(name)?
+        .as_any()
+        .downcast_ref::<SelectField<T>>()
+        .ok_or_else(|| format!("Field '{}' has incorrect type", name))?
+        .get_value()
+}
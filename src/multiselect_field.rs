@@ -1,10 +1,11 @@
-use crate::field::FieldTrait;
-use crate::input::read_multiselect;
-use std::fmt::Debug;
-use std::str::FromStr;
+use crate::field::{FieldDetails, FieldTrait, FillContext, FillError};
+use crate::input::{ErrorCallback, PromptDetails, read_multiselect_with_details};
+use crate::policy::FieldPolicy;
+use crate::provider::OptionProvider;
+use crate::value::{FieldKind, Value};
+use std::fmt::{self, Debug};
 
 /// A struct representing a multiselect field in a form.
-#[derive(Debug)]
 pub struct MultiselectField<T> {
     /// The prompt to display to the user.
     pub prompt: String,
@@ -14,12 +15,35 @@ pub struct MultiselectField<T> {
     pub value: Vec<T>,
     /// The optional limit on the number of selections.
     pub limit: Option<usize>,
+    /// The help text and option descriptions of the field.
+    pub details: FieldDetails,
+    /// An optional provider replacing the options each time the field is filled.
+    pub provider: Option<Box<dyn OptionProvider<T>>>,
+    /// The retry policy and default answer of the field.
+    pub policy: FieldPolicy,
+}
+
+impl<T> Debug for MultiselectField<T>
+where
+    T: Debug,
+{
+    /// Formats the field, showing whether it has an option provider.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiselectField")
+            .field("prompt", &self.prompt)
+            .field("options", &self.options)
+            .field("value", &self.value)
+            .field("limit", &self.limit)
+            .field("details", &self.details)
+            .field("provider", &self.provider.is_some())
+            .field("policy", &self.policy)
+            .finish()
+    }
 }
 
 impl<T> FieldTrait for MultiselectField<T>
 where
-    T: 'static + Clone + PartialEq + Debug + FromStr,
-    T::Err: Debug,
+    T: 'static + Clone + PartialEq + Debug,
 {
     /// Fills the multiselect field by prompting the user for input.
     ///
@@ -28,9 +52,34 @@ where
     /// * `Ok(())` if the field is successfully filled.
     /// * `Err(String)` if there is an error filling the field.
     fn fill(&mut self) -> Result<(), String> {
-        // Używamy read_multiselect do odczytania wartości od użytkownika
-        self.value = read_multiselect(&self.prompt, &self.options, self.limit)?;
-        Ok(())
+        Ok(self.fill_with_context(&FillContext::default())?)
+    }
+
+    /// Loads the options from the provider, if any, and fills the field.
+    fn fill_with_context(&mut self, context: &FillContext) -> Result<(), FillError> {
+        if let Some(provider) = &self.provider {
+            self.options = provider.options(&context.answers)?;
+        }
+        let report = |message: &str| context.report_error(&context.field, message);
+        let details = PromptDetails {
+            on_error: Some(ErrorCallback(&report)),
+            ..self
+                .policy
+                .prompt_details(context, self.details.prompt_details(&[], &[]))
+        };
+        match read_multiselect_with_details(&self.prompt, &self.options, self.limit, &details) {
+            Ok(value) => {
+                self.value = value;
+                Ok(())
+            }
+            Err(error) => match self.policy.recover(&context.field, context, error)? {
+                Some(default) => Ok(self.restore(&default, context)?),
+                None => {
+                    self.value.clear();
+                    Ok(())
+                }
+            },
+        }
     }
 
     /// Returns a reference to the field as a `dyn Any`.
@@ -42,6 +91,86 @@ where
         self
     }
 
+    /// Returns a mutable reference to the field as a `dyn Any`.
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+
+    /// Returns the help text and option descriptions of the field.
+    fn details(&self) -> Option<&FieldDetails> {
+        Some(&self.details)
+    }
+
+    /// Returns the help text and option descriptions of the field for editing.
+    fn details_mut(&mut self) -> Option<&mut FieldDetails> {
+        Some(&mut self.details)
+    }
+
+    /// Returns the retry policy and default answer of the field for editing.
+    fn policy_mut(&mut self) -> Option<&mut FieldPolicy> {
+        Some(&mut self.policy)
+    }
+
+    /// Gets the selected values as a list, which is empty if nothing was selected.
+    fn draft_value(&self) -> Value {
+        Value::List(self.value.iter().map(Value::of).collect())
+    }
+
+    /// Restores the selections by finding the options with the saved values.
+    fn restore(&mut self, value: &Value, context: &FillContext) -> Result<(), String> {
+        let Value::List(values) = value else {
+            return Err(format!(
+                "Invalid draft answer for field '{}'",
+                context.field
+            ));
+        };
+        if let Some(provider) = &self.provider {
+            self.options = provider.options(&context.answers)?;
+        }
+        self.value = values
+            .iter()
+            .map(|value| {
+                self.options
+                    .iter()
+                    .find(|(option, _)| Value::of(option) == *value)
+                    .map(|(option, _)| option.clone())
+                    .ok_or_else(|| {
+                        format!("'{}' is not an option of field '{}'", value, context.field)
+                    })
+            })
+            .collect::<Result<Vec<T>, String>>()?;
+        Ok(())
+    }
+
+    /// Fills the selections from a list of option values.
+    fn answer(&mut self, answer: &Value, context: &FillContext) -> Result<(), FillError> {
+        let values = match answer {
+            Value::List(values) => values.as_slice(),
+            Value::Null => &[],
+            _ => return Err(context.reject("The answer must be a list".to_string())),
+        };
+        if values.is_empty() {
+            return Err(context.reject("Please choose at least one option".to_string()));
+        }
+        if let Some(limit) = self.limit.filter(|limit| values.len() > *limit) {
+            return Err(context.reject(format!("Please choose at most {} options", limit)));
+        }
+        if let Some(provider) = &self.provider {
+            self.options = provider.options(&context.answers)?;
+        }
+        self.value = values
+            .iter()
+            .map(|value| {
+                self.options
+                    .iter()
+                    .find(|(option, _)| Value::of(option) == *value)
+                    .map(|(option, _)| option.clone())
+                    .ok_or_else(|| context.reject(format!("'{}' is not an option", value)))
+            })
+            .collect::<Result<Vec<T>, FillError>>()?;
+        Ok(())
+    }
+
     /// Gets the value of the multiselect field as a string.
     ///
     /// # Returns
@@ -52,4 +181,60 @@ where
         // Return the value as a string
         Ok(format!("{:?}", self.value))
     }
+
+    /// Returns the prompt of the field.
+    fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    /// Returns the kind of the field.
+    fn kind(&self) -> FieldKind {
+        FieldKind::Multiselect
+    }
+
+    /// Multiselect fields need at least one option chosen.
+    fn is_required(&self) -> bool {
+        true
+    }
+
+    /// Returns the maximum number of options that can be chosen.
+    fn selection_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Returns the options of the field with their values.
+    fn options(&self) -> Vec<(Value, String)> {
+        self.options
+            .iter()
+            .map(|(key, label)| (Value::of(key), label.clone()))
+            .collect()
+    }
+
+    /// Gets the value of the field as a dynamically typed `Value`.
+    fn value(&self) -> Value {
+        if self.value.is_empty() {
+            Value::Null
+        } else {
+            Value::List(self.value.iter().map(Value::of).collect())
+        }
+    }
+}
+
+impl<T> MultiselectField<T>
+where
+    T: Clone,
+{
+    /// Gets the selected values.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<T>)` if the field value is successfully retrieved.
+    /// * `Err(String)` if no value has been selected.
+    pub fn get_value(&self) -> Result<Vec<T>, String> {
+        if self.value.is_empty() {
+            Err("Field has no value".to_string())
+        } else {
+            Ok(self.value.clone())
+        }
+    }
 }
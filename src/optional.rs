@@ -1,5 +1,7 @@
+use std::cmp::Ordering;
 use std::fmt::{self, Debug, Display};
-use std::ops::{Add, Div, Mul, Sub};
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 use std::str::FromStr;
 
 /// An optional value that can be `Some` or `None`.
@@ -11,6 +13,194 @@ pub enum Optional<T> {
     None,
 }
 
+impl<T> Optional<T> {
+    /// Checks whether the value is `Optional::Some`.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a value is present, `false` otherwise.
+    pub fn is_some(&self) -> bool {
+        matches!(self, Optional::Some(_))
+    }
+
+    /// Checks whether the value is `Optional::None`.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if no value is present, `false` otherwise.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Optional::None)
+    }
+
+    /// Converts from `&Optional<T>` to `Optional<&T>`.
+    ///
+    /// # Returns
+    ///
+    /// * An `Optional` containing a reference to the value, if any.
+    pub fn as_ref(&self) -> Optional<&T> {
+        match self {
+            Optional::Some(value) => Optional::Some(value),
+            Optional::None => Optional::None,
+        }
+    }
+
+    /// Converts from `&mut Optional<T>` to `Optional<&mut T>`.
+    ///
+    /// # Returns
+    ///
+    /// * An `Optional` containing a mutable reference to the value, if any.
+    pub fn as_mut(&mut self) -> Optional<&mut T> {
+        match self {
+            Optional::Some(value) => Optional::Some(value),
+            Optional::None => Optional::None,
+        }
+    }
+
+    /// Converts the value to a standard `Option<T>`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(T)` if a value is present, `None` otherwise.
+    pub fn into_option(self) -> Option<T> {
+        self.into()
+    }
+
+    /// Maps the contained value with a function.
+    ///
+    /// # Returns
+    ///
+    /// * `Optional::Some(f(value))` if a value is present, `Optional::None` otherwise.
+    pub fn map<U, F>(self, f: F) -> Optional<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            Optional::Some(value) => Optional::Some(f(value)),
+            Optional::None => Optional::None,
+        }
+    }
+
+    /// Chains a function returning another `Optional`.
+    ///
+    /// # Returns
+    ///
+    /// * `f(value)` if a value is present, `Optional::None` otherwise.
+    pub fn and_then<U, F>(self, f: F) -> Optional<U>
+    where
+        F: FnOnce(T) -> Optional<U>,
+    {
+        match self {
+            Optional::Some(value) => f(value),
+            Optional::None => Optional::None,
+        }
+    }
+
+    /// Keeps the value only if it matches a predicate.
+    ///
+    /// # Returns
+    ///
+    /// * `Optional::Some(value)` if a value is present and `predicate` returns `true`, `Optional::None` otherwise.
+    pub fn filter<P>(self, predicate: P) -> Self
+    where
+        P: FnOnce(&T) -> bool,
+    {
+        match self {
+            Optional::Some(value) if predicate(&value) => Optional::Some(value),
+            _ => Optional::None,
+        }
+    }
+
+    /// Returns the value if present, or another `Optional` otherwise.
+    ///
+    /// # Returns
+    ///
+    /// * `self` if a value is present, `other` otherwise.
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Optional::Some(value) => Optional::Some(value),
+            Optional::None => other,
+        }
+    }
+
+    /// Returns the contained value or a default.
+    ///
+    /// # Returns
+    ///
+    /// * The contained value if present, `default` otherwise.
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            Optional::Some(value) => value,
+            Optional::None => default,
+        }
+    }
+
+    /// Returns the contained value or computes it from a closure.
+    ///
+    /// # Returns
+    ///
+    /// * The contained value if present, the result of `f` otherwise.
+    pub fn unwrap_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        match self {
+            Optional::Some(value) => value,
+            Optional::None => f(),
+        }
+    }
+
+    /// Returns the contained value or the default value of `T`.
+    ///
+    /// # Returns
+    ///
+    /// * The contained value if present, `T::default()` otherwise.
+    pub fn unwrap_or_default(self) -> T
+    where
+        T: Default,
+    {
+        self.unwrap_or_else(T::default)
+    }
+
+    /// Transforms the value into a `Result`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(value)` if a value is present, `Err(err)` otherwise.
+    pub fn ok_or<E>(self, err: E) -> Result<T, E> {
+        match self {
+            Optional::Some(value) => Ok(value),
+            Optional::None => Err(err),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Optional<T> {
+    /// Converts a standard `Option<T>` to an `Optional<T>`.
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Optional::Some(value),
+            None => Optional::None,
+        }
+    }
+}
+
+impl<T> From<Optional<T>> for Option<T> {
+    /// Converts an `Optional<T>` to a standard `Option<T>`.
+    fn from(value: Optional<T>) -> Self {
+        match value {
+            Optional::Some(value) => Some(value),
+            Optional::None => None,
+        }
+    }
+}
+
+impl<T> From<T> for Optional<T> {
+    /// Wraps a value in `Optional::Some`.
+    fn from(value: T) -> Self {
+        Optional::Some(value)
+    }
+}
+
 impl<T> FromStr for Optional<T>
 where
     T: FromStr,
@@ -111,6 +301,9 @@ where
     /// * `Optional::Some(a - b)` if both are `Optional::Some`.
     /// * `Optional::Some(a)` if one is `Optional::None`.
     /// * `Optional::None` if both are `Optional::None`.
+    ///
+    /// Returning `b` for `None - b` is kept for compatibility; use `sub_with`
+    /// to treat `Optional::None` as zero or to propagate it instead.
     fn sub(self, other: Self) -> Self {
         match (self, other) {
             (Optional::Some(a), Optional::Some(b)) => Optional::Some(a - b),
@@ -157,6 +350,12 @@ where
     /// * `Optional::Some(a / b)` if both are `Optional::Some`.
     /// * `Optional::Some(a)` if one is `Optional::None`.
     /// * `Optional::None` if both are `Optional::None`.
+    ///
+    /// Returning the present operand is deprecated and only kept for
+    /// compatibility; use `div_with` with `NullMode::Propagate` or
+    /// `checked_div` for a result that is `Optional::None` when an operand
+    /// is missing. Like the division of `T`, this panics on integer division
+    /// by zero.
     fn div(self, other: Self) -> Self {
         match (self, other) {
             (Optional::Some(a), Optional::Some(b)) => Optional::Some(a / b),
@@ -167,6 +366,384 @@ where
     }
 }
 
+impl<T> Rem for Optional<T>
+where
+    T: Rem<Output = T> + Default,
+{
+    type Output = Self;
+
+    /// Computes the remainder of two `Optional<T>` values.
+    ///
+    /// # Returns
+    ///
+    /// * `Optional::Some(a % b)` if both are `Optional::Some`.
+    /// * `Optional::None` if either is `Optional::None`.
+    fn rem(self, other: Self) -> Self {
+        self.rem_with(other, NullMode::Propagate)
+    }
+}
+
+impl<T> Neg for Optional<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    /// Negates an `Optional<T>` value.
+    ///
+    /// # Returns
+    ///
+    /// * `Optional::Some(-a)` if the value is `Optional::Some(a)`.
+    /// * `Optional::None` otherwise.
+    fn neg(self) -> Self {
+        self.map(|value| -value)
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Optional<T> {
+    /// Compares two `Optional<T>` values.
+    ///
+    /// # Returns
+    ///
+    /// * The ordering of the values if both are `Optional::Some`.
+    /// * `Optional::None` ordered before any `Optional::Some`, like `Option<T>`.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Optional::Some(a), Optional::Some(b)) => a.partial_cmp(b),
+            (Optional::Some(_), Optional::None) => Some(Ordering::Greater),
+            (Optional::None, Optional::Some(_)) => Some(Ordering::Less),
+            (Optional::None, Optional::None) => Some(Ordering::Equal),
+        }
+    }
+}
+
+impl<T> Sum for Optional<T>
+where
+    T: Add<Output = T> + Default,
+{
+    /// Sums an iterator of `Optional<T>` values, skipping `Optional::None` like SQL `SUM`.
+    ///
+    /// # Returns
+    ///
+    /// * `Optional::Some(total)` if any value is `Optional::Some`.
+    /// * `Optional::None` if the iterator is empty or contains only `Optional::None`.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Optional::None, |total, value| total + value)
+    }
+}
+
+impl<T> Product for Optional<T>
+where
+    T: Mul<Output = T> + Default,
+{
+    /// Multiplies an iterator of `Optional<T>` values, skipping `Optional::None`.
+    ///
+    /// # Returns
+    ///
+    /// * `Optional::Some(product)` if any value is `Optional::Some`.
+    /// * `Optional::None` if the iterator is empty or contains only `Optional::None`.
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Optional::None, |product, value| product * value)
+    }
+}
+
+/// How arithmetic on `Optional<T>` treats `Optional::None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NullMode {
+    /// `Optional::None` stands for the identity of the operation.
+    ///
+    /// A missing operand of `+` and `*` or a missing divisor returns the other operand,
+    /// `None - b` is `-b`, as if the missing operand were zero, and a missing dividend
+    /// gives `Optional::None`.
+    Identity,
+    /// `Optional::None` in either operand makes the result `Optional::None`, like SQL `NULL`.
+    Propagate,
+}
+
+/// An error returned by the checked arithmetic of `Optional<T>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// The result does not fit into the value type, or is not finite.
+    Overflow,
+    /// The divisor is zero.
+    DivisionByZero,
+}
+
+impl Display for ArithmeticError {
+    /// Formats the error as a message.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArithmeticError::Overflow => write!(f, "Arithmetic overflow"),
+            ArithmeticError::DivisionByZero => write!(f, "Division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for ArithmeticError {}
+
+/// A trait for numbers supporting checked arithmetic.
+///
+/// It is implemented for all primitive integer and floating-point types.
+pub trait CheckedArithmetic: Sized {
+    /// Checks whether the number is zero.
+    fn is_zero(&self) -> bool;
+    /// Adds two numbers, returning `None` on overflow.
+    fn checked_add(self, other: Self) -> Option<Self>;
+    /// Subtracts two numbers, returning `None` on overflow.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    /// Multiplies two numbers, returning `None` on overflow.
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    /// Divides two numbers, returning `None` on overflow or division by zero.
+    fn checked_div(self, other: Self) -> Option<Self>;
+    /// Computes the remainder of two numbers, returning `None` on overflow or division by zero.
+    fn checked_rem(self, other: Self) -> Option<Self>;
+}
+
+/// Implements `CheckedArithmetic` for integer types with their inherent checked methods.
+macro_rules! impl_checked_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl CheckedArithmetic for $ty {
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_mul(self, other)
+                }
+
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_div(self, other)
+                }
+
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_rem(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+/// Implements `CheckedArithmetic` for floating-point types, treating non-finite results as overflow.
+macro_rules! impl_checked_float {
+    ($($ty:ty),*) => {
+        $(
+            impl CheckedArithmetic for $ty {
+                fn is_zero(&self) -> bool {
+                    *self == 0.0
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    Some(self + other).filter(|value| value.is_finite())
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    Some(self - other).filter(|value| value.is_finite())
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    Some(self * other).filter(|value| value.is_finite())
+                }
+
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    Some(self / other).filter(|value| value.is_finite())
+                }
+
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    Some(self % other).filter(|value| value.is_finite())
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_float!(f32, f64);
+
+impl<T> Optional<T> {
+    /// Combines two `Optional<T>` values with a function, treating `Optional::None` according to `mode`.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - The right-hand operand.
+    /// * `mode` - How `Optional::None` operands are treated.
+    /// * `op` - The operation applied when both operands are `Optional::Some`.
+    ///
+    /// # Returns
+    ///
+    /// * `Optional::Some(op(a, b))` if both are `Optional::Some`.
+    /// * With `NullMode::Identity`, the operand that is `Optional::Some`, if any.
+    /// * With `NullMode::Propagate`, `Optional::None` if either operand is `Optional::None`.
+    pub fn combine<F>(self, other: Self, mode: NullMode, op: F) -> Self
+    where
+        F: FnOnce(T, T) -> T,
+    {
+        match (self, other, mode) {
+            (Optional::Some(a), Optional::Some(b), _) => Optional::Some(op(a, b)),
+            (Optional::Some(a), Optional::None, NullMode::Identity) => Optional::Some(a),
+            (Optional::None, Optional::Some(b), NullMode::Identity) => Optional::Some(b),
+            _ => Optional::None,
+        }
+    }
+
+    /// Adds two `Optional<T>` values, treating `Optional::None` according to `mode`.
+    pub fn add_with(self, other: Self, mode: NullMode) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        self.combine(other, mode, |a, b| a + b)
+    }
+
+    /// Subtracts two `Optional<T>` values, treating `Optional::None` according to `mode`.
+    ///
+    /// With `NullMode::Identity`, `None - b` is `-b`. Unsigned types, which
+    /// cannot be negated, use `checked_sub` instead.
+    pub fn sub_with(self, other: Self, mode: NullMode) -> Self
+    where
+        T: Sub<Output = T> + Neg<Output = T>,
+    {
+        match (self, other, mode) {
+            (Optional::None, Optional::Some(b), NullMode::Identity) => Optional::Some(-b),
+            (a, b, mode) => a.combine(b, mode, |a, b| a - b),
+        }
+    }
+
+    /// Multiplies two `Optional<T>` values, treating `Optional::None` according to `mode`.
+    pub fn mul_with(self, other: Self, mode: NullMode) -> Self
+    where
+        T: Mul<Output = T>,
+    {
+        self.combine(other, mode, |a, b| a * b)
+    }
+
+    /// Divides two `Optional<T>` values, treating `Optional::None` according to `mode`.
+    ///
+    /// A missing dividend gives `Optional::None` in both modes.
+    pub fn div_with(self, other: Self, mode: NullMode) -> Self
+    where
+        T: Div<Output = T>,
+    {
+        match self {
+            Optional::None => Optional::None,
+            a => a.combine(other, mode, |a, b| a / b),
+        }
+    }
+
+    /// Computes the remainder of two `Optional<T>` values, treating `Optional::None` according to `mode`.
+    ///
+    /// A missing dividend gives `Optional::None` in both modes.
+    pub fn rem_with(self, other: Self, mode: NullMode) -> Self
+    where
+        T: Rem<Output = T>,
+    {
+        match self {
+            Optional::None => Optional::None,
+            a => a.combine(other, mode, |a, b| a % b),
+        }
+    }
+
+    /// Applies a checked operation with NULL-propagating semantics.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Optional::Some(result))` if both are `Optional::Some` and the operation succeeds.
+    /// * `Ok(Optional::None)` if either operand is `Optional::None`.
+    /// * `Err(ArithmeticError)` if the operation overflows or divides by zero.
+    fn checked<F>(self, other: Self, divides: bool, op: F) -> Result<Self, ArithmeticError>
+    where
+        T: CheckedArithmetic,
+        F: FnOnce(T, T) -> Option<T>,
+    {
+        match (self, other) {
+            (Optional::Some(_), Optional::Some(b)) if divides && b.is_zero() => {
+                Err(ArithmeticError::DivisionByZero)
+            }
+            (Optional::Some(a), Optional::Some(b)) => op(a, b)
+                .map(Optional::Some)
+                .ok_or(ArithmeticError::Overflow),
+            _ => Ok(Optional::None),
+        }
+    }
+
+    /// Adds two `Optional<T>` values with NULL propagation, checking for overflow.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Optional)` with the sum, or `Optional::None` if either operand is `Optional::None`.
+    /// * `Err(ArithmeticError::Overflow)` if the sum overflows.
+    pub fn checked_add(self, other: Self) -> Result<Self, ArithmeticError>
+    where
+        T: CheckedArithmetic,
+    {
+        self.checked(other, false, T::checked_add)
+    }
+
+    /// Subtracts two `Optional<T>` values with NULL propagation, checking for overflow.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Optional)` with the difference, or `Optional::None` if either operand is `Optional::None`.
+    /// * `Err(ArithmeticError::Overflow)` if the difference overflows.
+    pub fn checked_sub(self, other: Self) -> Result<Self, ArithmeticError>
+    where
+        T: CheckedArithmetic,
+    {
+        self.checked(other, false, T::checked_sub)
+    }
+
+    /// Multiplies two `Optional<T>` values with NULL propagation, checking for overflow.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Optional)` with the product, or `Optional::None` if either operand is `Optional::None`.
+    /// * `Err(ArithmeticError::Overflow)` if the product overflows.
+    pub fn checked_mul(self, other: Self) -> Result<Self, ArithmeticError>
+    where
+        T: CheckedArithmetic,
+    {
+        self.checked(other, false, T::checked_mul)
+    }
+
+    /// Divides two `Optional<T>` values with NULL propagation, checking for division by zero.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Optional)` with the quotient, or `Optional::None` if either operand is `Optional::None`.
+    /// * `Err(ArithmeticError::DivisionByZero)` if the divisor is zero.
+    /// * `Err(ArithmeticError::Overflow)` if the quotient overflows.
+    pub fn checked_div(self, other: Self) -> Result<Self, ArithmeticError>
+    where
+        T: CheckedArithmetic,
+    {
+        self.checked(other, true, T::checked_div)
+    }
+
+    /// Computes the remainder of two `Optional<T>` values with NULL propagation, checking for division by zero.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Optional)` with the remainder, or `Optional::None` if either operand is `Optional::None`.
+    /// * `Err(ArithmeticError::DivisionByZero)` if the divisor is zero.
+    /// * `Err(ArithmeticError::Overflow)` if the remainder overflows.
+    pub fn checked_rem(self, other: Self) -> Result<Self, ArithmeticError>
+    where
+        T: CheckedArithmetic,
+    {
+        self.checked(other, true, T::checked_rem)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +769,49 @@ mod tests {
         assert_eq!(format!("{}", none_value), "None");
     }
 
+    #[test]
+    fn test_option_conversions() {
+        let some: Optional<i32> = Some(3).into();
+        let none: Optional<i32> = None.into();
+        assert_eq!(some, Optional::Some(3));
+        assert_eq!(none, Optional::None);
+        assert_eq!(Option::from(some), Some(3));
+        assert_eq!(none.into_option(), None);
+        assert_eq!(Optional::from(7), Optional::Some(7));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let some = Optional::Some(4);
+        let none: Optional<i32> = Optional::None;
+        assert!(some.is_some() && none.is_none());
+        assert_eq!(some.as_ref(), Optional::Some(&4));
+        assert_eq!(some.clone().map(|v| v * 2), Optional::Some(8));
+        assert_eq!(none.clone().map(|v| v * 2), Optional::None);
+        assert_eq!(
+            some.clone().and_then(|v| if v > 3 {
+                Optional::Some(v)
+            } else {
+                Optional::None
+            }),
+            Optional::Some(4)
+        );
+        assert_eq!(some.clone().filter(|v| *v > 5), Optional::None);
+        assert_eq!(none.clone().or(Optional::Some(1)), Optional::Some(1));
+        assert_eq!(some.clone().unwrap_or(0), 4);
+        assert_eq!(none.clone().unwrap_or(0), 0);
+        assert_eq!(none.clone().unwrap_or_else(|| 9), 9);
+        assert_eq!(none.clone().unwrap_or_default(), 0);
+        assert_eq!(some.ok_or("missing"), Ok(4));
+        assert_eq!(none.ok_or("missing"), Err("missing"));
+
+        let mut value = Optional::Some(1);
+        if let Optional::Some(v) = value.as_mut() {
+            *v += 1;
+        }
+        assert_eq!(value, Optional::Some(2));
+    }
+
     #[test]
     fn test_add() {
         let a = Optional::Some(2);
@@ -235,4 +855,132 @@ mod tests {
         assert_eq!(none.clone() / b.clone(), Optional::Some(3));
         assert_eq!(none.clone() / none.clone(), Optional::None);
     }
+
+    #[test]
+    fn test_rem() {
+        let a = Optional::Some(7);
+        let b = Optional::Some(3);
+        let none: Optional<i32> = Optional::None;
+        assert_eq!(a.clone() % b.clone(), Optional::Some(1));
+        assert_eq!(a.clone() % none.clone(), Optional::None);
+        assert_eq!(none.clone() % b.clone(), Optional::None);
+        assert_eq!(none.clone() % none.clone(), Optional::None);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-Optional::Some(3), Optional::Some(-3));
+        assert_eq!(-Optional::<i32>::None, Optional::None);
+    }
+
+    #[test]
+    fn test_partial_ord() {
+        let none: Optional<i32> = Optional::None;
+        assert!(Optional::Some(2) < Optional::Some(3));
+        assert!(none < Optional::Some(i32::MIN));
+        assert!(Optional::Some(0) > none);
+        assert!(none <= Optional::None);
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        let values = vec![Optional::Some(2), Optional::None, Optional::Some(5)];
+        assert_eq!(
+            values.clone().into_iter().sum::<Optional<i32>>(),
+            Optional::Some(7)
+        );
+        assert_eq!(
+            values.into_iter().product::<Optional<i32>>(),
+            Optional::Some(10)
+        );
+
+        let empty: Vec<Optional<i32>> = vec![Optional::None];
+        assert_eq!(
+            empty.clone().into_iter().sum::<Optional<i32>>(),
+            Optional::None
+        );
+        assert_eq!(empty.into_iter().product::<Optional<i32>>(), Optional::None);
+    }
+
+    #[test]
+    fn test_null_modes() {
+        let a = Optional::Some(6);
+        let b = Optional::Some(3);
+        let none: Optional<i32> = Optional::None;
+        assert_eq!(
+            none.clone().sub_with(b.clone(), NullMode::Identity),
+            Optional::Some(-3)
+        );
+        assert_eq!(
+            a.clone().sub_with(none.clone(), NullMode::Identity),
+            Optional::Some(6)
+        );
+        assert_eq!(
+            none.clone().div_with(b.clone(), NullMode::Identity),
+            Optional::None
+        );
+        assert_eq!(
+            a.clone().div_with(none.clone(), NullMode::Identity),
+            Optional::Some(6)
+        );
+        assert_eq!(
+            none.clone().rem_with(b.clone(), NullMode::Identity),
+            Optional::None
+        );
+        assert_eq!(
+            none.clone().sub_with(b.clone(), NullMode::Propagate),
+            Optional::None
+        );
+        assert_eq!(
+            a.clone().div_with(none.clone(), NullMode::Propagate),
+            Optional::None
+        );
+        assert_eq!(
+            a.clone().add_with(b.clone(), NullMode::Propagate),
+            Optional::Some(9)
+        );
+        assert_eq!(
+            a.clone().mul_with(b.clone(), NullMode::Propagate),
+            Optional::Some(18)
+        );
+        assert_eq!(a.rem_with(b, NullMode::Propagate), Optional::Some(0));
+    }
+
+    #[test]
+    fn test_checked() {
+        let none: Optional<i32> = Optional::None;
+        assert_eq!(
+            Optional::Some(6).checked_div(Optional::Some(3)),
+            Ok(Optional::Some(2))
+        );
+        assert_eq!(
+            Optional::Some(6).checked_div(Optional::Some(0)),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            Optional::Some(6).checked_rem(Optional::Some(0)),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            Optional::Some(i32::MAX).checked_add(Optional::Some(1)),
+            Err(ArithmeticError::Overflow)
+        );
+        assert_eq!(
+            Optional::Some(0u8).checked_sub(Optional::Some(1)),
+            Err(ArithmeticError::Overflow)
+        );
+        assert_eq!(
+            Optional::Some(4).checked_mul(none.clone()),
+            Ok(Optional::None)
+        );
+        assert_eq!(none.checked_sub(Optional::Some(3)), Ok(Optional::None));
+        assert_eq!(
+            Optional::Some(1.0f64).checked_div(Optional::Some(0.0)),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            Optional::Some(f64::MAX).checked_mul(Optional::Some(2.0)),
+            Err(ArithmeticError::Overflow)
+        );
+    }
 }
@@ -0,0 +1,346 @@
+use crate::field::{FillContext, FillError};
+use crate::input::{InputError, PromptDetails};
+use crate::value::Value;
+use std::time::Duration;
+
+/// What a field does when it cannot get an answer from the user.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Fallback {
+    /// Filling the form fails with an error.
+    #[default]
+    Error,
+    /// The field takes its default value.
+    Default,
+    /// The field is left without a value.
+    Skip,
+}
+
+/// How often the user may enter invalid input for a field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, or `None` to ask until the input is valid.
+    pub max_attempts: Option<usize>,
+    /// What happens once the attempts are used up.
+    pub on_exhausted: Fallback,
+}
+
+impl RetryPolicy {
+    /// Creates a policy asking until the input is valid.
+    ///
+    /// # Returns
+    ///
+    /// * A new `RetryPolicy` instance without a limit.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy allowing a number of attempts, failing once they are used up.
+    ///
+    /// # Parameters
+    ///
+    /// * `max_attempts` - The maximum number of attempts.
+    ///
+    /// # Returns
+    ///
+    /// * A new `RetryPolicy` instance.
+    ///
+    /// # Panics
+    ///
+    /// * If `max_attempts` is zero.
+    pub fn attempts(max_attempts: usize) -> Self {
+        assert!(max_attempts > 0, "A field needs at least one attempt");
+        Self {
+            max_attempts: Some(max_attempts),
+            on_exhausted: Fallback::Error,
+        }
+    }
+
+    /// Sets what happens once the attempts are used up.
+    ///
+    /// # Parameters
+    ///
+    /// * `fallback` - The fallback, such as `Fallback::Default`.
+    ///
+    /// # Returns
+    ///
+    /// * The `RetryPolicy` instance with the fallback set.
+    pub fn on_exhausted(mut self, fallback: Fallback) -> Self {
+        self.on_exhausted = fallback;
+        self
+    }
+}
+
+/// How long the user has to answer a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutPolicy {
+    /// The time the user has to answer.
+    pub duration: Duration,
+    /// What happens once the time is up.
+    pub on_timeout: Fallback,
+    /// Whether the seconds left are shown while waiting on a terminal.
+    pub countdown: bool,
+}
+
+impl TimeoutPolicy {
+    /// Creates a timeout failing once the time is up, without a countdown.
+    ///
+    /// # Parameters
+    ///
+    /// * `duration` - The time the user has to answer.
+    ///
+    /// # Returns
+    ///
+    /// * A new `TimeoutPolicy` instance.
+    ///
+    /// # Panics
+    ///
+    /// * If `duration` is zero.
+    pub fn after(duration: Duration) -> Self {
+        assert!(!duration.is_zero(), "A timeout must be longer than zero");
+        Self {
+            duration,
+            on_timeout: Fallback::Error,
+            countdown: false,
+        }
+    }
+
+    /// Sets what happens once the time is up.
+    ///
+    /// # Parameters
+    ///
+    /// * `fallback` - The fallback, such as `Fallback::Default`.
+    ///
+    /// # Returns
+    ///
+    /// * The `TimeoutPolicy` instance with the fallback set.
+    pub fn on_timeout(mut self, fallback: Fallback) -> Self {
+        self.on_timeout = fallback;
+        self
+    }
+
+    /// Shows the seconds left while waiting for an answer on a terminal.
+    ///
+    /// # Returns
+    ///
+    /// * The `TimeoutPolicy` instance with the countdown enabled.
+    pub fn with_countdown(mut self) -> Self {
+        self.countdown = true;
+        self
+    }
+}
+
+/// How a field behaves when the user does not give a valid answer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldPolicy {
+    /// The retry policy of the field, or `None` to use the policy of the form.
+    pub retry: Option<RetryPolicy>,
+    /// The default answer, in the form saved in drafts by `FieldTrait::draft_value`.
+    pub default: Option<Value>,
+    /// The timeout of the field, or `None` to use the timeout of the form.
+    pub timeout: Option<TimeoutPolicy>,
+}
+
+impl FieldPolicy {
+    /// Returns the retry policy of the field, falling back to the policy of the form.
+    ///
+    /// # Parameters
+    ///
+    /// * `context` - The state of the form being filled.
+    ///
+    /// # Returns
+    ///
+    /// * The `RetryPolicy` applying to the field.
+    pub fn retry(&self, context: &FillContext) -> RetryPolicy {
+        self.retry.unwrap_or(context.retry)
+    }
+
+    /// Returns the timeout of the field, falling back to the timeout of the form.
+    ///
+    /// # Parameters
+    ///
+    /// * `context` - The state of the form being filled.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(TimeoutPolicy)` if the field has to be answered in time, `None` otherwise.
+    pub fn timeout(&self, context: &FillContext) -> Option<TimeoutPolicy> {
+        self.timeout.or(context.timeout)
+    }
+
+    /// Applies the attempt limit and timeout of the field and the renderer and output of the form to the details of its prompt.
+    ///
+    /// # Parameters
+    ///
+    /// * `context` - The state of the form being filled.
+    /// * `details` - The details of the prompt.
+    ///
+    /// # Returns
+    ///
+    /// * The `PromptDetails` with the limits set.
+    pub fn prompt_details<'a>(
+        &self,
+        context: &'a FillContext,
+        details: PromptDetails<'a>,
+    ) -> PromptDetails<'a> {
+        let timeout = self.timeout(context);
+        PromptDetails {
+            max_attempts: self.retry(context).max_attempts,
+            timeout: timeout.map(|timeout| timeout.duration),
+            countdown: timeout.is_some_and(|timeout| timeout.countdown),
+            renderer: context.renderer.as_deref(),
+            output: context.output,
+            ..details
+        }
+    }
+
+    /// Decides how a field continues after a prompt ended without an answer.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field, used in error messages.
+    /// * `context` - The state of the form being filled.
+    /// * `error` - The error ending the prompt.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Value))` with the default answer the field should restore.
+    /// * `Ok(None)` if the field should be left without a value.
+    /// * `Err(FillError)` if filling the form should fail.
+    pub fn recover(
+        &self,
+        name: &str,
+        context: &FillContext,
+        error: InputError,
+    ) -> Result<Option<Value>, FillError> {
+        let fallback = match error {
+            InputError::AttemptsExhausted(_) => self.retry(context).on_exhausted,
+            InputError::TimedOut => self
+                .timeout(context)
+                .map_or(Fallback::Error, |timeout| timeout.on_timeout),
+            _ => Fallback::Error,
+        };
+        match fallback {
+            Fallback::Error => Err(FillError::Input {
+                field: name.to_string(),
+                error,
+            }),
+            Fallback::Default => {
+                self.default.clone().map(Some).ok_or_else(|| {
+                    FillError::Other(format!("Field '{}' has no default value", name))
+                })
+            }
+            Fallback::Skip => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::FieldDetails;
+
+    #[test]
+    fn test_recover() {
+        let context = FillContext::default();
+        let policy = FieldPolicy {
+            retry: Some(RetryPolicy::attempts(3).on_exhausted(Fallback::Default)),
+            default: Some(Value::String("42".to_string())),
+            ..FieldPolicy::default()
+        };
+        assert_eq!(
+            policy.recover("age", &context, InputError::AttemptsExhausted(3)),
+            Ok(Some(Value::String("42".to_string())))
+        );
+        assert_eq!(
+            policy.recover("age", &context, InputError::Eof),
+            Err(FillError::Input {
+                field: "age".to_string(),
+                error: InputError::Eof,
+            })
+        );
+
+        let policy = FieldPolicy::default();
+        let context = FillContext {
+            retry: RetryPolicy::attempts(2).on_exhausted(Fallback::Skip),
+            ..FillContext::default()
+        };
+        assert_eq!(policy.retry(&context).max_attempts, Some(2));
+        assert_eq!(
+            policy.recover("age", &context, InputError::AttemptsExhausted(2)),
+            Ok(None)
+        );
+
+        let context = FillContext {
+            retry: RetryPolicy::attempts(1).on_exhausted(Fallback::Default),
+            ..FillContext::default()
+        };
+        assert_eq!(
+            policy.recover("age", &context, InputError::AttemptsExhausted(1)),
+            Err(FillError::Other(
+                "Field 'age' has no default value".to_string()
+            ))
+        );
+        assert_eq!(
+            FieldPolicy {
+                retry: Some(RetryPolicy::attempts(5)),
+                ..FieldPolicy::default()
+            }
+            .recover("age", &context, InputError::AttemptsExhausted(5)),
+            Err(FillError::Input {
+                field: "age".to_string(),
+                error: InputError::AttemptsExhausted(5),
+            })
+        );
+    }
+
+    #[test]
+    fn test_recover_timeout() {
+        let policy = FieldPolicy {
+            default: Some(Value::String("42".to_string())),
+            ..FieldPolicy::default()
+        };
+        let context = FillContext::default();
+        assert_eq!(policy.timeout(&context), None);
+        assert_eq!(
+            policy.recover("age", &context, InputError::TimedOut),
+            Err(FillError::Input {
+                field: "age".to_string(),
+                error: InputError::TimedOut,
+            })
+        );
+
+        let context = FillContext {
+            timeout: Some(
+                TimeoutPolicy::after(Duration::from_secs(5)).on_timeout(Fallback::Default),
+            ),
+            ..FillContext::default()
+        };
+        assert_eq!(
+            policy.recover("age", &context, InputError::TimedOut),
+            Ok(Some(Value::String("42".to_string())))
+        );
+
+        let policy = FieldPolicy {
+            timeout: Some(
+                TimeoutPolicy::after(Duration::from_secs(1))
+                    .on_timeout(Fallback::Skip)
+                    .with_countdown(),
+            ),
+            ..policy
+        };
+        assert_eq!(
+            policy.recover("age", &context, InputError::TimedOut),
+            Ok(None)
+        );
+        let field_details = FieldDetails::default();
+        let details = policy.prompt_details(&context, field_details.prompt_details(&[], &[]));
+        assert_eq!(details.timeout, Some(Duration::from_secs(1)));
+        assert!(details.countdown);
+    }
+
+    #[test]
+    #[should_panic(expected = "A timeout must be longer than zero")]
+    fn test_zero_timeout() {
+        TimeoutPolicy::after(Duration::ZERO);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::value::ValueMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// A source of options for select and multiselect fields, loaded when the field is filled.
+///
+/// Providers receive the answers of the fields filled before, so the options
+/// of one field can depend on an earlier answer, such as the regions of a
+/// selected country. Closures taking `&ValueMap` implement this trait.
+pub trait OptionProvider<T> {
+    /// Loads the options.
+    ///
+    /// # Parameters
+    ///
+    /// * `answers` - The values of the fields filled so far, in field order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(T, String)>)` with the option values and labels.
+    /// * `Err(String)` if the options cannot be loaded.
+    fn options(&self, answers: &ValueMap) -> Result<Vec<(T, String)>, String>;
+}
+
+impl<T, F> OptionProvider<T> for F
+where
+    F: Fn(&ValueMap) -> Result<Vec<(T, String)>, String>,
+{
+    /// Loads the options by calling the closure.
+    fn options(&self, answers: &ValueMap) -> Result<Vec<(T, String)>, String> {
+        self(answers)
+    }
+}
+
+/// An option provider listing the entries of a directory.
+///
+/// The options are the paths of the entries, labelled with their file names
+/// and sorted by name.
+#[derive(Debug, Clone)]
+pub struct DirectoryOptions {
+    /// The directory to be listed.
+    path: PathBuf,
+    /// The file extension entries must have, if any.
+    extension: Option<String>,
+}
+
+impl DirectoryOptions {
+    /// Creates a provider listing every entry of a directory.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The directory to be listed.
+    ///
+    /// # Returns
+    ///
+    /// * A new `DirectoryOptions` instance.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            extension: None,
+        }
+    }
+
+    /// Restricts the options to files with an extension.
+    ///
+    /// # Parameters
+    ///
+    /// * `extension` - The extension without a leading dot, such as `"toml"`.
+    ///
+    /// # Returns
+    ///
+    /// * The `DirectoryOptions` instance with the extension set.
+    pub fn extension(mut self, extension: &str) -> Self {
+        self.extension = Some(extension.to_string());
+        self
+    }
+}
+
+impl OptionProvider<String> for DirectoryOptions {
+    /// Lists the directory.
+    fn options(&self, _answers: &ValueMap) -> Result<Vec<(String, String)>, String> {
+        let entries = fs::read_dir(&self.path)
+            .map_err(|e| format!("Failed to read '{}': {}", self.path.display(), e))?;
+        let mut options = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| format!("Failed to read '{}': {}", self.path.display(), e))?
+                .path();
+            if let Some(extension) = &self.extension
+                && path.extension().is_none_or(|e| e != extension.as_str())
+            {
+                continue;
+            }
+            let label = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            options.push((path.to_string_lossy().into_owned(), label));
+        }
+        options.sort_by(|a, b| a.1.cmp(&b.1));
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::Value;
+
+    #[test]
+    fn test_closure_provider() {
+        let regions = |answers: &ValueMap| match answers.get("country") {
+            Some(Value::String(country)) if country == "PL" => Ok(vec![
+                ("MZ".to_string(), "Masovia".to_string()),
+                ("MA".to_string(), "Lesser Poland".to_string()),
+            ]),
+            _ => Err("Unknown country".to_string()),
+        };
+        let mut answers = ValueMap::new();
+        assert!(regions.options(&answers).is_err());
+        answers.insert("country", Value::String("PL".to_string()));
+        assert_eq!(regions.options(&answers).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_directory_options() {
+        let dir = std::env::temp_dir().join(format!("form_builder_options_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["b.toml", "a.toml", "c.json"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let options = DirectoryOptions::new(&dir)
+            .extension("toml")
+            .options(&ValueMap::new())
+            .unwrap();
+        let labels: Vec<&str> = options.iter().map(|(_, label)| label.as_str()).collect();
+        assert_eq!(labels, vec!["a.toml", "b.toml"]);
+        assert_eq!(
+            DirectoryOptions::new(&dir)
+                .options(&ValueMap::new())
+                .unwrap()
+                .len(),
+            3
+        );
+        assert!(
+            DirectoryOptions::new(dir.join("missing"))
+                .options(&ValueMap::new())
+                .is_err()
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
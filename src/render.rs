@@ -0,0 +1,304 @@
+use std::fmt::Debug;
+
+/// The kind of prompt a frame shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    /// A line of text typed by the user.
+    Text,
+    /// A list navigated with the arrow keys, choosing one option with Enter.
+    Select,
+    /// A list navigated with the arrow keys, toggling options with Space.
+    Multiselect,
+    /// A numbered list of options, chosen by typing their numbers at a text prompt.
+    Numbered,
+    /// Only the help text and error, shown when a prompt ends.
+    Notice,
+}
+
+/// An option of a select list as shown in a frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameOption<'a> {
+    /// The label of the option.
+    pub label: &'a str,
+    /// The description of the option, if any.
+    pub description: Option<&'a str>,
+    /// Whether the option is selected in a multiselect list.
+    pub selected: bool,
+}
+
+/// The state of a prompt at one moment, drawn by a `Renderer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame<'a> {
+    /// The kind of prompt.
+    pub kind: PromptKind,
+    /// The prompt message.
+    pub prompt: &'a str,
+    /// The options of a select list, empty for text prompts.
+    pub options: Vec<FrameOption<'a>>,
+    /// The index of the highlighted option.
+    pub cursor: usize,
+    /// An example shown dimmed in an empty text input.
+    pub placeholder: Option<&'a str>,
+    /// The help text and requirements, if the user asked for them.
+    pub help: Option<String>,
+    /// Whether there is help text the user can ask for with `?`.
+    pub help_available: bool,
+    /// The error of the last answer, if it was invalid.
+    pub error: Option<String>,
+    /// The seconds left to answer, if a countdown is shown.
+    pub seconds_left: Option<u64>,
+}
+
+impl<'a> Frame<'a> {
+    /// Creates a frame without options, help, error or countdown.
+    ///
+    /// # Parameters
+    ///
+    /// * `kind` - The kind of prompt.
+    /// * `prompt` - The prompt message.
+    ///
+    /// # Returns
+    ///
+    /// * A new `Frame` instance.
+    pub fn new(kind: PromptKind, prompt: &'a str) -> Self {
+        Self {
+            kind,
+            prompt,
+            options: Vec::new(),
+            cursor: 0,
+            placeholder: None,
+            help: None,
+            help_available: false,
+            error: None,
+            seconds_left: None,
+        }
+    }
+}
+
+/// Draws frames of prompts as text.
+///
+/// Renderers only produce the text of a frame; the prompt functions write it
+/// to the terminal or stdout. Set a renderer for a form with
+/// `FormBuilder::renderer`.
+pub trait Renderer: Debug {
+    /// Draws a frame.
+    ///
+    /// # Parameters
+    ///
+    /// * `frame` - The state of the prompt.
+    ///
+    /// # Returns
+    ///
+    /// * The text to be written, replacing the previous frame of a select list.
+    fn render(&self, frame: &Frame<'_>) -> String;
+
+    /// Removes a select list from the screen once it is answered.
+    ///
+    /// # Returns
+    ///
+    /// * The text to be written, which is empty by default.
+    fn clear(&self) -> String {
+        String::new()
+    }
+}
+
+/// The default renderer, redrawing select lists in place with ANSI escape sequences.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnsiRenderer;
+
+impl Renderer for AnsiRenderer {
+    /// Draws a frame with escape sequences for the terminal.
+    fn render(&self, frame: &Frame<'_>) -> String {
+        draw(frame, true)
+    }
+
+    /// Clears the screen.
+    fn clear(&self) -> String {
+        CLEAR_SCREEN.to_string()
+    }
+}
+
+/// A renderer producing plain text without escape sequences, such as for snapshot tests.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlainRenderer;
+
+impl Renderer for PlainRenderer {
+    /// Draws a frame as plain text.
+    fn render(&self, frame: &Frame<'_>) -> String {
+        draw(frame, false)
+    }
+}
+
+/// The escape sequence clearing the screen and moving the cursor to the top left.
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H";
+
+/// Formats the countdown shown before a text prompt.
+pub(crate) fn countdown_prefix(seconds_left: u64) -> String {
+    format!("[{:>3}s] ", seconds_left)
+}
+
+/// Draws a frame, with escape sequences if `ansi` is set.
+fn draw(frame: &Frame<'_>, ansi: bool) -> String {
+    let dim = |text: &str| {
+        if ansi {
+            format!("\x1B[2m{}\x1B[0m", text)
+        } else {
+            text.to_string()
+        }
+    };
+    let mut text = String::new();
+    match frame.kind {
+        PromptKind::Text | PromptKind::Notice => {
+            if let Some(help) = &frame.help {
+                text.push_str(help);
+            }
+            if let Some(error) = &frame.error {
+                text.push_str(&format!("{}\n", error));
+            }
+            if frame.kind == PromptKind::Notice {
+                return text;
+            }
+            if let Some(seconds_left) = frame.seconds_left {
+                text.push_str(&countdown_prefix(seconds_left));
+            }
+            text.push_str(&format!("{} ", frame.prompt));
+            if let Some(placeholder) = frame.placeholder.filter(|_| ansi) {
+                text.push_str(&format!(
+                    "{}\x1B[{}D",
+                    dim(placeholder),
+                    placeholder.chars().count()
+                ));
+            }
+        }
+        PromptKind::Numbered => {
+            text.push_str(&format!("{}:\n", frame.prompt));
+            for (i, option) in frame.options.iter().enumerate() {
+                match option.description {
+                    Some(description) => text.push_str(&format!(
+                        "  {}) {} - {}\n",
+                        i + 1,
+                        option.label,
+                        description
+                    )),
+                    None => text.push_str(&format!("  {}) {}\n", i + 1, option.label)),
+                }
+            }
+            if frame.help_available {
+                text.push_str("Enter ? for help.\n");
+            }
+        }
+        PromptKind::Select | PromptKind::Multiselect => {
+            if ansi {
+                text.push_str(CLEAR_SCREEN);
+            }
+            text.push_str(&format!("{}:\n", frame.prompt));
+            if frame.kind == PromptKind::Multiselect {
+                text.push_str("Use Space to select/deselect, Enter to confirm\n");
+            }
+            for (i, option) in frame.options.iter().enumerate() {
+                let cursor = if i == frame.cursor { ">" } else { " " };
+                match frame.kind {
+                    PromptKind::Multiselect => {
+                        let marker = if option.selected { "*" } else { " " };
+                        text.push_str(&format!("{} [{}] {}\n", cursor, marker, option.label));
+                    }
+                    _ => text.push_str(&format!("{} {}\n", cursor, option.label)),
+                }
+            }
+            if let Some(description) = frame
+                .options
+                .get(frame.cursor)
+                .and_then(|option| option.description)
+            {
+                text.push_str(&format!("\n  {}\n", dim(description)));
+            }
+            if let Some(help) = &frame.help {
+                text.push_str(&format!("\n{}", help));
+            } else if frame.help_available {
+                text.push_str("\nPress ? for help\n");
+            }
+            if let Some(error) = &frame.error {
+                text.push_str(&format!("\n{}\n", error));
+            }
+            if let Some(seconds_left) = frame.seconds_left {
+                text.push_str(&format!("\nTime left: {}s\n", seconds_left));
+            }
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates the options of a plan select list for testing.
+    fn plans() -> Vec<FrameOption<'static>> {
+        vec![
+            FrameOption {
+                label: "Free",
+                description: None,
+                selected: false,
+            },
+            FrameOption {
+                label: "Pro",
+                description: Some("Unlimited seats"),
+                selected: true,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_plain_text_frames() {
+        let mut frame = Frame::new(PromptKind::Text, "Enter age:");
+        frame.placeholder = Some("42");
+        frame.error = Some("Invalid input. Please try again.".to_string());
+        frame.seconds_left = Some(9);
+        assert_eq!(
+            PlainRenderer.render(&frame),
+            "Invalid input. Please try again.\n[  9s] Enter age: "
+        );
+        assert_eq!(
+            AnsiRenderer.render(&frame),
+            "Invalid input. Please try again.\n[  9s] Enter age: \x1B[2m42\x1B[0m\x1B[2D"
+        );
+
+        frame.kind = PromptKind::Notice;
+        assert_eq!(
+            PlainRenderer.render(&frame),
+            "Invalid input. Please try again.\n"
+        );
+    }
+
+    #[test]
+    fn test_plain_select_frames() {
+        let mut frame = Frame::new(PromptKind::Select, "Select plan");
+        frame.options = plans();
+        frame.cursor = 1;
+        frame.help_available = true;
+        assert_eq!(
+            PlainRenderer.render(&frame),
+            "Select plan:\n  Free\n> Pro\n\n  Unlimited seats\n\nPress ? for help\n"
+        );
+        assert!(AnsiRenderer.render(&frame).starts_with(CLEAR_SCREEN));
+
+        frame.kind = PromptKind::Multiselect;
+        frame.cursor = 0;
+        frame.help = Some("Pick any.\n".to_string());
+        frame.error = Some("Please choose at least one option".to_string());
+        frame.seconds_left = Some(3);
+        assert_eq!(
+            PlainRenderer.render(&frame),
+            "Select plan:\nUse Space to select/deselect, Enter to confirm\n\
+             > [ ] Free\n  [*] Pro\n\nPick any.\n\n\
+             Please choose at least one option\n\nTime left: 3s\n"
+        );
+
+        frame.kind = PromptKind::Numbered;
+        assert_eq!(
+            PlainRenderer.render(&frame),
+            "Select plan:\n  1) Free\n  2) Pro - Unlimited seats\nEnter ? for help.\n"
+        );
+        assert_eq!(PlainRenderer.clear(), "");
+    }
+}
@@ -0,0 +1,189 @@
+use crate::field::{FieldTrait, FillContext, FillError};
+use crate::form::Form;
+use crate::input::{PromptDetails, print_text, read_confirm_with_details};
+use crate::value::{FieldKind, Value};
+
+/// A struct representing a repeatable group of fields in a form.
+///
+/// Each entry is a separate `Form` created from the template, so the group
+/// collects a list of records with the same fields.
+pub struct RepeatField {
+    /// The prompt to display to the user before the first entry.
+    pub prompt: String,
+    /// A function creating an empty sub-form for each new entry.
+    pub template: Box<dyn Fn() -> Form>,
+    /// The minimum number of entries.
+    pub min: usize,
+    /// The optional maximum number of entries.
+    pub max: Option<usize>,
+    /// The filled entries.
+    pub entries: Vec<Form>,
+}
+
+impl RepeatField {
+    /// Checks whether another entry may be added.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the maximum number of entries has not been reached, `false` otherwise.
+    fn can_add(&self) -> bool {
+        self.max.is_none_or(|max| self.entries.len() < max)
+    }
+}
+
+impl FieldTrait for RepeatField {
+    /// Fills the group by prompting the user for entries until they decline or the maximum is reached.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the group is successfully filled.
+    /// * `Err(String)` if there is an error filling any entry.
+    fn fill(&mut self) -> Result<(), String> {
+        Ok(self.fill_with_context(&FillContext::default())?)
+    }
+
+    /// Fills the group, passing the answers of the enclosing form to each entry.
+    fn fill_with_context(&mut self, context: &FillContext) -> Result<(), FillError> {
+        print_text(&format!("{}\n", self.prompt), context.output)?;
+        self.entries.clear();
+        let details = PromptDetails {
+            renderer: context.renderer.as_deref(),
+            output: context.output,
+            ..PromptDetails::default()
+        };
+
+        while self.can_add() {
+            if self.entries.len() >= self.min {
+                let question = if self.entries.is_empty() {
+                    "Add an entry? (y/n):"
+                } else {
+                    "Add another entry? (y/n):"
+                };
+                let add = read_confirm_with_details(question, &details).map_err(|error| {
+                    FillError::Input {
+                        field: context.field.clone(),
+                        error,
+                    }
+                })?;
+                if !add {
+                    break;
+                }
+            }
+
+            print_text(
+                &format!("Entry {}:\n", self.entries.len() + 1),
+                context.output,
+            )?;
+            let mut entry = (self.template)();
+            entry.fill_with_context(context)?;
+            self.entries.push(entry);
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the field as a `dyn Any`.
+    ///
+    /// # Returns
+    ///
+    /// * A reference to the field as a `dyn Any`.
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// Returns a mutable reference to the field as a `dyn Any`.
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+
+    /// Gets the answers of every entry as a list of records.
+    fn draft_value(&self) -> Value {
+        Value::List(
+            self.entries
+                .iter()
+                .map(|entry| Value::Record(entry.draft_answers()))
+                .collect(),
+        )
+    }
+
+    /// Restores the entries of the group from a list of records.
+    fn restore(&mut self, value: &Value, context: &FillContext) -> Result<(), String> {
+        let Value::List(entries) = value else {
+            return Err(format!(
+                "Invalid draft answer for field '{}'",
+                context.field
+            ));
+        };
+        self.entries = entries
+            .iter()
+            .map(|entry| {
+                let Value::Record(answers) = entry else {
+                    return Err(format!("Invalid draft entry for field '{}'", context.field));
+                };
+                let mut form = (self.template)();
+                form.restore_answers(answers, context)?;
+                Ok(form)
+            })
+            .collect::<Result<Vec<Form>, String>>()?;
+        Ok(())
+    }
+
+    /// Fills the entries of the group from a list of records of answers.
+    fn answer(&mut self, answer: &Value, context: &FillContext) -> Result<(), FillError> {
+        let entries = match answer {
+            Value::List(entries) => entries.as_slice(),
+            Value::Null => &[],
+            _ => return Err(context.reject("The answer must be a list".to_string())),
+        };
+        if entries.len() < self.min {
+            return Err(context.reject(format!("Please add at least {} entries", self.min)));
+        }
+        if let Some(max) = self.max.filter(|max| entries.len() > *max) {
+            return Err(context.reject(format!("Please add at most {} entries", max)));
+        }
+        self.entries.clear();
+        for entry in entries {
+            let Value::Record(answers) = entry else {
+                return Err(context.reject("Every entry must be an object".to_string()));
+            };
+            let mut form = (self.template)();
+            form.fill_from_answers_with_context(answers, context)?;
+            self.entries.push(form);
+        }
+        Ok(())
+    }
+
+    /// Gets the entries of the group as a string.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` with every entry formatted as a list of `name: value` pairs.
+    /// * `Err(String)` if any field of an entry has no value.
+    fn get_value(&self) -> Result<String, String> {
+        let entries = self
+            .entries
+            .iter()
+            .map(Form::format_values)
+            .collect::<Result<Vec<String>, String>>()?;
+        Ok(format!("[{}]", entries.join(", ")))
+    }
+
+    /// Returns the prompt of the field.
+    fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    /// Returns the kind of the field.
+    fn kind(&self) -> FieldKind {
+        FieldKind::Repeat
+    }
+
+    /// Gets the value of the field as a dynamically typed `Value`.
+    fn value(&self) -> Value {
+        Value::List(
+            self.entries
+                .iter()
+                .map(|entry| Value::Record(entry.values()))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,1146 @@
+use crate::field::FieldTrait;
+use crate::form::Form;
+use crate::form_builder::FormBuilder;
+use crate::json;
+use crate::repeat_field::RepeatField;
+use crate::section_field::SectionField;
+use crate::validation::{NamedRule, Validator};
+use crate::value::{FieldKind, Value, ValueKind, ValueMap};
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// The JSON Schema dialect of exported schemas.
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// The keywords allowed only in the root schema.
+const ROOT_KEYWORDS: &[&str] = &["$schema", "$id"];
+
+/// The keywords allowed in the schema of every field.
+const COMMON_KEYWORDS: &[&str] = &["type", "title", "description"];
+
+/// The keywords of object schemas, read as forms and sections.
+const OBJECT_KEYWORDS: &[&str] = &["properties", "required", "additionalProperties"];
+
+/// The keywords of array schemas, read as multiselect fields and repeatable groups.
+const ARRAY_KEYWORDS: &[&str] = &["items", "minItems", "maxItems", "uniqueItems"];
+
+/// The keywords listing the options of select and multiselect fields.
+const OPTIONS_KEYWORDS: &[&str] = &["enum", "oneOf"];
+
+/// The keywords of an option listed by `oneOf`.
+const OPTION_KEYWORDS: &[&str] = &["const", "title", "description"];
+
+/// The keywords of string, number and boolean schemas, read as text input fields.
+const INPUT_KEYWORDS: &[&str] = &["writeOnly"];
+
+/// The keywords of string schemas only.
+const STRING_KEYWORDS: &[&str] = &[
+    "minLength",
+    "maxLength",
+    "format",
+    "pattern",
+    "allOf",
+    "examples",
+];
+
+/// Writes the JSON Schema of the values of a form.
+///
+/// # Parameters
+///
+/// * `form` - The form to be described.
+///
+/// # Returns
+///
+/// * The schema as compact JSON text.
+pub(crate) fn write(form: &Form) -> String {
+    let mut schema = ValueMap::new();
+    schema.insert("$schema", Value::String(DIALECT.to_string()));
+    schema.insert("type", Value::String("object".to_string()));
+    write_object(form, &mut schema);
+    json::write(&Value::Record(schema))
+}
+
+/// Appends the properties of the fields of a form to an object schema.
+fn write_object(form: &Form, schema: &mut ValueMap) {
+    let mut properties = ValueMap::new();
+    let mut required = Vec::new();
+    for (name, field) in form.fields.values() {
+        properties.insert(name, Value::Record(field_schema(field.as_ref())));
+        if !field.is_optional() {
+            required.push(Value::String(name.clone()));
+        }
+    }
+    schema.insert("properties", Value::Record(properties));
+    schema.insert("required", Value::List(required));
+    schema.insert("additionalProperties", Value::Bool(false));
+}
+
+/// Describes the value of a field.
+fn field_schema(field: &dyn FieldTrait) -> ValueMap {
+    let mut schema = ValueMap::new();
+    let title = Value::String(field.prompt().to_string());
+    let help = field.details().and_then(|details| details.help.clone());
+    match field.kind() {
+        FieldKind::Section => {
+            schema.insert("type", Value::String("object".to_string()));
+            schema.insert("title", title);
+            if let Some(section) = field.as_any().downcast_ref::<SectionField>() {
+                if let Some(description) = &section.description {
+                    schema.insert("description", Value::String(description.clone()));
+                }
+                write_object(&section.form, &mut schema);
+            }
+        }
+        FieldKind::Repeat => {
+            schema.insert("type", Value::String("array".to_string()));
+            schema.insert("title", title);
+            if let Some(repeat) = field.as_any().downcast_ref::<RepeatField>() {
+                let mut items = ValueMap::new();
+                items.insert("type", Value::String("object".to_string()));
+                write_object(&(repeat.template)(), &mut items);
+                schema.insert("items", Value::Record(items));
+                if repeat.min > 0 {
+                    schema.insert("minItems", Value::of(&repeat.min));
+                }
+                if let Some(max) = repeat.max {
+                    schema.insert("maxItems", Value::of(&max));
+                }
+            }
+        }
+        FieldKind::Select => {
+            if let Some(type_name) = options_type(&field.options()) {
+                schema.insert("type", Value::String(type_name.to_string()));
+            }
+            schema.insert("title", title);
+            if let Some(help) = help {
+                schema.insert("description", Value::String(help));
+            }
+            write_options(field, &mut schema);
+        }
+        FieldKind::Multiselect => {
+            schema.insert("type", Value::String("array".to_string()));
+            schema.insert("title", title);
+            if let Some(help) = help {
+                schema.insert("description", Value::String(help));
+            }
+            let mut items = ValueMap::new();
+            if let Some(type_name) = options_type(&field.options()) {
+                items.insert("type", Value::String(type_name.to_string()));
+            }
+            write_options(field, &mut items);
+            schema.insert("items", Value::Record(items));
+            schema.insert("uniqueItems", Value::Bool(true));
+            schema.insert("minItems", Value::Integer(1));
+            if let Some(limit) = field.selection_limit() {
+                schema.insert("maxItems", Value::of(&limit));
+            }
+        }
+        FieldKind::Input(kind) => {
+            let value_type = Value::String(type_name(kind).to_string());
+            if field.is_optional() {
+                schema.insert(
+                    "type",
+                    Value::List(vec![value_type, Value::String("null".to_string())]),
+                );
+            } else {
+                schema.insert("type", value_type);
+            }
+            schema.insert("title", title);
+            if let Some(help) = help {
+                schema.insert("description", Value::String(help));
+            }
+            if type_name(kind) == "string" {
+                write_string_constraints(field, kind, &mut schema);
+                if let Some(placeholder) = field
+                    .details()
+                    .and_then(|details| details.placeholder.clone())
+                {
+                    schema.insert("examples", Value::List(vec![Value::String(placeholder)]));
+                }
+            }
+            if field.is_secret() {
+                schema.insert("writeOnly", Value::Bool(true));
+            }
+        }
+    }
+    schema
+}
+
+/// Appends the options of a select or multiselect field to a schema, as
+/// `oneOf` constants titled with their labels and described with their descriptions.
+fn write_options(field: &dyn FieldTrait, schema: &mut ValueMap) {
+    let descriptions = field
+        .details()
+        .map_or(&[][..], |details| &details.option_descriptions[..]);
+    let options = field
+        .options()
+        .into_iter()
+        .map(|(value, label)| {
+            let description = descriptions
+                .iter()
+                .find(|(option, _)| *option == value)
+                .map(|(_, description)| description.clone());
+            let mut option = ValueMap::new();
+            option.insert("const", value);
+            option.insert("title", Value::String(label));
+            if let Some(description) = description {
+                option.insert("description", Value::String(description));
+            }
+            Value::Record(option)
+        })
+        .collect();
+    schema.insert("oneOf", Value::List(options));
+}
+
+/// Appends the `minLength`, `maxLength`, `format` and `pattern` keywords
+/// derived from the validator of a text field to a schema.
+///
+/// Only `not_empty` and the built-in rules based on a format or a regular
+/// expression have a keyword. Further formats and patterns go into `allOf`.
+fn write_string_constraints(field: &dyn FieldTrait, kind: ValueKind, schema: &mut ValueMap) {
+    let mut constraints: Vec<(&str, Value)> = Vec::new();
+    if kind == ValueKind::Char {
+        constraints.push(("minLength", Value::Integer(1)));
+        constraints.push(("maxLength", Value::Integer(1)));
+    }
+    if let Some(validator) = field.validator() {
+        for rule in validator.named_rules() {
+            if rule == NamedRule::NotEmpty {
+                constraints.push(("minLength", Value::Integer(1)));
+            } else if let Some(format) = rule.format() {
+                constraints.push(("format", Value::String(format.to_string())));
+            } else if let Some(regex) = rule.regex() {
+                constraints.push(("pattern", Value::String(regex.to_string())));
+            }
+        }
+        for regex in validator.pattern_regexes() {
+            constraints.push(("pattern", Value::String(regex)));
+        }
+    }
+    let mut all_of = Vec::new();
+    for (keyword, value) in constraints {
+        match schema.get(keyword) {
+            None => schema.insert(keyword, value),
+            Some(existing) if *existing == value => {}
+            Some(_) => {
+                let mut constraint = ValueMap::new();
+                constraint.insert(keyword, value);
+                all_of.push(Value::Record(constraint));
+            }
+        }
+    }
+    if !all_of.is_empty() {
+        schema.insert("allOf", Value::List(all_of));
+    }
+}
+
+/// Returns the JSON Schema type of values of a kind.
+fn type_name(kind: ValueKind) -> &'static str {
+    match kind {
+        ValueKind::Integer => "integer",
+        ValueKind::Float => "number",
+        ValueKind::Bool => "boolean",
+        ValueKind::String | ValueKind::Char | ValueKind::Other => "string",
+    }
+}
+
+/// Returns the JSON Schema type shared by all option values, if there is one.
+fn options_type(options: &[(Value, String)]) -> Option<&'static str> {
+    let type_of = |value: &Value| match value {
+        Value::Integer(_) => Some("integer"),
+        Value::Float(_) => Some("number"),
+        Value::Bool(_) => Some("boolean"),
+        Value::String(_) | Value::Char(_) => Some("string"),
+        _ => None,
+    };
+    let first = type_of(&options.first()?.0)?;
+    options
+        .iter()
+        .all(|(value, _)| type_of(value) == Some(first))
+        .then_some(first)
+}
+
+/// A step adding a field read from a schema to a form builder.
+type Step = Rc<dyn Fn(FormBuilder) -> FormBuilder>;
+
+/// The fields read from an object schema, as the steps adding them to a form builder.
+///
+/// The schema is read and checked once, and the steps can then build any
+/// number of forms, such as one for each entry of a repeatable group.
+#[derive(Clone, Default)]
+struct Fields {
+    /// The steps, in the order of the properties.
+    steps: Vec<Step>,
+}
+
+impl Fields {
+    /// Adds a step to the fields.
+    ///
+    /// # Parameters
+    ///
+    /// * `step` - The function adding a field to the builder, or changing one added before.
+    ///
+    /// # Returns
+    ///
+    /// * The `Fields` with the step added.
+    fn then<F>(mut self, step: F) -> Self
+    where
+        F: Fn(FormBuilder) -> FormBuilder + 'static,
+    {
+        self.steps.push(Rc::new(step));
+        self
+    }
+
+    /// Builds a new form builder with the fields.
+    ///
+    /// # Returns
+    ///
+    /// * A `FormBuilder` with a field for each property.
+    fn builder(&self) -> FormBuilder {
+        self.steps
+            .iter()
+            .fold(FormBuilder::new(), |builder, step| step(builder))
+    }
+}
+
+/// Reads a form definition from a JSON Schema.
+///
+/// # Parameters
+///
+/// * `schema` - The JSON text of the schema.
+///
+/// # Returns
+///
+/// * `Ok(FormBuilder)` with a field for each property.
+/// * `Err(String)` if the schema is not valid JSON or uses unsupported keywords.
+pub(crate) fn read(schema: &str) -> Result<FormBuilder, String> {
+    let schema = json::parse(schema).map_err(|err| format!("Invalid JSON Schema: {}", err))?;
+    let schema = as_object(&schema, "")?;
+    check_keywords(
+        schema,
+        "",
+        &[ROOT_KEYWORDS, COMMON_KEYWORDS, OBJECT_KEYWORDS],
+    )?;
+    match read_type(schema, "")? {
+        (None, _) => {}
+        (Some(type_name), false) if type_name == "object" => {}
+        _ => return Err("The root of the JSON Schema must be an object".to_string()),
+    }
+    read_object(schema, "").map(|fields| fields.builder())
+}
+
+/// Reads the properties of an object schema as the fields of a form.
+fn read_object(schema: &ValueMap, path: &str) -> Result<Fields, String> {
+    if let Some(value) = schema.get("additionalProperties")
+        && !matches!(value, Value::Bool(_))
+    {
+        return Err(format!(
+            "'additionalProperties' of {} must be a boolean",
+            location(path)
+        ));
+    }
+    let required = match schema.get("required") {
+        None => Vec::new(),
+        Some(Value::List(names)) => names
+            .iter()
+            .map(|name| match name {
+                Value::String(name) => Ok(name.as_str()),
+                _ => Err(format!(
+                    "'required' of {} must list property names",
+                    location(path)
+                )),
+            })
+            .collect::<Result<Vec<&str>, String>>()?,
+        Some(_) => {
+            return Err(format!("'required' of {} must be an array", location(path)));
+        }
+    };
+    let properties = match schema.get("properties") {
+        None => &ValueMap::new(),
+        Some(Value::Record(properties)) => properties,
+        Some(_) => {
+            return Err(format!(
+                "'properties' of {} must be an object",
+                location(path)
+            ));
+        }
+    };
+    let mut fields = Fields::default();
+    for (name, property) in properties.iter() {
+        let path = if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", path, name)
+        };
+        let property = as_object(property, &path)?;
+        fields = read_property(fields, name, property, !required.contains(&name), &path)?;
+    }
+    Ok(fields)
+}
+
+/// Adds the field described by the schema of a property.
+fn read_property(
+    fields: Fields,
+    name: &str,
+    schema: &ValueMap,
+    optional: bool,
+    path: &str,
+) -> Result<Fields, String> {
+    let (type_name, nullable) = read_type(schema, path)?;
+    let title = string_keyword(schema, "title", path)?.unwrap_or(name);
+    let description = string_keyword(schema, "description", path)?;
+    let fields = match type_name.as_deref() {
+        Some("object") => {
+            check_keywords(schema, path, &[COMMON_KEYWORDS, OBJECT_KEYWORDS])?;
+            let section = read_object(schema, path)?;
+            let (name, title) = (name.to_string(), title.to_string());
+            let description = description.map(str::to_string);
+            return Ok(fields.then(move |builder| {
+                let form = section.builder().build();
+                builder.add_section(&name, &title, description.as_deref(), form)
+            }));
+        }
+        Some("array") => {
+            check_keywords(schema, path, &[COMMON_KEYWORDS, ARRAY_KEYWORDS])?;
+            return read_array(fields, name, title, description, schema, path);
+        }
+        _ if has_options(schema) => {
+            check_keywords(schema, path, &[COMMON_KEYWORDS, OPTIONS_KEYWORDS])?;
+            read_choices(fields, name, title, schema, None, path)?
+        }
+        Some(type_name) => {
+            let keywords: &[&[&str]] = if type_name == "string" {
+                &[COMMON_KEYWORDS, INPUT_KEYWORDS, STRING_KEYWORDS]
+            } else {
+                &[COMMON_KEYWORDS, INPUT_KEYWORDS]
+            };
+            check_keywords(schema, path, keywords)?;
+            read_input(
+                fields,
+                name,
+                title,
+                type_name,
+                optional || nullable,
+                schema,
+                path,
+            )?
+        }
+        None => return Err(format!("Missing 'type' of {}", location(path))),
+    };
+    Ok(with_help(fields, name, description))
+}
+
+/// Adds a step setting the help text of a field to its description, if it has one.
+fn with_help(fields: Fields, name: &str, description: Option<&str>) -> Fields {
+    match description {
+        Some(description) => {
+            let (name, description) = (name.to_string(), description.to_string());
+            fields.then(move |builder| builder.help(&name, &description))
+        }
+        None => fields,
+    }
+}
+
+/// Adds a multiselect field or a repeatable group described by an array schema.
+///
+/// Repeatable groups have no help text, so their schema cannot have a `description`.
+fn read_array(
+    fields: Fields,
+    name: &str,
+    title: &str,
+    description: Option<&str>,
+    schema: &ValueMap,
+    path: &str,
+) -> Result<Fields, String> {
+    let items_path = format!("{}[]", path);
+    let items = as_object(
+        schema
+            .get("items")
+            .ok_or_else(|| format!("Missing 'items' of {}", location(path)))?,
+        &items_path,
+    )?;
+    if let Some(unique) = schema.get("uniqueItems")
+        && !matches!(unique, Value::Bool(_))
+    {
+        return Err(format!(
+            "'uniqueItems' of {} must be a boolean",
+            location(path)
+        ));
+    }
+    let min = usize_keyword(schema, "minItems", path)?;
+    let max = usize_keyword(schema, "maxItems", path)?;
+    if has_options(items) {
+        check_keywords(items, &items_path, &[&["type"], OPTIONS_KEYWORDS])?;
+        if min.is_some_and(|min| min != 1) {
+            return Err(format!(
+                "Unsupported 'minItems' of {}; multiselect fields need exactly one selection at least",
+                location(path)
+            ));
+        }
+        let fields = read_choices(fields, name, title, items, Some(max), path)?;
+        return Ok(with_help(fields, name, description));
+    }
+    check_keywords(schema, path, &[&["type", "title"], ARRAY_KEYWORDS])?;
+    check_keywords(items, &items_path, &[COMMON_KEYWORDS, OBJECT_KEYWORDS])?;
+    let entry = read_object(items, &items_path)?;
+    let (name, title) = (name.to_string(), title.to_string());
+    Ok(fields.then(move |builder| {
+        let entry = entry.clone();
+        builder.add_repeat(
+            &name,
+            &title,
+            move || entry.builder().build(),
+            min.unwrap_or(0),
+            max,
+        )
+    }))
+}
+
+/// Checks whether a schema lists options with `enum` or `oneOf`.
+fn has_options(schema: &ValueMap) -> bool {
+    schema.get("enum").is_some() || schema.get("oneOf").is_some()
+}
+
+/// Reads the options listed by the `enum` or `oneOf` of a schema.
+///
+/// # Returns
+///
+/// * `Ok(Vec)` with the value, label and description of each option; the
+///   labels of `enum` values and of options without a `title` are their values.
+/// * `Err(String)` if the options are missing, empty or malformed.
+fn read_options(
+    schema: &ValueMap,
+    path: &str,
+) -> Result<Vec<(Value, String, Option<String>)>, String> {
+    let options = match (schema.get("enum"), schema.get("oneOf")) {
+        (Some(Value::List(values)), None) => values
+            .iter()
+            .map(|value| (value.clone(), value.to_string(), None))
+            .collect(),
+        (None, Some(Value::List(options))) => options
+            .iter()
+            .map(|option| {
+                let option = as_object(option, path)?;
+                check_keywords(option, path, &[OPTION_KEYWORDS])?;
+                let value = option
+                    .get("const")
+                    .ok_or_else(|| format!("Every option of {} needs a 'const'", location(path)))?;
+                let label = string_keyword(option, "title", path)?
+                    .map_or_else(|| value.to_string(), str::to_string);
+                let description = string_keyword(option, "description", path)?;
+                Ok((value.clone(), label, description.map(str::to_string)))
+            })
+            .collect::<Result<Vec<_>, String>>()?,
+        (Some(_), Some(_)) => {
+            return Err(format!(
+                "Options of {} must be listed by either 'enum' or 'oneOf'",
+                location(path)
+            ));
+        }
+        (Some(_), None) => {
+            return Err(format!("'enum' of {} must be an array", location(path)));
+        }
+        (None, _) => return Err(format!("'oneOf' of {} must be an array", location(path))),
+    };
+    if options.is_empty() {
+        return Err(format!("No options of {} are listed", location(path)));
+    }
+    Ok(options)
+}
+
+/// Adds a select field, or a multiselect field with the given limit, for the options of a schema.
+fn read_choices(
+    fields: Fields,
+    name: &str,
+    title: &str,
+    schema: &ValueMap,
+    multiselect: Option<Option<usize>>,
+    path: &str,
+) -> Result<Fields, String> {
+    let options = read_options(schema, path)?;
+    let fields = add_options(fields, name, title, &options, multiselect, path)?;
+    Ok(options.into_iter().fold(
+        fields,
+        |fields, (value, _, description)| match description {
+            Some(description) => {
+                let name = name.to_string();
+                fields.then(move |builder| {
+                    builder.describe_option(&name, value.clone(), &description)
+                })
+            }
+            None => fields,
+        },
+    ))
+}
+
+/// Adds a select field, or a multiselect field with the given limit, with options of the type of their values.
+fn add_options(
+    fields: Fields,
+    name: &str,
+    title: &str,
+    options: &[(Value, String, Option<String>)],
+    multiselect: Option<Option<usize>>,
+    path: &str,
+) -> Result<Fields, String> {
+    let (values, labels): (Vec<Value>, Vec<String>) = options
+        .iter()
+        .map(|(value, label, _)| (value.clone(), label.clone()))
+        .unzip();
+    let values = &values;
+    let labels = &labels;
+    match &values[0] {
+        Value::Integer(_) => {
+            let values = enum_values(values, path, |value| match value {
+                Value::Integer(value) => Some(*value),
+                _ => None,
+            })?;
+            Ok(add_choices(
+                fields,
+                name,
+                title,
+                values,
+                labels,
+                multiselect,
+            ))
+        }
+        Value::Float(_) => {
+            let values = enum_values(values, path, |value| match value {
+                Value::Float(value) => Some(*value),
+                _ => None,
+            })?;
+            Ok(add_choices(
+                fields,
+                name,
+                title,
+                values,
+                labels,
+                multiselect,
+            ))
+        }
+        Value::Bool(_) => {
+            let values = enum_values(values, path, |value| match value {
+                Value::Bool(value) => Some(*value),
+                _ => None,
+            })?;
+            Ok(add_choices(
+                fields,
+                name,
+                title,
+                values,
+                labels,
+                multiselect,
+            ))
+        }
+        Value::String(_) => {
+            let values = enum_values(values, path, |value| match value {
+                Value::String(value) => Some(value.clone()),
+                _ => None,
+            })?;
+            Ok(add_choices(
+                fields,
+                name,
+                title,
+                values,
+                labels,
+                multiselect,
+            ))
+        }
+        _ => Err(format!(
+            "Options of {} must be strings, numbers or booleans",
+            location(path)
+        )),
+    }
+}
+
+/// Converts the values of an enumeration, which must all have the same type.
+fn enum_values<T>(
+    values: &[Value],
+    path: &str,
+    convert: fn(&Value) -> Option<T>,
+) -> Result<Vec<T>, String> {
+    values
+        .iter()
+        .map(|value| {
+            convert(value)
+                .ok_or_else(|| format!("Options of {} must all have the same type", location(path)))
+        })
+        .collect()
+}
+
+/// Adds a select field, or a multiselect field with the given limit.
+fn add_choices<T>(
+    fields: Fields,
+    name: &str,
+    title: &str,
+    values: Vec<T>,
+    labels: &[String],
+    multiselect: Option<Option<usize>>,
+) -> Fields
+where
+    T: 'static + Clone + PartialEq + Debug,
+{
+    let (name, title, labels) = (name.to_string(), title.to_string(), labels.to_vec());
+    fields.then(move |builder| {
+        let options = values
+            .iter()
+            .cloned()
+            .zip(labels.iter().map(String::as_str))
+            .collect();
+        match multiselect {
+            Some(limit) => builder.add_multiselect(&name, &title, options, limit),
+            None => builder.add_select(&name, &title, options),
+        }
+    })
+}
+
+/// Adds a text input field for a string, number or boolean schema.
+fn read_input(
+    fields: Fields,
+    name: &str,
+    title: &str,
+    type_name: &str,
+    optional: bool,
+    schema: &ValueMap,
+    path: &str,
+) -> Result<Fields, String> {
+    let mut min_length = usize_keyword(schema, "minLength", path)?;
+    let mut max_length = usize_keyword(schema, "maxLength", path)?;
+    let is_char = min_length == Some(1) && max_length == Some(1);
+    if is_char {
+        (min_length, max_length) = (None, None);
+    }
+    if max_length.is_some() {
+        return Err(format!(
+            "Unsupported 'maxLength' of {}; only single characters are limited",
+            location(path)
+        ));
+    }
+    let mut rules = Vec::new();
+    match min_length {
+        None | Some(0) => {}
+        Some(1) => rules.push(NamedRule::NotEmpty),
+        Some(_) => {
+            return Err(format!(
+                "Unsupported 'minLength' of {}; only non-empty input is required",
+                location(path)
+            ));
+        }
+    }
+    let mut constraints = vec![schema];
+    match schema.get("allOf") {
+        None => {}
+        Some(Value::List(schemas)) => {
+            for constraint in schemas {
+                let constraint = as_object(constraint, path)?;
+                check_keywords(constraint, path, &[&["format", "pattern"]])?;
+                constraints.push(constraint);
+            }
+        }
+        Some(_) => return Err(format!("'allOf' of {} must be an array", location(path))),
+    }
+    let mut patterns = Vec::new();
+    for constraint in constraints {
+        if let Some(format) = string_keyword(constraint, "format", path)? {
+            let rule = NamedRule::from_format(format)
+                .ok_or_else(|| format!("Unsupported format '{}' of {}", format, location(path)))?;
+            rules.push(rule);
+        }
+        if let Some(pattern) = string_keyword(constraint, "pattern", path)? {
+            patterns.push(pattern);
+        }
+    }
+    let validator = if rules.is_empty() && patterns.is_empty() {
+        None
+    } else {
+        let validator = rules
+            .into_iter()
+            .fold(Validator::new(Vec::new()), |validator, rule| {
+                validator.builtin(rule, None)
+            });
+        let validator = patterns
+            .into_iter()
+            .try_fold(validator, |validator, pattern| {
+                validator.regex(pattern, None)
+            })
+            .map_err(|err| format!("Invalid 'pattern' of {}: {}", location(path), err))?;
+        Some(validator)
+    };
+    let add: AddInput = match type_name {
+        "string" if is_char => add_input::<char>,
+        "string" => add_input::<String>,
+        "integer" => add_input::<i64>,
+        "number" => add_input::<f64>,
+        "boolean" => add_input::<bool>,
+        _ => {
+            return Err(format!(
+                "Unsupported type '{}' of {}",
+                type_name,
+                location(path)
+            ));
+        }
+    };
+    let placeholder = match schema.get("examples") {
+        None => None,
+        Some(Value::List(examples)) => match examples.first() {
+            Some(Value::String(example)) => Some(example.clone()),
+            _ => None,
+        },
+        Some(_) => {
+            return Err(format!("'examples' of {} must be an array", location(path)));
+        }
+    };
+    let secret = match schema.get("writeOnly") {
+        None => false,
+        Some(Value::Bool(secret)) => *secret,
+        Some(_) => {
+            return Err(format!(
+                "'writeOnly' of {} must be a boolean",
+                location(path)
+            ));
+        }
+    };
+    let (name, title) = (name.to_string(), title.to_string());
+    Ok(fields.then(move |builder| {
+        let mut builder = add(builder, &name, &title, validator.clone(), optional);
+        if let Some(placeholder) = &placeholder {
+            builder = builder.placeholder(&name, placeholder);
+        }
+        if secret {
+            builder = builder.secret(&name);
+        }
+        builder
+    }))
+}
+
+/// A function adding a text input field of one value type.
+type AddInput = fn(FormBuilder, &str, &str, Option<Validator>, bool) -> FormBuilder;
+
+/// Adds a text input field, or an optional one whose value may be absent.
+fn add_input<T>(
+    builder: FormBuilder,
+    name: &str,
+    title: &str,
+    validator: Option<Validator>,
+    optional: bool,
+) -> FormBuilder
+where
+    T: 'static + FromStr + Debug + Clone + Default,
+    T::Err: Debug,
+{
+    if optional {
+        builder.add_optional_field::<T>(name, title, validator)
+    } else {
+        builder.add_field::<T>(name, title, validator)
+    }
+}
+
+/// Reads the `type` of a schema and whether it allows `null`.
+fn read_type(schema: &ValueMap, path: &str) -> Result<(Option<String>, bool), String> {
+    let (names, nullable) = match schema.get("type") {
+        None => return Ok((None, false)),
+        Some(Value::String(name)) => (vec![name.as_str()], false),
+        Some(Value::List(names)) => {
+            let names = names
+                .iter()
+                .map(|name| match name {
+                    Value::String(name) => Ok(name.as_str()),
+                    _ => Err(format!("'type' of {} must list type names", location(path))),
+                })
+                .collect::<Result<Vec<&str>, String>>()?;
+            let nullable = names.contains(&"null");
+            (
+                names.into_iter().filter(|name| *name != "null").collect(),
+                nullable,
+            )
+        }
+        Some(_) => {
+            return Err(format!(
+                "'type' of {} must be a string or an array",
+                location(path)
+            ));
+        }
+    };
+    match names[..] {
+        [name @ ("object" | "array" | "string" | "integer" | "number" | "boolean")] => {
+            Ok((Some(name.to_string()), nullable))
+        }
+        _ => Err(format!(
+            "Unsupported type '{}' of {}",
+            schema
+                .get("type")
+                .map_or_else(String::new, Value::to_string),
+            location(path)
+        )),
+    }
+}
+
+/// Fails on the first keyword of a schema not in any of the allowed lists.
+fn check_keywords(schema: &ValueMap, path: &str, allowed: &[&[&str]]) -> Result<(), String> {
+    match schema
+        .iter()
+        .find(|(keyword, _)| !allowed.iter().any(|keywords| keywords.contains(keyword)))
+    {
+        Some((keyword, _)) => Err(format!(
+            "Unsupported JSON Schema keyword '{}' at {}",
+            keyword,
+            location(path)
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Borrows a schema as an object.
+fn as_object<'a>(schema: &'a Value, path: &str) -> Result<&'a ValueMap, String> {
+    match schema {
+        Value::Record(schema) => Ok(schema),
+        _ => Err(format!(
+            "The schema of {} must be an object",
+            location(path)
+        )),
+    }
+}
+
+/// Reads a keyword with a string value.
+fn string_keyword<'a>(
+    schema: &'a ValueMap,
+    keyword: &str,
+    path: &str,
+) -> Result<Option<&'a str>, String> {
+    match schema.get(keyword) {
+        None => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(_) => Err(format!(
+            "'{}' of {} must be a string",
+            keyword,
+            location(path)
+        )),
+    }
+}
+
+/// Reads a keyword with a non-negative integer value.
+fn usize_keyword(schema: &ValueMap, keyword: &str, path: &str) -> Result<Option<usize>, String> {
+    match schema.get(keyword) {
+        None => Ok(None),
+        Some(Value::Integer(value)) if *value >= 0 => Ok(Some(*value as usize)),
+        Some(_) => Err(format!(
+            "'{}' of {} must be a non-negative integer",
+            keyword,
+            location(path)
+        )),
+    }
+}
+
+/// Names the location of a schema in error messages.
+fn location(path: &str) -> String {
+    if path.is_empty() {
+        "the root".to_string()
+    } else {
+        format!("'{}'", path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optional::Optional;
+    use crate::validation::Patterns;
+
+    /// Builds a form with every kind of field for testing.
+    fn signup_form() -> Form {
+        let patterns = Patterns::new()
+            .register("order_id", r"^ORD-\d{6}$")
+            .unwrap();
+        FormBuilder::new()
+            .add_field::<String>(
+                "email",
+                "Email:",
+                Some(Validator::named(NamedRule::NotEmpty, None).builtin(NamedRule::Email, None)),
+            )
+            .add_field::<u32>("age", "Age:", None)
+            .add_field::<Optional<f64>>("height", "Height:", None)
+            .add_field::<char>("initial", "Initial:", None)
+            .add_field::<String>(
+                "order",
+                "Order:",
+                Some(
+                    Validator::named(NamedRule::Slug, None)
+                        .pattern(&patterns, "order_id", None)
+                        .unwrap(),
+                ),
+            )
+            .add_field::<String>("password", "Password:", None)
+            .add_select("plan", "Plan:", vec![(1u8, "Free"), (2u8, "Pro")])
+            .add_multiselect("tags", "Tags:", vec![("a", "A"), ("b", "B")], Some(1))
+            .add_section(
+                "address",
+                "Address",
+                Some("Where we send letters"),
+                FormBuilder::new()
+                    .add_field::<String>("city", "City:", None)
+                    .build(),
+            )
+            .add_repeat(
+                "phones",
+                "Phones",
+                || {
+                    FormBuilder::new()
+                        .add_field::<bool>("mobile", "Mobile:", None)
+                        .build()
+                },
+                0,
+                Some(3),
+            )
+            .describe_option("plan", 1u8, "No cost")
+            .help("age", "In years")
+            .placeholder("email", "jane@example.com")
+            .secret("password")
+            .build()
+    }
+
+    #[test]
+    fn test_write_schema() {
+        let schema = json::parse(&write(&signup_form())).unwrap();
+        let Value::Record(schema) = schema else {
+            panic!("the schema is not an object");
+        };
+        assert_eq!(
+            schema.get("required"),
+            Some(&json::parse(r#"["email","age","initial","order","password","plan","tags","address","phones"]"#).unwrap())
+        );
+        let Some(Value::Record(properties)) = schema.get("properties") else {
+            panic!("the schema has no properties");
+        };
+        let property = |name: &str| json::write(properties.get(name).unwrap());
+        assert_eq!(
+            property("email"),
+            r#"{"type":"string","title":"Email:","minLength":1,"format":"email","examples":["jane@example.com"]}"#
+        );
+        assert_eq!(
+            property("age"),
+            r#"{"type":"integer","title":"Age:","description":"In years"}"#
+        );
+        assert_eq!(
+            property("height"),
+            r#"{"type":["number","null"],"title":"Height:"}"#
+        );
+        assert_eq!(
+            property("initial"),
+            r#"{"type":"string","title":"Initial:","minLength":1,"maxLength":1}"#
+        );
+        assert_eq!(
+            property("order"),
+            r#"{"type":"string","title":"Order:","pattern":"^[a-z0-9]+(-[a-z0-9]+)*$","allOf":[{"pattern":"^ORD-\\d{6}$"}]}"#
+        );
+        assert_eq!(
+            property("password"),
+            r#"{"type":"string","title":"Password:","writeOnly":true}"#
+        );
+        assert_eq!(
+            property("plan"),
+            r#"{"type":"integer","title":"Plan:","oneOf":[{"const":1,"title":"Free","description":"No cost"},{"const":2,"title":"Pro"}]}"#
+        );
+        assert_eq!(
+            property("tags"),
+            r#"{"type":"array","title":"Tags:","items":{"type":"string","oneOf":[{"const":"a","title":"A"},{"const":"b","title":"B"}]},"uniqueItems":true,"minItems":1,"maxItems":1}"#
+        );
+        assert_eq!(
+            property("address"),
+            r#"{"type":"object","title":"Address","description":"Where we send letters","properties":{"city":{"type":"string","title":"City:"}},"required":["city"],"additionalProperties":false}"#
+        );
+        assert_eq!(
+            property("phones"),
+            r#"{"type":"array","title":"Phones","items":{"type":"object","properties":{"mobile":{"type":"boolean","title":"Mobile:"}},"required":["mobile"],"additionalProperties":false},"maxItems":3}"#
+        );
+    }
+
+    #[test]
+    fn test_read_schema_round_trip() {
+        let schema = write(&signup_form());
+        let form = read(&schema).unwrap().build();
+        assert_eq!(write(&form), schema);
+
+        let field = form.find_field("initial").unwrap();
+        assert_eq!(field.kind(), FieldKind::Input(ValueKind::Char));
+        assert!(form.find_field("height").unwrap().is_optional());
+        assert!(form.find_field("password").unwrap().is_secret());
+        assert_eq!(form.find_field("tags").unwrap().selection_limit(), Some(1));
+
+        let form = read(
+            r#"{"properties":{"size":{"enum":["S","M"]},"colors":{"type":"array","items":{"oneOf":[{"const":1},{"const":2,"title":"Blue"}]}}}}"#,
+        )
+        .unwrap()
+        .build();
+        assert_eq!(
+            form.find_field("size").unwrap().options(),
+            vec![
+                (Value::String("S".to_string()), "S".to_string()),
+                (Value::String("M".to_string()), "M".to_string())
+            ]
+        );
+        assert_eq!(
+            form.find_field("colors").unwrap().options(),
+            vec![
+                (Value::Integer(1), "1".to_string()),
+                (Value::Integer(2), "Blue".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_pattern_is_local() {
+        let form = read(r#"{"properties":{"code":{"type":"string","pattern":"^[A-Z]{2}$"}}}"#)
+            .unwrap()
+            .build();
+        let validator = form.find_field("code").unwrap().validator().unwrap();
+        assert!(validator.validate("AB").is_ok());
+        assert!(validator.validate("abc").is_err());
+    }
+
+    #[test]
+    fn test_read_schema_errors() {
+        let read_error = |schema: &str| read(schema).err().unwrap();
+        assert_eq!(
+            read_error(r#"{"properties":{"age":{"type":"integer","minimum":0}}}"#),
+            "Unsupported JSON Schema keyword 'minimum' at 'age'"
+        );
+        assert_eq!(
+            read_error(
+                r#"{"properties":{"home":{"type":"object","properties":{"zip":{"type":"string","format":"zip"}}}}}"#
+            ),
+            "Unsupported format 'zip' of 'home.zip'"
+        );
+        assert_eq!(
+            read_error(r#"{"properties":{"id":{"type":"string","minLength":3}}}"#),
+            "Unsupported 'minLength' of 'id'; only non-empty input is required"
+        );
+        assert_eq!(
+            read_error(r#"{"properties":{"size":{"enum":[1,"M"]}}}"#),
+            "Options of 'size' must all have the same type"
+        );
+        assert_eq!(
+            read_error(r#"{"properties":{"size":{"oneOf":[{"title":"S"}]}}}"#),
+            "Every option of 'size' needs a 'const'"
+        );
+        assert_eq!(
+            read_error(r#"{"properties":{"code":{"type":"string","pattern":"("}}}"#)
+                .split(':')
+                .next(),
+            Some("Invalid 'pattern' of 'code'")
+        );
+        assert_eq!(
+            read_error(
+                r#"{"properties":{"tags":{"type":"array","minItems":0,"items":{"enum":["a"]}}}}"#
+            ),
+            "Unsupported 'minItems' of 'tags'; multiselect fields need exactly one selection at least"
+        );
+        assert_eq!(
+            read_error(r#"{"type":"object","$defs":{}}"#),
+            "Unsupported JSON Schema keyword '$defs' at the root"
+        );
+        assert_eq!(
+            read_error(r#"{"properties":{"x":{"type":"null"}}}"#),
+            "Unsupported type 'null' of 'x'"
+        );
+    }
+}
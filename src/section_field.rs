@@ -0,0 +1,105 @@
+use crate::field::{FieldTrait, FillContext, FillError};
+use crate::form::Form;
+use crate::input::print_text;
+use crate::value::{FieldKind, Value, ValueMap};
+
+/// A struct representing a named section of a form with its own heading.
+///
+/// The section wraps a complete sub-form, whose values are addressed with
+/// dotted paths such as `address.city`.
+pub struct SectionField {
+    /// The heading printed before the fields of the section.
+    pub heading: String,
+    /// An optional description printed below the heading.
+    pub description: Option<String>,
+    /// The sub-form containing the fields of the section.
+    pub form: Form,
+}
+
+impl FieldTrait for SectionField {
+    /// Fills the section by printing its heading and filling the sub-form.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all fields of the section are successfully filled.
+    /// * `Err(String)` if there is an error filling any field.
+    fn fill(&mut self) -> Result<(), String> {
+        Ok(self.fill_with_context(&FillContext::default())?)
+    }
+
+    /// Fills the section, passing the answers of the enclosing form to the sub-form.
+    fn fill_with_context(&mut self, context: &FillContext) -> Result<(), FillError> {
+        let mut heading = format!("\n== {} ==\n", self.heading);
+        if let Some(description) = &self.description {
+            heading.push_str(&format!("{}\n", description));
+        }
+        print_text(&heading, context.output)?;
+        self.form.fill_with_context(context)
+    }
+
+    /// Returns a reference to the field as a `dyn Any`.
+    ///
+    /// # Returns
+    ///
+    /// * A reference to the field as a `dyn Any`.
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// Returns a mutable reference to the field as a `dyn Any`.
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+
+    /// Gets the answers of the filled fields of the section as a record.
+    fn draft_value(&self) -> Value {
+        Value::Record(self.form.draft_answers())
+    }
+
+    /// Restores the fields of the section from a record.
+    fn restore(&mut self, value: &Value, context: &FillContext) -> Result<(), String> {
+        let Value::Record(answers) = value else {
+            return Err(format!(
+                "Invalid draft answer for section '{}'",
+                context.field
+            ));
+        };
+        self.form.restore_answers(answers, context)
+    }
+
+    /// Fills the fields of the section from a record of answers.
+    fn answer(&mut self, answer: &Value, context: &FillContext) -> Result<(), FillError> {
+        match answer {
+            Value::Record(answers) => self.form.fill_from_answers_with_context(answers, context),
+            Value::Null => self
+                .form
+                .fill_from_answers_with_context(&ValueMap::new(), context),
+            _ => Err(context.reject("The answer must be an object".to_string())),
+        }
+    }
+
+    /// Gets the values of the section as a string.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` with the fields formatted as a list of `name: value` pairs.
+    /// * `Err(String)` if any field of the section has no value.
+    fn get_value(&self) -> Result<String, String> {
+        self.form.format_values()
+    }
+
+    /// Returns the heading of the section.
+    fn prompt(&self) -> &str {
+        &self.heading
+    }
+
+    /// Returns the kind of the field.
+    fn kind(&self) -> FieldKind {
+        FieldKind::Section
+    }
+
+    /// Gets the value of the field as a dynamically typed `Value`.
+    fn value(&self) -> Value {
+        Value::Record(self.form.values())
+    }
+}
@@ -1,10 +1,11 @@
-use crate::field::FieldTrait;
-use crate::input::read_select;
-use std::fmt::Debug;
-use std::str::FromStr;
+use crate::field::{FieldDetails, FieldTrait, FillContext, FillError};
+use crate::input::{ErrorCallback, PromptDetails, read_select_with_details};
+use crate::policy::FieldPolicy;
+use crate::provider::OptionProvider;
+use crate::value::{FieldKind, Value};
+use std::fmt::{self, Debug};
 
 /// A struct representing a select field in a form.
-#[derive(Debug)]
 pub struct SelectField<T> {
     /// The prompt to display to the user.
     pub prompt: String,
@@ -12,12 +13,34 @@ pub struct SelectField<T> {
     pub options: Vec<(T, String)>,
     /// The selected value.
     pub value: Option<T>,
+    /// The help text and option descriptions of the field.
+    pub details: FieldDetails,
+    /// An optional provider replacing the options each time the field is filled.
+    pub provider: Option<Box<dyn OptionProvider<T>>>,
+    /// The retry policy and default answer of the field.
+    pub policy: FieldPolicy,
+}
+
+impl<T> Debug for SelectField<T>
+where
+    T: Debug,
+{
+    /// Formats the field, showing whether it has an option provider.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectField")
+            .field("prompt", &self.prompt)
+            .field("options", &self.options)
+            .field("value", &self.value)
+            .field("details", &self.details)
+            .field("provider", &self.provider.is_some())
+            .field("policy", &self.policy)
+            .finish()
+    }
 }
 
 impl<T> FieldTrait for SelectField<T>
 where
-    T: 'static + Clone + PartialEq + Debug + FromStr,
-    T::Err: Debug,
+    T: 'static + Clone + PartialEq + Debug,
 {
     /// Fills the select field by prompting the user for input.
     ///
@@ -26,9 +49,34 @@ where
     /// * `Ok(())` if the field is successfully filled.
     /// * `Err(String)` if there is an error filling the field.
     fn fill(&mut self) -> Result<(), String> {
-        // Use the read_select function to prompt the user for input
-        self.value = Some(read_select::<T>(&self.prompt, &self.options)?);
-        Ok(())
+        Ok(self.fill_with_context(&FillContext::default())?)
+    }
+
+    /// Loads the options from the provider, if any, and fills the field.
+    fn fill_with_context(&mut self, context: &FillContext) -> Result<(), FillError> {
+        if let Some(provider) = &self.provider {
+            self.options = provider.options(&context.answers)?;
+        }
+        let report = |message: &str| context.report_error(&context.field, message);
+        let details = PromptDetails {
+            on_error: Some(ErrorCallback(&report)),
+            ..self
+                .policy
+                .prompt_details(context, self.details.prompt_details(&[], &[]))
+        };
+        match read_select_with_details::<T>(&self.prompt, &self.options, &details) {
+            Ok(value) => {
+                self.value = Some(value);
+                Ok(())
+            }
+            Err(error) => match self.policy.recover(&context.field, context, error)? {
+                Some(default) => Ok(self.restore(&default, context)?),
+                None => {
+                    self.value = None;
+                    Ok(())
+                }
+            },
+        }
     }
 
     /// Returns a reference to the field as a `dyn Any`.
@@ -40,6 +88,62 @@ where
         self
     }
 
+    /// Returns a mutable reference to the field as a `dyn Any`.
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+
+    /// Returns the help text and option descriptions of the field.
+    fn details(&self) -> Option<&FieldDetails> {
+        Some(&self.details)
+    }
+
+    /// Returns the help text and option descriptions of the field for editing.
+    fn details_mut(&mut self) -> Option<&mut FieldDetails> {
+        Some(&mut self.details)
+    }
+
+    /// Returns the retry policy and default answer of the field for editing.
+    fn policy_mut(&mut self) -> Option<&mut FieldPolicy> {
+        Some(&mut self.policy)
+    }
+
+    /// Gets the selected value.
+    fn draft_value(&self) -> Value {
+        self.value()
+    }
+
+    /// Restores the selection by finding the option with the saved value.
+    fn restore(&mut self, value: &Value, context: &FillContext) -> Result<(), String> {
+        if let Some(provider) = &self.provider {
+            self.options = provider.options(&context.answers)?;
+        }
+        let (option, _) = self
+            .options
+            .iter()
+            .find(|(option, _)| Value::of(option) == *value)
+            .ok_or_else(|| format!("'{}' is not an option of field '{}'", value, context.field))?;
+        self.value = Some(option.clone());
+        Ok(())
+    }
+
+    /// Fills the selection from the value of an option.
+    fn answer(&mut self, answer: &Value, context: &FillContext) -> Result<(), FillError> {
+        if answer.is_null() {
+            return Err(context.reject("Missing answer".to_string()));
+        }
+        if let Some(provider) = &self.provider {
+            self.options = provider.options(&context.answers)?;
+        }
+        let (option, _) = self
+            .options
+            .iter()
+            .find(|(option, _)| Value::of(option) == *answer)
+            .ok_or_else(|| context.reject(format!("'{}' is not an option", answer)))?;
+        self.value = Some(option.clone());
+        Ok(())
+    }
+
     /// Gets the value of the select field as a string.
     ///
     /// # Returns
@@ -53,4 +157,45 @@ where
             .ok_or_else(|| "Field has no value".to_string())
             .map(|v| format!("{:?}", v))
     }
+
+    /// Returns the prompt of the field.
+    fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    /// Returns the kind of the field.
+    fn kind(&self) -> FieldKind {
+        FieldKind::Select
+    }
+
+    /// Returns the options of the field with their values.
+    fn options(&self) -> Vec<(Value, String)> {
+        self.options
+            .iter()
+            .map(|(key, label)| (Value::of(key), label.clone()))
+            .collect()
+    }
+
+    /// Gets the value of the field as a dynamically typed `Value`.
+    fn value(&self) -> Value {
+        self.value.as_ref().map_or(Value::Null, Value::of)
+    }
+}
+
+impl<T> SelectField<T>
+where
+    T: Clone,
+{
+    /// Gets the selected value.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` if the field value is successfully retrieved.
+    /// * `Err(String)` if the field has no value.
+    pub fn get_value(&self) -> Result<T, String> {
+        self.value
+            .as_ref()
+            .ok_or_else(|| "Field has no value".to_string())
+            .cloned()
+    }
 }
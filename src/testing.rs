@@ -0,0 +1,442 @@
+use crate::field::FillError;
+use crate::form::Form;
+use crate::input::{Entry, InputError, Key};
+use crate::render::PlainRenderer;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// A key pressed or character typed in a script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keystroke {
+    /// A typed character. In select lists, `?` asks for help and a space toggles an option.
+    Char(char),
+    /// The enter key, ending a line or confirming a selection.
+    Enter,
+    /// The up arrow key.
+    Up,
+    /// The down arrow key.
+    Down,
+    /// The space key.
+    Space,
+    /// The F1 key, asking for help.
+    Help,
+}
+
+/// The script being run on the current thread and the frames drawn so far.
+struct Session {
+    /// The keystrokes not yet read.
+    keys: VecDeque<Keystroke>,
+    /// The output written by the prompts.
+    frames: Vec<String>,
+}
+
+thread_local! {
+    /// The session of the script being run on the current thread, if any.
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+/// Ends the session of the current thread when a script stops running, even by panicking.
+struct SessionGuard;
+
+impl Drop for SessionGuard {
+    /// Removes the session.
+    fn drop(&mut self) {
+        SESSION.with(|session| session.borrow_mut().take());
+    }
+}
+
+/// Checks whether a script is running on the current thread.
+pub(crate) fn is_active() -> bool {
+    SESSION.with(|session| session.borrow().is_some())
+}
+
+/// Records the output of a prompt, if a script is running.
+///
+/// # Returns
+///
+/// * `true` if the output was recorded, `false` if it should be written to stdout.
+pub(crate) fn record(text: &str) -> bool {
+    SESSION.with(|session| match session.borrow_mut().as_mut() {
+        Some(session) => {
+            if !text.is_empty() {
+                session.frames.push(text.to_string());
+            }
+            true
+        }
+        None => false,
+    })
+}
+
+/// Reads the next key press of the script.
+///
+/// # Returns
+///
+/// * `Ok(Key)` with the key press.
+/// * `Err(InputError::Eof)` if the script has ended.
+pub(crate) fn read_key() -> Result<Key, InputError> {
+    let key = SESSION.with(|session| {
+        session
+            .borrow_mut()
+            .as_mut()
+            .and_then(|session| session.keys.pop_front())
+    });
+    match key.ok_or(InputError::Eof)? {
+        Keystroke::Up => Ok(Key::Up),
+        Keystroke::Down => Ok(Key::Down),
+        Keystroke::Enter => Ok(Key::Enter),
+        Keystroke::Space | Keystroke::Char(' ') => Ok(Key::Space),
+        Keystroke::Help | Keystroke::Char('?') => Ok(Key::Help),
+        Keystroke::Char(_) => Ok(Key::Other),
+    }
+}
+
+/// Reads the characters typed in the script up to the next Enter, if a script is running.
+///
+/// Arrow keys are ignored, like in a line read from a terminal.
+///
+/// # Returns
+///
+/// * `None` if no script is running.
+/// * `Some(Ok(Entry))` with the line typed, or `Entry::Help` if F1 was pressed.
+/// * `Some(Err(InputError::Eof))` if the script ended before anything was typed.
+pub(crate) fn read_line() -> Option<Result<Entry, InputError>> {
+    SESSION.with(|session| {
+        let mut session = session.borrow_mut();
+        let session = session.as_mut()?;
+        let mut line = String::new();
+        loop {
+            match session.keys.pop_front() {
+                None if line.is_empty() => return Some(Err(InputError::Eof)),
+                None | Some(Keystroke::Enter) => return Some(Ok(Entry::Line(line))),
+                Some(Keystroke::Char(c)) => line.push(c),
+                Some(Keystroke::Space) => line.push(' '),
+                Some(Keystroke::Help) => return Some(Ok(Entry::Help)),
+                Some(Keystroke::Up | Keystroke::Down) => {}
+            }
+        }
+    })
+}
+
+/// A script of keystrokes fed to prompts instead of the terminal.
+///
+/// While a script runs, select and multiselect fields are navigated with the
+/// scripted arrow keys, text fields read the scripted characters up to each
+/// Enter, and everything the prompts draw is captured as frames.
+///
+/// # Example
+///
+/// ```rust
+/// use form_builder::FormBuilder;
+/// use form_builder::testing::Script;
+///
+/// let mut form = FormBuilder::new()
+///     .add_field::<String>("name", "Enter name:", None)
+///     .add_select("plan", "Select plan", vec![(1u8, "Free"), (2u8, "Pro")])
+///     .build();
+/// let (result, transcript) = Script::new()
+///     .type_text("John")
+///     .enter()
+///     .down()
+///     .enter()
+///     .fill(&mut form);
+///
+/// assert_eq!(result, Ok(()));
+/// assert_eq!(form.get_value::<u8>("plan"), Ok(2));
+/// assert_eq!(transcript.frames[0], "Enter name: ");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Script {
+    /// The keystrokes in the order they are read.
+    keys: Vec<Keystroke>,
+}
+
+impl Script {
+    /// Creates an empty script.
+    ///
+    /// # Returns
+    ///
+    /// * A new `Script` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a keystroke.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The keystroke.
+    ///
+    /// # Returns
+    ///
+    /// * The `Script` instance with the keystroke added.
+    pub fn key(mut self, key: Keystroke) -> Self {
+        self.keys.push(key);
+        self
+    }
+
+    /// Adds the characters of a text, without a final Enter.
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text to be typed.
+    ///
+    /// # Returns
+    ///
+    /// * The `Script` instance with the characters added.
+    pub fn type_text(mut self, text: &str) -> Self {
+        self.keys.extend(text.chars().map(Keystroke::Char));
+        self
+    }
+
+    /// Adds the characters of a line followed by Enter.
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The line to be typed.
+    ///
+    /// # Returns
+    ///
+    /// * The `Script` instance with the line added.
+    pub fn line(self, text: &str) -> Self {
+        self.type_text(text).enter()
+    }
+
+    /// Adds the enter key.
+    pub fn enter(self) -> Self {
+        self.key(Keystroke::Enter)
+    }
+
+    /// Adds the up arrow key.
+    pub fn up(self) -> Self {
+        self.key(Keystroke::Up)
+    }
+
+    /// Adds the down arrow key.
+    pub fn down(self) -> Self {
+        self.key(Keystroke::Down)
+    }
+
+    /// Adds the space key.
+    pub fn space(self) -> Self {
+        self.key(Keystroke::Space)
+    }
+
+    /// Adds the F1 key, asking for help.
+    pub fn help(self) -> Self {
+        self.key(Keystroke::Help)
+    }
+
+    /// Runs a function reading its input from the script.
+    ///
+    /// # Parameters
+    ///
+    /// * `run` - The function prompting the user, such as a call of `read_input`.
+    ///
+    /// # Returns
+    ///
+    /// * The result of the function and the `Transcript` of the script.
+    ///
+    /// # Panics
+    ///
+    /// * If another script is already running on the current thread.
+    pub fn run<R>(self, run: impl FnOnce() -> R) -> (R, Transcript) {
+        assert!(!is_active(), "A script is already running on this thread");
+        SESSION.with(|session| {
+            *session.borrow_mut() = Some(Session {
+                keys: self.keys.into(),
+                frames: Vec::new(),
+            })
+        });
+        let guard = SessionGuard;
+        let result = run();
+        let session = SESSION.with(|session| session.borrow_mut().take()).unwrap();
+        drop(guard);
+        (
+            result,
+            Transcript {
+                frames: session.frames,
+                unused: session.keys.into(),
+            },
+        )
+    }
+
+    /// Fills a form from the script.
+    ///
+    /// Forms without a renderer are drawn with `PlainRenderer`, so the frames
+    /// contain no escape sequences. The renderer of the form is put back
+    /// afterwards.
+    ///
+    /// # Parameters
+    ///
+    /// * `form` - The form to be filled.
+    ///
+    /// # Returns
+    ///
+    /// * The result of `Form::fill` and the `Transcript` of the script.
+    ///
+    /// # Panics
+    ///
+    /// * If another script is already running on the current thread.
+    pub fn fill(self, form: &mut Form) -> (Result<(), FillError>, Transcript) {
+        let renderer = form.renderer.clone();
+        if renderer.is_none() {
+            form.renderer = Some(Rc::new(PlainRenderer));
+        }
+        let filled = self.run(|| form.fill());
+        form.renderer = renderer;
+        filled
+    }
+}
+
+/// What the prompts drew while a script ran.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    /// The frames drawn, in order.
+    pub frames: Vec<String>,
+    /// The keystrokes the prompts did not read.
+    pub unused: Vec<Keystroke>,
+}
+
+impl Transcript {
+    /// Returns the last frame drawn.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&str)` with the frame, or `None` if nothing was drawn.
+    pub fn last_frame(&self) -> Option<&str> {
+        self.frames.last().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form_builder::FormBuilder;
+    use crate::render::Renderer;
+    use crate::validation::{ValidationMethods, Validator};
+
+    /// Builds a form with a field, a select field and a multiselect field for testing.
+    fn profile_form() -> Form {
+        FormBuilder::new()
+            .add_field::<String>(
+                "name",
+                "Enter name:",
+                Some(Validator::new(vec![(
+                    ValidationMethods::not_empty,
+                    Some("Name cannot be empty"),
+                )])),
+            )
+            .add_select(
+                "plan",
+                "Select plan",
+                vec![(1u8, "Free"), (2u8, "Team"), (3u8, "Pro")],
+            )
+            .add_multiselect(
+                "tags",
+                "Select tags",
+                vec![("a", "A"), ("b", "B"), ("c", "C")],
+                Some(2),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_fill_from_script() {
+        let mut form = profile_form();
+        let (result, transcript) = Script::new()
+            .enter()
+            .line("John")
+            .down()
+            .down()
+            .enter()
+            .space()
+            .down()
+            .down()
+            .space()
+            .enter()
+            .fill(&mut form);
+
+        assert_eq!(result, Ok(()));
+        assert!(form.renderer.is_none());
+        assert_eq!(form.get_value::<String>("name"), Ok("John".to_string()));
+        assert_eq!(form.get_value::<u8>("plan"), Ok(3));
+        assert_eq!(form.get_value_vec::<&str>("tags"), Ok(vec!["a", "c"]));
+        assert!(transcript.unused.is_empty());
+        assert_eq!(
+            &transcript.frames[..2],
+            ["Enter name: ", "Name cannot be empty\nEnter name: "]
+        );
+        assert_eq!(
+            transcript.frames[4],
+            "Select plan:\n  Free\n  Team\n> Pro\n"
+        );
+        assert_eq!(
+            transcript.last_frame(),
+            Some(
+                "Select tags:\nUse Space to select/deselect, Enter to confirm\n  [*] A\n  [ ] B\n> [*] C\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_script_errors_and_end() {
+        let mut form = profile_form();
+        let (result, transcript) = Script::new()
+            .line("Ann")
+            .help()
+            .enter()
+            .enter()
+            .space()
+            .down()
+            .space()
+            .down()
+            .space()
+            .fill(&mut form);
+
+        assert_eq!(
+            result,
+            Err(FillError::Input {
+                field: "tags".to_string(),
+                error: InputError::Eof,
+            })
+        );
+        assert_eq!(form.get_value::<u8>("plan"), Ok(1));
+        assert!(
+            transcript.frames.contains(
+                &"Select tags:\nUse Space to select/deselect, Enter to confirm\n\
+                  > [ ] A\n  [ ] B\n  [ ] C\n\nPlease choose at least one option\n"
+                    .to_string()
+            )
+        );
+        assert!(
+            transcript
+                .last_frame()
+                .unwrap()
+                .ends_with("\nPlease choose at most 2 options\n")
+        );
+    }
+
+    #[test]
+    fn test_fill_keeps_renderer() {
+        let renderer: Rc<dyn Renderer> = Rc::new(PlainRenderer);
+        let mut form = profile_form();
+        form.renderer = Some(renderer.clone());
+        let (result, _) = Script::new().fill(&mut form);
+        assert!(result.is_err());
+        assert!(Rc::ptr_eq(form.renderer.as_ref().unwrap(), &renderer));
+    }
+
+    #[test]
+    fn test_run_unused_keys() {
+        let (value, transcript) = Script::new()
+            .line("42")
+            .line("unused")
+            .run(|| crate::input::read_input::<u32>("Enter age:", None));
+
+        assert_eq!(value, Ok(42));
+        assert_eq!(transcript.frames, vec!["Enter age: "]);
+        assert_eq!(transcript.unused, Script::new().line("unused").keys);
+        assert!(!is_active());
+    }
+}
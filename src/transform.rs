@@ -0,0 +1,256 @@
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+/// A function normalizing the raw input of a field before it is validated and parsed.
+///
+/// Transformers are added to fields with `FormBuilder::transform` and run in
+/// the order they were added, after the input has been trimmed.
+#[derive(Clone)]
+pub struct Transformer {
+    /// The name of the transformer, shown in `Debug` output.
+    name: &'static str,
+    /// The function transforming the input.
+    apply: Arc<dyn Fn(&str) -> String + Send + Sync>,
+}
+
+impl Transformer {
+    /// Creates a new transformer from a function.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - A name describing the transformer.
+    /// * `apply` - The function transforming the input.
+    ///
+    /// # Returns
+    ///
+    /// * A new `Transformer` instance.
+    pub fn new<F>(name: &'static str, apply: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        Self {
+            name,
+            apply: Arc::new(apply),
+        }
+    }
+
+    /// Returns the name of the transformer.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Transforms the input.
+    ///
+    /// # Parameters
+    ///
+    /// * `input` - The input to be transformed.
+    ///
+    /// # Returns
+    ///
+    /// * The transformed input.
+    pub fn apply(&self, input: &str) -> String {
+        (self.apply)(input)
+    }
+
+    /// Removes leading and trailing whitespace.
+    pub fn trim() -> Self {
+        Self::new("trim", |input| input.trim().to_string())
+    }
+
+    /// Converts the input to lowercase, for example to normalize email addresses.
+    pub fn lowercase() -> Self {
+        Self::new("lowercase", str::to_lowercase)
+    }
+
+    /// Converts the input to uppercase, for example to normalize postal codes.
+    pub fn uppercase() -> Self {
+        Self::new("uppercase", str::to_uppercase)
+    }
+
+    /// Replaces every run of whitespace with a single space and trims the ends.
+    pub fn collapse_whitespace() -> Self {
+        Self::new("collapse_whitespace", |input| {
+            input.split_whitespace().collect::<Vec<&str>>().join(" ")
+        })
+    }
+
+    /// Removes every occurrence of the given characters.
+    ///
+    /// # Parameters
+    ///
+    /// * `chars` - The characters to be removed, such as `&[' ', '-']` for card numbers.
+    ///
+    /// # Returns
+    ///
+    /// * A new `Transformer` instance.
+    pub fn remove_chars(chars: &[char]) -> Self {
+        let chars = chars.to_vec();
+        Self::new("remove_chars", move |input| {
+            input.chars().filter(|c| !chars.contains(c)).collect()
+        })
+    }
+
+    /// Removes dashes and whitespace, for example from card and phone numbers.
+    pub fn strip_dashes() -> Self {
+        Self::new("strip_dashes", |input| {
+            input
+                .chars()
+                .filter(|c| *c != '-' && !c.is_whitespace())
+                .collect()
+        })
+    }
+
+    /// Keeps only ASCII digits, and a leading `+` for international phone numbers.
+    pub fn digits_only() -> Self {
+        Self::new("digits_only", |input| {
+            let input = input.trim_start();
+            let sign = if input.starts_with('+') { "+" } else { "" };
+            let digits: String = input.chars().filter(char::is_ascii_digit).collect();
+            format!("{}{}", sign, digits)
+        })
+    }
+
+    /// Converts the input to Unicode Normalization Form C.
+    ///
+    /// Requires the `unicode` feature.
+    #[cfg(feature = "unicode")]
+    pub fn nfc() -> Self {
+        use unicode_normalization::UnicodeNormalization;
+        Self::new("nfc", |input| input.nfc().collect())
+    }
+}
+
+impl Debug for Transformer {
+    /// Formats the transformer with its name.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Transformer").field(&self.name).finish()
+    }
+}
+
+/// Applies transformers to the input in order.
+///
+/// # Parameters
+///
+/// * `transformers` - The transformers to be applied.
+/// * `input` - The input to be transformed.
+///
+/// # Returns
+///
+/// * The transformed input.
+pub fn apply_transformers(transformers: &[Transformer], input: &str) -> String {
+    transformers
+        .iter()
+        .fold(input.to_string(), |input, transformer| {
+            transformer.apply(&input)
+        })
+}
+
+/// A function post-processing the parsed value of a field.
+///
+/// Value transformers are added to fields with `FormBuilder::after_parse`.
+pub struct ValueTransformer<T> {
+    /// The function transforming the value.
+    apply: Arc<dyn Fn(T) -> T + Send + Sync>,
+}
+
+impl<T> ValueTransformer<T> {
+    /// Creates a new value transformer from a function.
+    ///
+    /// # Parameters
+    ///
+    /// * `apply` - The function transforming the parsed value.
+    ///
+    /// # Returns
+    ///
+    /// * A new `ValueTransformer` instance.
+    pub fn new<F>(apply: F) -> Self
+    where
+        F: Fn(T) -> T + Send + Sync + 'static,
+    {
+        Self {
+            apply: Arc::new(apply),
+        }
+    }
+
+    /// Transforms the value.
+    pub fn apply(&self, value: T) -> T {
+        (self.apply)(value)
+    }
+}
+
+impl<T> Clone for ValueTransformer<T> {
+    /// Returns a transformer sharing the same function.
+    fn clone(&self) -> Self {
+        Self {
+            apply: Arc::clone(&self.apply),
+        }
+    }
+}
+
+impl<T> Debug for ValueTransformer<T> {
+    /// Formats the transformer without its function.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ValueTransformer")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_transformers() {
+        assert_eq!(
+            Transformer::lowercase().apply("John@Example.COM"),
+            "john@example.com"
+        );
+        assert_eq!(Transformer::uppercase().apply("sw1a 1aa"), "SW1A 1AA");
+        assert_eq!(Transformer::trim().apply("  a b  "), "a b");
+        assert_eq!(
+            Transformer::collapse_whitespace().apply(" New \t  York\n City "),
+            "New York City"
+        );
+        assert_eq!(
+            Transformer::strip_dashes().apply("4111-1111 1111-1111"),
+            "4111111111111111"
+        );
+        assert_eq!(
+            Transformer::remove_chars(&['(', ')']).apply("(030) 123"),
+            "030 123"
+        );
+        assert_eq!(
+            Transformer::digits_only().apply("+1 (555) 010-9999"),
+            "+15550109999"
+        );
+        assert_eq!(Transformer::digits_only().apply("555-0100"), "5550100");
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_nfc() {
+        assert_eq!(Transformer::nfc().apply("Cafe\u{301}"), "Caf\u{e9}");
+    }
+
+    #[test]
+    fn test_apply_transformers() {
+        let transformers = vec![
+            Transformer::collapse_whitespace(),
+            Transformer::lowercase(),
+            Transformer::new("dots_to_dashes", |input| input.replace('.', "-")),
+        ];
+        assert_eq!(
+            apply_transformers(&transformers, "  My.Post   Title "),
+            "my-post title"
+        );
+        assert_eq!(
+            format!("{:?}", transformers[1]),
+            "Transformer(\"lowercase\")"
+        );
+    }
+
+    #[test]
+    fn test_value_transformer() {
+        let round = ValueTransformer::new(|value: f64| (value * 100.0).round() / 100.0);
+        assert_eq!(round.clone().apply(1.23456), 1.23);
+    }
+}
@@ -1,4 +1,55 @@
+use crate::json;
 use regex::Regex;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+
+/// The built-in regular expressions, each compiled once on first use.
+mod compiled {
+    use super::ValidationMethods;
+    use regex::Regex;
+    use std::sync::LazyLock;
+
+    /// Declares a lazily compiled static for each built-in expression.
+    macro_rules! compiled_regexes {
+        ($($name:ident => $pattern:ident),* $(,)?) => {
+            $(
+                pub(super) static $name: LazyLock<Regex> = LazyLock::new(|| {
+                    Regex::new(ValidationMethods::$pattern).expect("built-in patterns are valid")
+                });
+            )*
+        };
+    }
+
+    compiled_regexes!(
+        EMAIL => EMAIL_REGEX,
+        DATE => DATE_REGEX,
+        TIME => TIME_REGEX,
+        URL => URL_REGEX,
+        PHONE => PHONE_REGEX,
+        POSTAL_CODE => POSTAL_CODE_REGEX,
+        CREDIT_CARD => CREDIT_CARD_REGEX,
+        UUID => UUID_REGEX,
+        IBAN => IBAN_REGEX,
+        SEMVER => SEMVER_REGEX,
+        HEX_COLOR => HEX_COLOR_REGEX,
+        SLUG => SLUG_REGEX,
+        BASE64 => BASE64_REGEX,
+        DURATION => DURATION_REGEX,
+        COORDINATE => COORDINATE_REGEX,
+    );
+
+    /// The postal code expressions by ISO 3166-1 alpha-2 country code.
+    pub(super) static POSTAL_CODES: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+        ValidationMethods::POSTAL_CODE_PATTERNS
+            .iter()
+            .map(|(code, pattern)| {
+                let regex = Regex::new(pattern).expect("built-in patterns are valid");
+                (*code, regex)
+            })
+            .collect()
+    });
+}
 
 /// A struct containing various validation methods.
 pub struct ValidationMethods;
@@ -11,9 +62,141 @@ impl ValidationMethods {
     const URL_REGEX: &'static str = r"^(http|https)://[^\s/$.?#].[^\s]*$";
     const PHONE_REGEX: &'static str = r"^\+?[1-9]\d{1,14}$";
     const POSTAL_CODE_REGEX: &'static str = r"^\d{5}(-\d{4})?$";
-    const CREDIT_CARD_REGEX: &'static str = r"^\d{4}-?\d{4}-?\d{4}-?\d{4}$";
+    const CREDIT_CARD_REGEX: &'static str = r"^\d+([ -]\d+)*$";
     const UUID_REGEX: &'static str =
         r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$";
+    const SEMVER_REGEX: &'static str = r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(-((0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(\.(0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?(\+[0-9a-zA-Z-]+(\.[0-9a-zA-Z-]+)*)?$";
+    const HEX_COLOR_REGEX: &'static str =
+        r"^#([0-9a-fA-F]{3}|[0-9a-fA-F]{4}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})$";
+    const SLUG_REGEX: &'static str = r"^[a-z0-9]+(-[a-z0-9]+)*$";
+    const BASE64_REGEX: &'static str =
+        r"^([A-Za-z0-9+/]{4})*([A-Za-z0-9+/]{2}==|[A-Za-z0-9+/]{3}=)?$";
+    const DURATION_REGEX: &'static str =
+        r"^P(\d+Y)?(\d+M)?(\d+W)?(\d+D)?(T(\d+H)?(\d+M)?(\d+([.,]\d+)?S)?)?$";
+    const COORDINATE_REGEX: &'static str = r"^[+-]?\d{1,3}(\.\d+)?$";
+    const IBAN_REGEX: &'static str = r"^[A-Z]{2}\d{2}[A-Z0-9]{11,30}$";
+
+    /// IBAN lengths by ISO 3166-1 alpha-2 country code.
+    const IBAN_LENGTHS: &'static [(&'static str, usize)] = &[
+        ("AD", 24),
+        ("AE", 23),
+        ("AL", 28),
+        ("AT", 20),
+        ("AZ", 28),
+        ("BA", 20),
+        ("BE", 16),
+        ("BG", 22),
+        ("BH", 22),
+        ("BR", 29),
+        ("BY", 28),
+        ("CH", 21),
+        ("CR", 22),
+        ("CY", 28),
+        ("CZ", 24),
+        ("DE", 22),
+        ("DK", 18),
+        ("DO", 28),
+        ("EE", 20),
+        ("EG", 29),
+        ("ES", 24),
+        ("FI", 18),
+        ("FO", 18),
+        ("FR", 27),
+        ("GB", 22),
+        ("GE", 22),
+        ("GI", 23),
+        ("GL", 18),
+        ("GR", 27),
+        ("GT", 28),
+        ("HR", 21),
+        ("HU", 28),
+        ("IE", 22),
+        ("IL", 23),
+        ("IQ", 23),
+        ("IS", 26),
+        ("IT", 27),
+        ("JO", 30),
+        ("KW", 30),
+        ("KZ", 20),
+        ("LB", 28),
+        ("LC", 32),
+        ("LI", 21),
+        ("LT", 20),
+        ("LU", 20),
+        ("LV", 21),
+        ("MC", 27),
+        ("MD", 24),
+        ("ME", 22),
+        ("MK", 19),
+        ("MR", 27),
+        ("MT", 31),
+        ("MU", 30),
+        ("NL", 18),
+        ("NO", 15),
+        ("PK", 24),
+        ("PL", 28),
+        ("PS", 29),
+        ("PT", 25),
+        ("QA", 29),
+        ("RO", 24),
+        ("RS", 22),
+        ("SA", 24),
+        ("SC", 31),
+        ("SE", 24),
+        ("SI", 19),
+        ("SK", 24),
+        ("SM", 27),
+        ("TN", 24),
+        ("TR", 26),
+        ("UA", 29),
+        ("VA", 22),
+        ("VG", 24),
+        ("XK", 20),
+    ];
+
+    /// Postal code patterns by ISO 3166-1 alpha-2 country code.
+    const POSTAL_CODE_PATTERNS: &'static [(&'static str, &'static str)] = &[
+        ("US", r"^\d{5}(-\d{4})?$"),
+        (
+            "CA",
+            r"^[ABCEGHJ-NPRSTVXY]\d[ABCEGHJ-NPRSTV-Z] ?\d[ABCEGHJ-NPRSTV-Z]\d$",
+        ),
+        ("GB", r"^([A-Z]{1,2}\d[A-Z\d]? ?\d[A-Z]{2}|GIR ?0AA)$"),
+        ("IE", r"^([AC-FHKNPRTV-Y]\d{2}|D6W) ?[0-9AC-FHKNPRTV-Y]{4}$"),
+        ("DE", r"^\d{5}$"),
+        ("FR", r"^\d{2} ?\d{3}$"),
+        ("IT", r"^\d{5}$"),
+        ("ES", r"^(0[1-9]|[1-4]\d|5[0-2])\d{3}$"),
+        ("PT", r"^\d{4}-\d{3}$"),
+        ("NL", r"^[1-9]\d{3} ?[A-Z]{2}$"),
+        ("BE", r"^[1-9]\d{3}$"),
+        ("LU", r"^(L-)?\d{4}$"),
+        ("CH", r"^[1-9]\d{3}$"),
+        ("AT", r"^[1-9]\d{3}$"),
+        ("DK", r"^[1-9]\d{3}$"),
+        ("NO", r"^\d{4}$"),
+        ("SE", r"^[1-9]\d{2} ?\d{2}$"),
+        ("FI", r"^\d{5}$"),
+        ("PL", r"^\d{2}-\d{3}$"),
+        ("CZ", r"^[1-7]\d{2} ?\d{2}$"),
+        ("SK", r"^[089]\d{2} ?\d{2}$"),
+        ("HU", r"^[1-9]\d{3}$"),
+        ("RO", r"^\d{6}$"),
+        ("GR", r"^\d{3} ?\d{2}$"),
+        ("RU", r"^\d{6}$"),
+        ("UA", r"^\d{5}$"),
+        ("TR", r"^(0[1-9]|[1-7]\d|8[01])\d{3}$"),
+        ("IN", r"^[1-9]\d{2} ?\d{3}$"),
+        ("CN", r"^\d{6}$"),
+        ("JP", r"^\d{3}-?\d{4}$"),
+        ("KR", r"^\d{5}$"),
+        ("AU", r"^\d{4}$"),
+        ("NZ", r"^\d{4}$"),
+        ("BR", r"^\d{5}-?\d{3}$"),
+        ("MX", r"^\d{5}$"),
+        ("AR", r"^([A-HJ-NP-Z]\d{4}[A-Z]{3}|\d{4})$"),
+        ("ZA", r"^\d{4}$"),
+    ];
 
     /// Validates that the name does not contain any numeric characters.
     ///
@@ -38,7 +221,7 @@ impl ValidationMethods {
     ///
     /// * `true` if the email is in a valid format, `false` otherwise.
     pub fn validate_email(email: &str) -> bool {
-        let email_regex = Regex::new(Self::EMAIL_REGEX).unwrap();
+        let email_regex = &*compiled::EMAIL;
         email_regex.is_match(email)
     }
 
@@ -122,7 +305,10 @@ impl ValidationMethods {
         value.parse::<f64>().is_ok()
     }
 
-    /// Validates that the value is in a date format (YYYY-MM-DD).
+    /// Validates that the value is a calendar date in the format YYYY-MM-DD.
+    ///
+    /// Days are checked against the length of the month, so `2023-02-29`
+    /// is rejected while `2024-02-29` is accepted.
     ///
     /// # Arguments
     ///
@@ -130,10 +316,53 @@ impl ValidationMethods {
     ///
     /// # Returns
     ///
-    /// * `true` if the value is in a date format, `false` otherwise.
+    /// * `true` if the value is an existing date, `false` otherwise.
     pub fn is_date(value: &str) -> bool {
-        let date_regex = Regex::new(Self::DATE_REGEX).unwrap();
-        date_regex.is_match(value)
+        let date_regex = &*compiled::DATE;
+        if !date_regex.is_match(value) {
+            return false;
+        }
+        let parts: Vec<u32> = value
+            .split('-')
+            .filter_map(|part| part.parse().ok())
+            .collect();
+        match parts[..] {
+            [year, month, day] => day <= Self::days_in_month(year, month),
+            _ => false,
+        }
+    }
+
+    /// Checks whether a year is a leap year in the Gregorian calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year to be checked.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the year is a leap year, `false` otherwise.
+    pub fn is_leap_year(year: u32) -> bool {
+        (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+    }
+
+    /// Returns the number of days of a month.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year, used to determine the length of February.
+    /// * `month` - The month, from 1 to 12.
+    ///
+    /// # Returns
+    ///
+    /// * The number of days of the month, or `0` if the month is out of range.
+    pub fn days_in_month(year: u32, month: u32) -> u32 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if Self::is_leap_year(year) => 29,
+            2 => 28,
+            _ => 0,
+        }
     }
 
     /// Validates that the value is in a time format (HH:MM:SS).
@@ -146,7 +375,7 @@ impl ValidationMethods {
     ///
     /// * `true` if the value is in a time format, `false` otherwise.
     pub fn is_time(value: &str) -> bool {
-        let time_regex = Regex::new(Self::TIME_REGEX).unwrap();
+        let time_regex = &*compiled::TIME;
         time_regex.is_match(value)
     }
 
@@ -160,7 +389,7 @@ impl ValidationMethods {
     ///
     /// * `true` if the value is in a URL format, `false` otherwise.
     pub fn is_url(value: &str) -> bool {
-        let url_regex = Regex::new(Self::URL_REGEX).unwrap();
+        let url_regex = &*compiled::URL;
         url_regex.is_match(value)
     }
 
@@ -174,11 +403,13 @@ impl ValidationMethods {
     ///
     /// * `true` if the value is in a phone number format, `false` otherwise.
     pub fn is_phone_number(value: &str) -> bool {
-        let phone_regex = Regex::new(Self::PHONE_REGEX).unwrap();
+        let phone_regex = &*compiled::PHONE;
         phone_regex.is_match(value)
     }
 
-    /// Validates that the value is in a postal code format (e.g., 12345 or 12345-6789).
+    /// Validates that the value is in a US postal code format (e.g., 12345 or 12345-6789).
+    ///
+    /// Use `is_postal_code_for` to validate postal codes of other countries.
     ///
     /// # Arguments
     ///
@@ -188,25 +419,44 @@ impl ValidationMethods {
     ///
     /// * `true` if the value is in a postal code format, `false` otherwise.
     pub fn is_postal_code(value: &str) -> bool {
-        let postal_code_regex = Regex::new(Self::POSTAL_CODE_REGEX).unwrap();
+        let postal_code_regex = &*compiled::POSTAL_CODE;
         postal_code_regex.is_match(value)
     }
 
-    /// Validates that the value is in a credit card number format.
+    /// Validates that the value is a postal code of the given country.
+    ///
+    /// The check can be used in a `Validator` through a non-capturing closure,
+    /// such as `|value| ValidationMethods::is_postal_code_for(value, "DE")`.
     ///
     /// # Arguments
     ///
     /// * `value` - A string slice that holds the value to be validated.
+    /// * `country` - The ISO 3166-1 alpha-2 code of the country, such as `"GB"`.
     ///
     /// # Returns
     ///
-    /// * `true` if the value is in a credit card number format, `false` otherwise.
-    pub fn is_credit_card(value: &str) -> bool {
-        let credit_card_regex = Regex::new(Self::CREDIT_CARD_REGEX).unwrap();
-        credit_card_regex.is_match(value)
+    /// * `true` if the value is a postal code of the country, `false` otherwise,
+    ///   including for countries without known postal code rules.
+    pub fn is_postal_code_for(value: &str, country: &str) -> bool {
+        compiled::POSTAL_CODES
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(country))
+            .is_some_and(|(_, regex)| regex.is_match(&value.to_ascii_uppercase()))
     }
 
-    /// Validates that the value is in a UUID format.
+    /// Returns the country codes supported by `is_postal_code_for`.
+    ///
+    /// # Returns
+    ///
+    /// * An iterator of ISO 3166-1 alpha-2 country codes.
+    pub fn postal_code_countries() -> impl Iterator<Item = &'static str> {
+        Self::POSTAL_CODE_PATTERNS.iter().map(|(code, _)| *code)
+    }
+
+    /// Validates that the value is a credit card number.
+    ///
+    /// The number may contain spaces or dashes between digit groups. It must
+    /// have 12 to 19 digits and pass the Luhn checksum.
     ///
     /// # Arguments
     ///
@@ -214,152 +464,1864 @@ impl ValidationMethods {
     ///
     /// # Returns
     ///
-    /// * `true` if the value is in a UUID format, `false` otherwise.
-    pub fn is_uuid(value: &str) -> bool {
-        let uuid_regex = Regex::new(Self::UUID_REGEX).unwrap();
-        uuid_regex.is_match(value)
+    /// * `true` if the value is a valid credit card number, `false` otherwise.
+    pub fn is_credit_card(value: &str) -> bool {
+        let credit_card_regex = &*compiled::CREDIT_CARD;
+        if !credit_card_regex.is_match(value) {
+            return false;
+        }
+        let digits = Self::card_digits(value);
+        (12..=19).contains(&digits.len()) && Self::luhn_check(&digits)
     }
-}
 
-/// A struct that holds a list of validation functions and their corresponding error messages.
-#[derive(Debug)]
-pub struct Validator {
-    pub validations: Vec<(fn(&str) -> bool, Option<&'static str>)>,
-}
+    /// Validates that the value passes the Luhn checksum.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the digits to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value consists of digits and passes the checksum, `false` otherwise.
+    pub fn luhn_check(value: &str) -> bool {
+        if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+            return false;
+        }
+        let sum: u32 = value
+            .bytes()
+            .rev()
+            .map(|b| u32::from(b - b'0'))
+            .enumerate()
+            .map(|(i, digit)| match (i % 2 == 1, digit * 2) {
+                (true, doubled) if doubled > 9 => doubled - 9,
+                (true, doubled) => doubled,
+                (false, _) => digit,
+            })
+            .sum();
+        sum.is_multiple_of(10)
+    }
 
-impl Validator {
-    /// Creates a new `Validator` with the given list of validation functions and error messages.
+    /// Detects the brand of a credit card number from its prefix and length.
     ///
     /// # Arguments
     ///
-    /// * `validations` - A vector of tuples where each tuple contains a validation function and an optional error message.
+    /// * `value` - A string slice that holds the card number, optionally with spaces or dashes.
     ///
     /// # Returns
     ///
-    /// * A new instance of `Validator`.
-    pub fn new(validations: Vec<(fn(&str) -> bool, Option<&'static str>)>) -> Self {
-        Self { validations }
+    /// * `Some(CardBrand)` if the number is a valid card number of a known brand, `None` otherwise.
+    pub fn card_brand(value: &str) -> Option<CardBrand> {
+        if !Self::is_credit_card(value) {
+            return None;
+        }
+        let digits = Self::card_digits(value);
+        let length = digits.len();
+        let prefix = |n: usize| digits[..n].parse::<u32>().unwrap();
+
+        if digits.starts_with('4') && matches!(length, 13 | 16 | 19) {
+            Some(CardBrand::Visa)
+        } else if ((51..=55).contains(&prefix(2)) || (2221..=2720).contains(&prefix(4)))
+            && length == 16
+        {
+            Some(CardBrand::Mastercard)
+        } else if matches!(prefix(2), 34 | 37) && length == 15 {
+            Some(CardBrand::AmericanExpress)
+        } else if (prefix(4) == 6011 || prefix(2) == 65 || (644..=649).contains(&prefix(3)))
+            && (16..=19).contains(&length)
+        {
+            Some(CardBrand::Discover)
+        } else if (3528..=3589).contains(&prefix(4)) && (16..=19).contains(&length) {
+            Some(CardBrand::Jcb)
+        } else if (prefix(2) == 36 || prefix(2) == 38 || (300..=305).contains(&prefix(3)))
+            && (14..=19).contains(&length)
+        {
+            Some(CardBrand::DinersClub)
+        } else if prefix(2) == 62 && (16..=19).contains(&length) {
+            Some(CardBrand::UnionPay)
+        } else if matches!(
+            prefix(4),
+            5018 | 5020 | 5038 | 5893 | 6304 | 6759 | 6761 | 6762 | 6763
+        ) && (12..=19).contains(&length)
+        {
+            Some(CardBrand::Maestro)
+        } else {
+            None
+        }
     }
 
-    /// Validates the input string using the list of validation functions.
+    /// Removes spaces and dashes from a card number.
+    fn card_digits(value: &str) -> String {
+        value.chars().filter(|c| c.is_ascii_digit()).collect()
+    }
+
+    /// Validates that the value is a UUID.
+    ///
+    /// Besides the format, the version nibble must be 1 to 8 and the variant
+    /// bits must be those of RFC 9562. The nil and max UUIDs are accepted too.
     ///
     /// # Arguments
     ///
-    /// * `input` - A string slice that holds the input to be validated.
+    /// * `value` - A string slice that holds the value to be validated.
     ///
     /// # Returns
     ///
-    /// * `Ok(())` if the input passes all validations, `Err(String)` with an error message otherwise.
-    pub fn validate(&self, input: &str) -> Result<(), String> {
-        for (validation, error_message) in &self.validations {
-            if !validation(input) {
-                return Err(error_message
-                    .unwrap_or("Invalid input, please try again.")
-                    .to_string());
-            }
+    /// * `true` if the value is a valid UUID, `false` otherwise.
+    pub fn is_uuid(value: &str) -> bool {
+        let uuid_regex = &*compiled::UUID;
+        if !uuid_regex.is_match(value) {
+            return false;
         }
-        Ok(())
+        let digits: String = value.chars().filter(|c| *c != '-').collect();
+        if digits.chars().all(|c| c == '0') || digits.chars().all(|c| c.eq_ignore_ascii_case(&'f'))
+        {
+            return true;
+        }
+        Self::uuid_version(value).is_some()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::ValidationMethods;
+    /// Validates that the value is a UUID of the given version.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    /// * `version` - The expected UUID version, such as `4` for random UUIDs.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a valid UUID of the version, `false` otherwise.
+    pub fn is_uuid_version(value: &str, version: u8) -> bool {
+        Self::uuid_version(value) == Some(version)
+    }
 
-    #[test]
-    fn test_validate_name() {
-        assert!(ValidationMethods::validate_name("John"));
-        assert!(!ValidationMethods::validate_name("John123"));
+    /// Returns the version of a UUID.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the UUID.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(u8)` with the version, from 1 to 8, if the value is a valid UUID.
+    /// * `None` if the value is not a UUID, or has an unknown version or variant.
+    pub fn uuid_version(value: &str) -> Option<u8> {
+        let uuid_regex = &*compiled::UUID;
+        if !uuid_regex.is_match(value) {
+            return None;
+        }
+        let version = value[14..15].parse::<u8>().ok()?;
+        let variant = value[19..20].to_ascii_lowercase();
+        ((1..=8).contains(&version) && matches!(variant.as_str(), "8" | "9" | "a" | "b"))
+            .then_some(version)
     }
 
-    #[test]
-    fn test_validate_email() {
-        assert!(ValidationMethods::validate_email("test@example.com"));
-        assert!(!ValidationMethods::validate_email("test@.com"));
-        assert!(!ValidationMethods::validate_email("test@com"));
+    /// Validates that the value is an IPv4 address in dotted decimal notation.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is an IPv4 address, `false` otherwise.
+    pub fn is_ipv4(value: &str) -> bool {
+        value.parse::<Ipv4Addr>().is_ok()
     }
 
-    #[test]
-    fn test_not_empty() {
-        assert!(ValidationMethods::not_empty("non-empty"));
-        assert!(!ValidationMethods::not_empty(""));
+    /// Validates that the value is an IPv6 address.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is an IPv6 address, `false` otherwise.
+    pub fn is_ipv6(value: &str) -> bool {
+        value.parse::<Ipv6Addr>().is_ok()
     }
 
-    #[test]
-    fn test_min_length() {
-        assert!(ValidationMethods::min_length("hello", 3));
-        assert!(!ValidationMethods::min_length("hi", 3));
+    /// Validates that the value is an IPv4 or IPv6 address.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is an IP address, `false` otherwise.
+    pub fn is_ip_address(value: &str) -> bool {
+        Self::is_ipv4(value) || Self::is_ipv6(value)
     }
 
-    #[test]
-    fn test_max_length() {
-        assert!(ValidationMethods::max_length("hello", 10));
-        assert!(!ValidationMethods::max_length("hello world", 10));
+    /// Validates that the value is an IPv4 or IPv6 network in CIDR notation (e.g., 10.0.0.0/8).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is an address followed by a valid prefix length, `false` otherwise.
+    pub fn is_cidr(value: &str) -> bool {
+        let Some((address, prefix)) = value.split_once('/') else {
+            return false;
+        };
+        if prefix.is_empty()
+            || !prefix.chars().all(|c| c.is_ascii_digit())
+            || (prefix.len() > 1 && prefix.starts_with('0'))
+        {
+            return false;
+        }
+        let max_prefix = if Self::is_ipv4(address) {
+            32
+        } else if Self::is_ipv6(address) {
+            128
+        } else {
+            return false;
+        };
+        prefix
+            .parse::<u32>()
+            .is_ok_and(|prefix| prefix <= max_prefix)
     }
 
-    #[test]
-    fn test_is_alpha() {
-        assert!(ValidationMethods::is_alpha("hello"));
-        assert!(!ValidationMethods::is_alpha("hello123"));
+    /// Validates that the value is a hostname.
+    ///
+    /// A hostname consists of dot-separated labels of 1 to 63 letters, digits
+    /// and hyphens, not starting or ending with a hyphen, and is at most 253
+    /// characters long. A single trailing dot is allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a hostname, `false` otherwise.
+    pub fn is_hostname(value: &str) -> bool {
+        let value = value.strip_suffix('.').unwrap_or(value);
+        !value.is_empty()
+            && value.len() <= 253
+            && value.split('.').all(|label| {
+                (1..=63).contains(&label.len())
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+            })
     }
 
-    #[test]
-    fn test_is_integer() {
-        assert!(ValidationMethods::is_integer("123"));
-        assert!(!ValidationMethods::is_integer("123abc"));
+    /// Validates that the value is a fully qualified domain name (e.g., example.com).
+    ///
+    /// A domain is a hostname with at least two labels whose top-level
+    /// domain is alphabetic, or a punycode `xn--` label.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a domain name, `false` otherwise.
+    pub fn is_domain(value: &str) -> bool {
+        if !Self::is_hostname(value) {
+            return false;
+        }
+        let value = value.strip_suffix('.').unwrap_or(value);
+        match value.rsplit_once('.') {
+            Some((_, tld)) => {
+                (tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()))
+                    || tld.to_ascii_lowercase().starts_with("xn--")
+            }
+            None => false,
+        }
     }
 
-    #[test]
-    fn test_is_float() {
-        assert!(ValidationMethods::is_float("123.45"));
-        assert!(!ValidationMethods::is_float("123.45abc"));
+    /// Validates that the value is an IBAN.
+    ///
+    /// Spaces between groups are allowed. The length must match the country
+    /// and the mod-97 checksum of ISO 13616 must be 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a valid IBAN, `false` otherwise.
+    pub fn is_iban(value: &str) -> bool {
+        let iban: String = value
+            .chars()
+            .filter(|c| *c != ' ')
+            .collect::<String>()
+            .to_ascii_uppercase();
+        let iban_regex = &*compiled::IBAN;
+        if !iban_regex.is_match(&iban) {
+            return false;
+        }
+        let length_matches = Self::IBAN_LENGTHS
+            .iter()
+            .find(|(country, _)| iban.starts_with(country))
+            .is_some_and(|(_, length)| iban.len() == *length);
+        if !length_matches {
+            return false;
+        }
+        let (head, tail) = iban.split_at(4);
+        let remainder = tail.chars().chain(head.chars()).fold(0u32, |acc, c| {
+            let digit = c.to_digit(36).unwrap();
+            if digit < 10 {
+                (acc * 10 + digit) % 97
+            } else {
+                (acc * 100 + digit) % 97
+            }
+        });
+        remainder == 1
     }
 
-    #[test]
-    fn test_is_date() {
-        assert!(ValidationMethods::is_date("2023-10-01"));
-        assert!(!ValidationMethods::is_date("2023-10-32"));
+    /// Validates that the value is an ISBN-10, with optional hyphens or spaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is an ISBN-10 with a correct check digit, `false` otherwise.
+    pub fn is_isbn10(value: &str) -> bool {
+        let Some(digits) = Self::isbn_digits(value) else {
+            return false;
+        };
+        if digits.len() != 10 {
+            return false;
+        }
+        let mut sum = 0;
+        for (i, c) in digits.chars().enumerate() {
+            let digit = match c {
+                'X' | 'x' if i == 9 => 10,
+                _ => match c.to_digit(10) {
+                    Some(digit) => digit,
+                    None => return false,
+                },
+            };
+            sum += digit * (10 - i as u32);
+        }
+        sum.is_multiple_of(11)
     }
 
-    #[test]
-    fn test_is_time() {
-        assert!(ValidationMethods::is_time("12:34:56"));
-        assert!(!ValidationMethods::is_time("25:34:56"));
+    /// Validates that the value is an ISBN-13, with optional hyphens or spaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is an ISBN-13 with a correct check digit, `false` otherwise.
+    pub fn is_isbn13(value: &str) -> bool {
+        let Some(digits) = Self::isbn_digits(value) else {
+            return false;
+        };
+        if digits.len() != 13
+            || !digits.chars().all(|c| c.is_ascii_digit())
+            || !(digits.starts_with("978") || digits.starts_with("979"))
+        {
+            return false;
+        }
+        let sum: u32 = digits
+            .chars()
+            .enumerate()
+            .map(|(i, c)| c.to_digit(10).unwrap() * if i % 2 == 0 { 1 } else { 3 })
+            .sum();
+        sum.is_multiple_of(10)
     }
 
-    #[test]
-    fn test_is_url() {
-        assert!(ValidationMethods::is_url("https://example.com"));
-        assert!(!ValidationMethods::is_url("htp://example.com"));
+    /// Validates that the value is an ISBN-10 or an ISBN-13.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a valid ISBN, `false` otherwise.
+    pub fn is_isbn(value: &str) -> bool {
+        Self::is_isbn10(value) || Self::is_isbn13(value)
     }
 
-    #[test]
-    fn test_is_phone_number() {
-        assert!(ValidationMethods::is_phone_number("+1234567890"));
-        assert!(!ValidationMethods::is_phone_number("123-456-7890"));
+    /// Removes single hyphens or spaces between the groups of an ISBN.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(String)` with the digits, or `None` if a separator is misplaced.
+    fn isbn_digits(value: &str) -> Option<String> {
+        let groups: Vec<&str> = value.split(['-', ' ']).collect();
+        if groups.iter().any(|group| group.is_empty()) {
+            return None;
+        }
+        Some(groups.concat())
     }
 
-    #[test]
-    fn test_is_postal_code() {
-        assert!(ValidationMethods::is_postal_code("12345"));
-        assert!(ValidationMethods::is_postal_code("12345-6789"));
-        assert!(!ValidationMethods::is_postal_code("1234"));
+    /// Validates that the value is a semantic version (e.g., 1.2.3-beta.1+build.5).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value follows Semantic Versioning 2.0.0, `false` otherwise.
+    pub fn is_semver(value: &str) -> bool {
+        let semver_regex = &*compiled::SEMVER;
+        semver_regex.is_match(value)
     }
 
-    #[test]
-    fn test_is_credit_card() {
-        assert!(ValidationMethods::is_credit_card("1234-5678-1234-5678"));
-        assert!(ValidationMethods::is_credit_card("1234567812345678"));
-        assert!(!ValidationMethods::is_credit_card("1234-5678-1234-567"));
+    /// Validates that the value is a hex color (e.g., #fff, #ffcc00 or #ffcc0080).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a hex color with 3, 4, 6 or 8 digits, `false` otherwise.
+    pub fn is_hex_color(value: &str) -> bool {
+        let hex_color_regex = &*compiled::HEX_COLOR;
+        hex_color_regex.is_match(value)
     }
 
-    #[test]
-    fn test_is_uuid() {
-        assert!(ValidationMethods::is_uuid(
-            "123e4567-e89b-12d3-a456-426614174000"
-        ));
+    /// Validates that the value is a URL slug (e.g., my-first-post).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value consists of lowercase letters and digits separated by single hyphens, `false` otherwise.
+    pub fn is_slug(value: &str) -> bool {
+        let slug_regex = &*compiled::SLUG;
+        slug_regex.is_match(value)
+    }
+
+    /// Validates that the value contains only ASCII characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value contains only ASCII characters, `false` otherwise.
+    pub fn is_ascii(value: &str) -> bool {
+        value.is_ascii()
+    }
+
+    /// Validates that the value contains only alphanumeric characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value contains only letters and digits, `false` otherwise.
+    pub fn is_alphanumeric(value: &str) -> bool {
+        value.chars().all(|c| c.is_alphanumeric())
+    }
+
+    /// Validates that the value is standard base64 with padding.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is base64 encoded, `false` otherwise.
+    pub fn is_base64(value: &str) -> bool {
+        let base64_regex = &*compiled::BASE64;
+        base64_regex.is_match(value)
+    }
+
+    /// Validates that the value is a JSON text.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is valid JSON, `false` otherwise.
+    pub fn is_json(value: &str) -> bool {
+        json::parse(value).is_ok()
+    }
+
+    /// Validates that the value is an ISO 8601 duration (e.g., P3Y6M4DT12H30M5S or PT0.5S).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a duration with at least one component, `false` otherwise.
+    pub fn is_iso8601_duration(value: &str) -> bool {
+        let duration_regex = &*compiled::DURATION;
+        duration_regex.is_match(value) && value != "P" && !value.ends_with('T')
+    }
+
+    /// Validates that the value is a latitude in decimal degrees, from -90 to 90.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a latitude, `false` otherwise.
+    pub fn is_latitude(value: &str) -> bool {
+        Self::is_coordinate(value, 90.0)
+    }
+
+    /// Validates that the value is a longitude in decimal degrees, from -180 to 180.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a longitude, `false` otherwise.
+    pub fn is_longitude(value: &str) -> bool {
+        Self::is_coordinate(value, 180.0)
+    }
+
+    /// Validates that the value is a latitude and longitude pair separated by a comma (e.g., 52.52, 13.405).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is a valid coordinate pair, `false` otherwise.
+    pub fn is_lat_long(value: &str) -> bool {
+        value.split_once(',').is_some_and(|(latitude, longitude)| {
+            Self::is_latitude(latitude.trim()) && Self::is_longitude(longitude.trim())
+        })
+    }
+
+    /// Checks that the value is a decimal number of degrees within `-max..=max`.
+    fn is_coordinate(value: &str, max: f64) -> bool {
+        let coordinate_regex = &*compiled::COORDINATE;
+        coordinate_regex.is_match(value)
+            && value
+                .parse::<f64>()
+                .is_ok_and(|degrees| degrees.abs() <= max)
+    }
+}
+
+/// The brand of a credit card, detected by `ValidationMethods::card_brand`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardBrand {
+    /// A Visa card.
+    Visa,
+    /// A Mastercard card.
+    Mastercard,
+    /// An American Express card.
+    AmericanExpress,
+    /// A Discover card.
+    Discover,
+    /// A Diners Club card.
+    DinersClub,
+    /// A JCB card.
+    Jcb,
+    /// A UnionPay card.
+    UnionPay,
+    /// A Maestro card.
+    Maestro,
+}
+
+/// Declares `NamedRule` with a variant for each built-in validation function.
+macro_rules! named_rules {
+    ($($variant:ident => $function:ident, $requirement:literal;)*) => {
+        /// A built-in validation function, identified by name.
+        ///
+        /// Validators describe, export and import named rules, which plain
+        /// validation functions cannot be told apart by.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum NamedRule {
+            $(
+                #[doc = $requirement]
+                $variant,
+            )*
+        }
+
+        impl NamedRule {
+            /// Every named rule, in the order of the `ValidationMethods` functions.
+            pub const ALL: &'static [NamedRule] = &[$(NamedRule::$variant),*];
+
+            /// Returns the name of the validation function, such as `"validate_email"`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(NamedRule::$variant => stringify!($function),)*
+                }
+            }
+
+            /// Returns the validation function, such as `ValidationMethods::validate_email`.
+            pub fn function(self) -> fn(&str) -> bool {
+                match self {
+                    $(NamedRule::$variant => ValidationMethods::$function,)*
+                }
+            }
+
+            /// Returns a description of the requirement checked by the rule.
+            pub fn requirement(self) -> &'static str {
+                match self {
+                    $(NamedRule::$variant => $requirement,)*
+                }
+            }
+        }
+    };
+}
+
+named_rules! {
+    Name => validate_name, "Must not contain digits";
+    Email => validate_email, "Must be a valid email address";
+    NotEmpty => not_empty, "Must not be empty";
+    Alpha => is_alpha, "Must contain only letters";
+    Integer => is_integer, "Must be a whole number";
+    Float => is_float, "Must be a number";
+    Date => is_date, "Must be a date in the format YYYY-MM-DD";
+    Time => is_time, "Must be a time in the format HH:MM:SS";
+    Url => is_url, "Must be an http or https URL";
+    PhoneNumber => is_phone_number, "Must be a phone number in international format";
+    PostalCode => is_postal_code, "Must be a US postal code, such as 12345 or 12345-6789";
+    CreditCard => is_credit_card, "Must be a valid card number";
+    Luhn => luhn_check, "Must pass the Luhn checksum";
+    Uuid => is_uuid, "Must be a UUID";
+    Ipv4 => is_ipv4, "Must be an IPv4 address";
+    Ipv6 => is_ipv6, "Must be an IPv6 address";
+    IpAddress => is_ip_address, "Must be an IP address";
+    Cidr => is_cidr, "Must be a network in CIDR notation, such as 10.0.0.0/8";
+    Hostname => is_hostname, "Must be a hostname";
+    Domain => is_domain, "Must be a domain name, such as example.com";
+    Iban => is_iban, "Must be a valid IBAN";
+    Isbn10 => is_isbn10, "Must be a valid ISBN-10";
+    Isbn13 => is_isbn13, "Must be a valid ISBN-13";
+    Isbn => is_isbn, "Must be a valid ISBN";
+    Semver => is_semver, "Must be a semantic version, such as 1.2.3";
+    HexColor => is_hex_color, "Must be a hex color, such as #ffcc00";
+    Slug => is_slug, "Must contain only lowercase letters, digits and single hyphens";
+    Ascii => is_ascii, "Must contain only ASCII characters";
+    Alphanumeric => is_alphanumeric, "Must contain only letters and digits";
+    Base64 => is_base64, "Must be base64 encoded";
+    Json => is_json, "Must be valid JSON";
+    Duration => is_iso8601_duration, "Must be an ISO 8601 duration, such as P1DT12H";
+    Latitude => is_latitude, "Must be a latitude between -90 and 90";
+    Longitude => is_longitude, "Must be a longitude between -180 and 180";
+    LatLong => is_lat_long, "Must be a latitude and longitude pair, such as 52.52, 13.405";
+}
+
+impl NamedRule {
+    /// Looks up a named rule by the name of its validation function.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the function, such as `"validate_email"`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(NamedRule)` with the rule, or `None` if no built-in function has the name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|rule| rule.name() == name)
+    }
+
+    /// Looks up the regular expression the rule matches values against.
+    ///
+    /// Rules checking more than the expression, such as the day of a
+    /// date, still return it, since every valid value matches it.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(regex)` with the anchored expression, if the rule is based on one.
+    /// * `None` for other rules.
+    pub(crate) fn regex(self) -> Option<&'static str> {
+        match self {
+            NamedRule::Email => Some(ValidationMethods::EMAIL_REGEX),
+            NamedRule::Date => Some(ValidationMethods::DATE_REGEX),
+            NamedRule::Time => Some(ValidationMethods::TIME_REGEX),
+            NamedRule::Url => Some(ValidationMethods::URL_REGEX),
+            NamedRule::PhoneNumber => Some(ValidationMethods::PHONE_REGEX),
+            NamedRule::PostalCode => Some(ValidationMethods::POSTAL_CODE_REGEX),
+            NamedRule::CreditCard => Some(ValidationMethods::CREDIT_CARD_REGEX),
+            NamedRule::Uuid => Some(ValidationMethods::UUID_REGEX),
+            NamedRule::Semver => Some(ValidationMethods::SEMVER_REGEX),
+            NamedRule::HexColor => Some(ValidationMethods::HEX_COLOR_REGEX),
+            NamedRule::Slug => Some(ValidationMethods::SLUG_REGEX),
+            NamedRule::Base64 => Some(ValidationMethods::BASE64_REGEX),
+            NamedRule::Duration => Some(ValidationMethods::DURATION_REGEX),
+            NamedRule::Latitude => Some(ValidationMethods::COORDINATE_REGEX),
+            NamedRule::Longitude => Some(ValidationMethods::COORDINATE_REGEX),
+            _ => None,
+        }
+    }
+
+    /// Looks up the JSON Schema `format` the rule checks.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(format)` with the name of the format, such as `"email"`.
+    /// * `None` for rules without a matching format.
+    pub(crate) fn format(self) -> Option<&'static str> {
+        match self {
+            NamedRule::Email => Some("email"),
+            NamedRule::Date => Some("date"),
+            NamedRule::Url => Some("uri"),
+            NamedRule::Uuid => Some("uuid"),
+            NamedRule::Duration => Some("duration"),
+            NamedRule::Ipv4 => Some("ipv4"),
+            NamedRule::Ipv6 => Some("ipv6"),
+            NamedRule::Hostname => Some("hostname"),
+            _ => None,
+        }
+    }
+
+    /// Looks up the rule checking a JSON Schema `format`.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The name of the format, such as `"email"`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(NamedRule)` with the rule, or `None` for unsupported formats.
+    pub(crate) fn from_format(format: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|rule| rule.format() == Some(format))
+    }
+
+    /// Looks up the range of numbers the rule accepts.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((min, max))` with the inclusive bounds, if the rule checks a range.
+    /// * `None` for other rules.
+    pub(crate) fn range(self) -> Option<(f64, f64)> {
+        match self {
+            NamedRule::Latitude => Some((-90.0, 90.0)),
+            NamedRule::Longitude => Some((-180.0, 180.0)),
+            _ => None,
+        }
+    }
+}
+
+/// A validation function paired with an optional error message.
+pub type ValidationRule = (fn(&str) -> bool, Option<&'static str>);
+
+/// The default error message of failed validations.
+const DEFAULT_MESSAGE: &str = "Invalid input, please try again.";
+
+/// A validator combining other validators, created by the combinators of `Validator`.
+#[derive(Debug, Clone)]
+enum Combinator {
+    /// Passes if every validator passes.
+    All(Vec<Validator>),
+    /// Passes if at least one validator passes.
+    Any(Vec<Validator>),
+    /// Passes if the validator fails, with the error message used otherwise.
+    Not(Box<Validator>, Option<&'static str>),
+    /// Passes for empty input, and otherwise if the validator passes.
+    WhenNonempty(Box<Validator>),
+    /// Passes if the validator passes for every item of a list split at the separator.
+    Each(Box<Validator>, char),
+}
+
+/// A registry of named regular expression validators.
+///
+/// Each pattern is compiled once when registered and shared by the validators
+/// created from it with `Validator::pattern`, so registries of different forms
+/// do not see each other's names.
+#[derive(Debug, Clone, Default)]
+pub struct Patterns {
+    /// The compiled expressions, keyed by name.
+    patterns: HashMap<String, Arc<Regex>>,
+}
+
+impl Patterns {
+    /// Creates an empty registry.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Patterns`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a named regular expression validator.
+    ///
+    /// Registering a name again replaces its pattern for the validators
+    /// created afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the validator, such as `"order_id"`.
+    /// * `pattern` - The regular expression the whole value must match; anchor it with `^` and `$`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Patterns)` with the pattern registered.
+    /// * `Err(String)` if the pattern is not a valid regular expression.
+    pub fn register(mut self, name: &str, pattern: &str) -> Result<Self, String> {
+        let regex = Regex::new(pattern).map_err(|err| err.to_string())?;
+        self.patterns.insert(name.to_string(), Arc::new(regex));
+        Ok(self)
+    }
+
+    /// Checks whether a pattern is registered under a name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the validator.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a pattern is registered under the name, `false` otherwise.
+    pub fn contains(&self, name: &str) -> bool {
+        self.patterns.contains_key(name)
+    }
+
+    /// Validates a value against a named regular expression validator.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name the pattern was registered under.
+    /// * `value` - A string slice that holds the value to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` if the value matches the pattern, `Ok(false)` otherwise.
+    /// * `Err(String)` if no pattern is registered under the name.
+    pub fn matches(&self, name: &str, value: &str) -> Result<bool, String> {
+        Ok(self.get(name)?.is_match(value))
+    }
+
+    /// Returns the compiled expression registered under a name.
+    fn get(&self, name: &str) -> Result<Arc<Regex>, String> {
+        self.patterns
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Pattern '{}' is not registered", name))
+    }
+}
+
+/// A struct that holds a list of validation functions and their corresponding error messages.
+///
+/// Validators can be composed with `all`, `any`, `not`, `when_nonempty` and
+/// `each`, which nest arbitrarily.
+#[derive(Debug, Clone)]
+pub struct Validator {
+    pub validations: Vec<ValidationRule>,
+    /// Built-in rules, checked after the validation functions, paired with an optional error message.
+    rules: Vec<(NamedRule, Option<&'static str>)>,
+    /// Named regular expression validators taken from a `Patterns` registry,
+    /// paired with an optional error message.
+    patterns: Vec<(String, Arc<Regex>, Option<&'static str>)>,
+    /// Regular expressions compiled for this validator only, paired with an optional error message.
+    regexes: Vec<(Regex, Option<&'static str>)>,
+    /// Combined validators, checked after the validation functions and patterns.
+    combinators: Vec<Combinator>,
+    /// An error message replacing the messages of all failed checks.
+    message: Option<&'static str>,
+}
+
+impl Validator {
+    /// Creates a new `Validator` with the given list of validation functions and error messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `validations` - A vector of tuples where each tuple contains a validation function and an optional error message.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator`.
+    pub fn new(validations: Vec<ValidationRule>) -> Self {
+        Self {
+            validations,
+            rules: Vec::new(),
+            patterns: Vec::new(),
+            regexes: Vec::new(),
+            combinators: Vec::new(),
+            message: None,
+        }
+    }
+
+    /// Creates a `Validator` with a single validation function.
+    ///
+    /// # Arguments
+    ///
+    /// * `validation` - The validation function.
+    /// * `error_message` - An optional error message for values failing the validation.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator`.
+    pub fn rule(validation: fn(&str) -> bool, error_message: Option<&'static str>) -> Self {
+        Self::new(vec![(validation, error_message)])
+    }
+
+    /// Creates a `Validator` with a single built-in rule.
+    ///
+    /// Unlike plain validation functions, built-in rules are described by
+    /// `requirements` and exported to HTML and JSON Schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The built-in rule, such as `NamedRule::Email`.
+    /// * `error_message` - An optional error message for values failing the rule.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator`.
+    pub fn named(rule: NamedRule, error_message: Option<&'static str>) -> Self {
+        Self::new(Vec::new()).builtin(rule, error_message)
+    }
+
+    /// Creates a `Validator` from a combinator.
+    fn combinator(combinator: Combinator) -> Self {
+        let mut validator = Self::new(Vec::new());
+        validator.combinators.push(combinator);
+        validator
+    }
+
+    /// Creates a `Validator` that passes if every given validator passes.
+    ///
+    /// # Arguments
+    ///
+    /// * `validators` - The validators, checked in order.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator` failing with the error of the first failed validator.
+    pub fn all(validators: Vec<Validator>) -> Self {
+        Self::combinator(Combinator::All(validators))
+    }
+
+    /// Creates a `Validator` that passes if at least one of the given validators passes.
+    ///
+    /// # Arguments
+    ///
+    /// * `validators` - The alternative validators. An empty list never passes.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator` failing with the errors of all validators joined by "or".
+    pub fn any(validators: Vec<Validator>) -> Self {
+        Self::combinator(Combinator::Any(validators))
+    }
+
+    /// Creates a `Validator` that passes if the given validator fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `validator` - The validator to be negated.
+    /// * `error_message` - An optional error message for values passing `validator`.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator`.
+    pub fn not(validator: Validator, error_message: Option<&'static str>) -> Self {
+        Self::combinator(Combinator::Not(Box::new(validator), error_message))
+    }
+
+    /// Creates a `Validator` that accepts empty input and checks any other input with the given validator.
+    ///
+    /// # Arguments
+    ///
+    /// * `validator` - The validator applied to non-empty input.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator`.
+    pub fn when_nonempty(validator: Validator) -> Self {
+        Self::combinator(Combinator::WhenNonempty(Box::new(validator)))
+    }
+
+    /// Alias of `when_nonempty`, for validators of optional fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `validator` - The validator applied to non-empty input.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator`.
+    pub fn optional(validator: Validator) -> Self {
+        Self::when_nonempty(validator)
+    }
+
+    /// Creates a `Validator` that checks every item of a comma-separated list.
+    ///
+    /// Items are trimmed before they are validated.
+    ///
+    /// # Arguments
+    ///
+    /// * `validator` - The validator applied to each item.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator` failing with the position, the item and its error.
+    pub fn each(validator: Validator) -> Self {
+        Self::each_separated(validator, ',')
+    }
+
+    /// Creates a `Validator` that checks every item of a list split at the given separator.
+    ///
+    /// # Arguments
+    ///
+    /// * `validator` - The validator applied to each item.
+    /// * `separator` - The character separating the items.
+    ///
+    /// # Returns
+    ///
+    /// * A new instance of `Validator`.
+    pub fn each_separated(validator: Validator, separator: char) -> Self {
+        Self::combinator(Combinator::Each(Box::new(validator), separator))
+    }
+
+    /// Combines the validator with another one that must pass as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The validator checked after this one.
+    ///
+    /// # Returns
+    ///
+    /// * A `Validator` passing if both validators pass.
+    pub fn and(self, other: Validator) -> Self {
+        Self::all(vec![self, other])
+    }
+
+    /// Combines the validator with an alternative one.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The validator checked if this one fails.
+    ///
+    /// # Returns
+    ///
+    /// * A `Validator` passing if either validator passes.
+    pub fn or(self, other: Validator) -> Self {
+        Self::any(vec![self, other])
+    }
+
+    /// Sets an error message replacing the messages of all failed checks.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The error message.
+    ///
+    /// # Returns
+    ///
+    /// * The `Validator` instance with the message set.
+    pub fn with_message(mut self, message: &'static str) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Adds a built-in rule, checked after the validation functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The built-in rule, such as `NamedRule::Email`.
+    /// * `error_message` - An optional error message for values failing the rule.
+    ///
+    /// # Returns
+    ///
+    /// * The `Validator` instance with the rule added.
+    pub fn builtin(mut self, rule: NamedRule, error_message: Option<&'static str>) -> Self {
+        self.rules.push((rule, error_message));
+        self
+    }
+
+    /// Adds a named regular expression validator, checked after the validation functions and built-in rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - The registry the pattern was registered in.
+    /// * `name` - The name the pattern was registered under.
+    /// * `error_message` - An optional error message for values not matching the pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Validator)` with the pattern added.
+    /// * `Err(String)` if no pattern is registered under the name.
+    pub fn pattern(
+        mut self,
+        patterns: &Patterns,
+        name: &str,
+        error_message: Option<&'static str>,
+    ) -> Result<Self, String> {
+        let regex = patterns.get(name)?;
+        self.patterns.push((name.to_string(), regex, error_message));
+        Ok(self)
+    }
+
+    /// Adds a regular expression validator known only to this validator, checked after the named patterns.
+    ///
+    /// Unlike `pattern`, the expression is not registered under a name, so it
+    /// is compiled for every validator it is added to.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex` - The regular expression the input has to match.
+    /// * `error_message` - An optional error message for values not matching the expression.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Validator)` with the expression added.
+    /// * `Err(String)` if the expression is invalid.
+    pub fn regex(
+        mut self,
+        regex: &str,
+        error_message: Option<&'static str>,
+    ) -> Result<Self, String> {
+        let regex = Regex::new(regex).map_err(|err| err.to_string())?;
+        self.regexes.push((regex, error_message));
+        Ok(self)
+    }
+
+    /// Returns the built-in rules of the validator, leaving out those of its combinators.
+    pub(crate) fn named_rules(&self) -> impl Iterator<Item = NamedRule> + Clone + '_ {
+        self.rules.iter().map(|(rule, _)| *rule)
+    }
+
+    /// Returns the regular expressions of the patterns of the validator.
+    ///
+    /// # Returns
+    ///
+    /// * The expressions of the named patterns followed by those added with `regex`.
+    pub(crate) fn pattern_regexes(&self) -> Vec<String> {
+        self.patterns
+            .iter()
+            .map(|(_, regex, _)| regex.to_string())
+            .chain(self.regexes.iter().map(|(regex, _)| regex.to_string()))
+            .collect()
+    }
+
+    /// Validates the input string using the list of validation functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - A string slice that holds the input to be validated.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the input passes all validations, `Err(String)` with an error message otherwise.
+    pub fn validate(&self, input: &str) -> Result<(), String> {
+        self.check(input)
+            .map_err(|err| self.message.map_or(err, str::to_string))
+    }
+
+    /// Describes the requirements checked by the validator.
+    ///
+    /// Built-in rules are described by `NamedRule::requirement`, other checks
+    /// by their error message. Checks without either are left out.
+    ///
+    /// # Returns
+    ///
+    /// * A list of human-readable requirements, such as "Must be a valid email address".
+    pub fn requirements(&self) -> Vec<String> {
+        if let Some(message) = self.message {
+            return vec![message.to_string()];
+        }
+        let mut requirements: Vec<String> = Vec::new();
+        for (_, error_message) in &self.validations {
+            requirements.extend(error_message.map(str::to_string));
+        }
+        for (rule, _) in &self.rules {
+            requirements.push(rule.requirement().to_string());
+        }
+        for (name, _, error_message) in &self.patterns {
+            requirements.push(
+                error_message
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("Must match the '{}' pattern", name)),
+            );
+        }
+        for (regex, error_message) in &self.regexes {
+            requirements.push(
+                error_message
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("Must match '{}'", regex)),
+            );
+        }
+        for combinator in &self.combinators {
+            requirements.extend(combinator.requirements());
+        }
+        requirements
+    }
+
+    /// Checks the input against the validation functions, built-in rules, patterns and
+    /// combinators, in that order.
+    fn check(&self, input: &str) -> Result<(), String> {
+        for (validation, error_message) in &self.validations {
+            if !validation(input) {
+                return Err(error_message.unwrap_or(DEFAULT_MESSAGE).to_string());
+            }
+        }
+        for (rule, error_message) in &self.rules {
+            if !rule.function()(input) {
+                return Err(error_message.unwrap_or(DEFAULT_MESSAGE).to_string());
+            }
+        }
+        for (_, regex, error_message) in &self.patterns {
+            if !regex.is_match(input) {
+                return Err(error_message.unwrap_or(DEFAULT_MESSAGE).to_string());
+            }
+        }
+        for (regex, error_message) in &self.regexes {
+            if !regex.is_match(input) {
+                return Err(error_message.unwrap_or(DEFAULT_MESSAGE).to_string());
+            }
+        }
+        for combinator in &self.combinators {
+            combinator.check(input)?;
+        }
+        Ok(())
+    }
+}
+
+impl Combinator {
+    /// Describes the requirements checked by the combined validators.
+    fn requirements(&self) -> Vec<String> {
+        match self {
+            Combinator::All(validators) => validators
+                .iter()
+                .flat_map(Validator::requirements)
+                .collect(),
+            Combinator::Any(validators) => {
+                let alternatives: Vec<String> = validators
+                    .iter()
+                    .map(|validator| validator.requirements().join(" and "))
+                    .filter(|requirement| !requirement.is_empty())
+                    .collect();
+                match alternatives.len() {
+                    0 => Vec::new(),
+                    1 => alternatives,
+                    _ => vec![format!("One of: {}", alternatives.join("; "))],
+                }
+            }
+            Combinator::Not(validator, error_message) => match error_message {
+                Some(message) => vec![message.to_string()],
+                None => validator
+                    .requirements()
+                    .into_iter()
+                    .map(|requirement| format!("Not: {}", requirement))
+                    .collect(),
+            },
+            Combinator::WhenNonempty(validator) => validator
+                .requirements()
+                .into_iter()
+                .map(|requirement| format!("{} (if provided)", requirement))
+                .collect(),
+            Combinator::Each(validator, separator) => validator
+                .requirements()
+                .into_iter()
+                .map(|requirement| {
+                    format!("Each item separated by '{}': {}", separator, requirement)
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks the input against the combined validators.
+    fn check(&self, input: &str) -> Result<(), String> {
+        match self {
+            Combinator::All(validators) => validators
+                .iter()
+                .try_for_each(|validator| validator.validate(input)),
+            Combinator::Any(validators) => {
+                let mut errors: Vec<String> = Vec::new();
+                for validator in validators {
+                    match validator.validate(input) {
+                        Ok(()) => return Ok(()),
+                        Err(err) => {
+                            let err = err.trim_end_matches('.').to_string();
+                            if !errors.contains(&err) {
+                                errors.push(err);
+                            }
+                        }
+                    }
+                }
+                if errors.is_empty() {
+                    Err(DEFAULT_MESSAGE.to_string())
+                } else {
+                    Err(errors.join(" or "))
+                }
+            }
+            Combinator::Not(validator, error_message) => match validator.validate(input) {
+                Ok(()) => Err(error_message.unwrap_or(DEFAULT_MESSAGE).to_string()),
+                Err(_) => Ok(()),
+            },
+            Combinator::WhenNonempty(validator) => {
+                if input.trim().is_empty() {
+                    Ok(())
+                } else {
+                    validator.validate(input)
+                }
+            }
+            Combinator::Each(validator, separator) => input
+                .split(*separator)
+                .map(str::trim)
+                .enumerate()
+                .try_for_each(|(i, item)| {
+                    validator
+                        .validate(item)
+                        .map_err(|err| format!("Item {} ('{}'): {}", i + 1, item, err))
+                }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardBrand, DEFAULT_MESSAGE, NamedRule, Patterns, ValidationMethods, Validator};
+
+    #[test]
+    fn test_validate_name() {
+        assert!(ValidationMethods::validate_name("John"));
+        assert!(!ValidationMethods::validate_name("John123"));
+    }
+
+    #[test]
+    fn test_validate_email() {
+        assert!(ValidationMethods::validate_email("test@example.com"));
+        assert!(!ValidationMethods::validate_email("test@.com"));
+        assert!(!ValidationMethods::validate_email("test@com"));
+    }
+
+    #[test]
+    fn test_not_empty() {
+        assert!(ValidationMethods::not_empty("non-empty"));
+        assert!(!ValidationMethods::not_empty(""));
+    }
+
+    #[test]
+    fn test_min_length() {
+        assert!(ValidationMethods::min_length("hello", 3));
+        assert!(!ValidationMethods::min_length("hi", 3));
+    }
+
+    #[test]
+    fn test_max_length() {
+        assert!(ValidationMethods::max_length("hello", 10));
+        assert!(!ValidationMethods::max_length("hello world", 10));
+    }
+
+    #[test]
+    fn test_is_alpha() {
+        assert!(ValidationMethods::is_alpha("hello"));
+        assert!(!ValidationMethods::is_alpha("hello123"));
+    }
+
+    #[test]
+    fn test_is_integer() {
+        assert!(ValidationMethods::is_integer("123"));
+        assert!(!ValidationMethods::is_integer("123abc"));
+    }
+
+    #[test]
+    fn test_is_float() {
+        assert!(ValidationMethods::is_float("123.45"));
+        assert!(!ValidationMethods::is_float("123.45abc"));
+    }
+
+    #[test]
+    fn test_is_date() {
+        assert!(ValidationMethods::is_date("2023-10-01"));
+        assert!(!ValidationMethods::is_date("2023-10-32"));
+        assert!(!ValidationMethods::is_date("2023-02-31"));
+        assert!(!ValidationMethods::is_date("2023-04-31"));
+        assert!(!ValidationMethods::is_date("2023-02-29"));
+        assert!(ValidationMethods::is_date("2024-02-29"));
+        assert!(ValidationMethods::is_date("2000-02-29"));
+        assert!(!ValidationMethods::is_date("1900-02-29"));
+    }
+
+    #[test]
+    fn test_is_time() {
+        assert!(ValidationMethods::is_time("12:34:56"));
+        assert!(!ValidationMethods::is_time("25:34:56"));
+    }
+
+    #[test]
+    fn test_is_url() {
+        assert!(ValidationMethods::is_url("https://example.com"));
+        assert!(!ValidationMethods::is_url("htp://example.com"));
+    }
+
+    #[test]
+    fn test_is_phone_number() {
+        assert!(ValidationMethods::is_phone_number("+1234567890"));
+        assert!(!ValidationMethods::is_phone_number("123-456-7890"));
+    }
+
+    #[test]
+    fn test_is_postal_code() {
+        assert!(ValidationMethods::is_postal_code("12345"));
+        assert!(ValidationMethods::is_postal_code("12345-6789"));
+        assert!(!ValidationMethods::is_postal_code("1234"));
+    }
+
+    #[test]
+    fn test_is_postal_code_for() {
+        assert!(ValidationMethods::is_postal_code_for("10115", "DE"));
+        assert!(!ValidationMethods::is_postal_code_for("1011", "DE"));
+        assert!(ValidationMethods::is_postal_code_for("SW1A 1AA", "GB"));
+        assert!(ValidationMethods::is_postal_code_for("sw1a1aa", "gb"));
+        assert!(ValidationMethods::is_postal_code_for("K1A 0B1", "CA"));
+        assert!(ValidationMethods::is_postal_code_for("00-950", "PL"));
+        assert!(!ValidationMethods::is_postal_code_for("00950", "PL"));
+        assert!(ValidationMethods::is_postal_code_for("1012 AB", "NL"));
+        assert!(ValidationMethods::is_postal_code_for("100-0001", "JP"));
+        assert!(ValidationMethods::is_postal_code_for("D02 X285", "IE"));
+        assert!(ValidationMethods::is_postal_code_for("12345-6789", "US"));
+        assert!(!ValidationMethods::is_postal_code_for("12345", "XX"));
+        assert!(ValidationMethods::postal_code_countries().any(|code| code == "FR"));
+    }
+
+    #[test]
+    fn test_is_postal_code_for_as_validator() {
+        let validator = Validator::new(vec![(
+            |value| ValidationMethods::is_postal_code_for(value, "FR"),
+            Some("Invalid postal code"),
+        )]);
+        assert!(validator.validate("75008").is_ok());
+        assert!(validator.validate("7500").is_err());
+    }
+
+    #[test]
+    fn test_is_credit_card() {
+        assert!(ValidationMethods::is_credit_card("4111-1111-1111-1111"));
+        assert!(ValidationMethods::is_credit_card("4111111111111111"));
+        assert!(ValidationMethods::is_credit_card("3782 822463 10005"));
+        assert!(!ValidationMethods::is_credit_card("4111-1111-1111-1112"));
+        assert!(!ValidationMethods::is_credit_card("1234-5678-1234-5678"));
+        assert!(!ValidationMethods::is_credit_card("4111-1111-1111-111"));
+        assert!(!ValidationMethods::is_credit_card("4111--1111-1111-1111"));
+    }
+
+    #[test]
+    fn test_luhn_check() {
+        assert!(ValidationMethods::luhn_check("79927398713"));
+        assert!(!ValidationMethods::luhn_check("79927398710"));
+        assert!(!ValidationMethods::luhn_check(""));
+        assert!(!ValidationMethods::luhn_check("7992a398713"));
+    }
+
+    #[test]
+    fn test_card_brand() {
+        assert_eq!(
+            ValidationMethods::card_brand("4111 1111 1111 1111"),
+            Some(CardBrand::Visa)
+        );
+        assert_eq!(
+            ValidationMethods::card_brand("5555555555554444"),
+            Some(CardBrand::Mastercard)
+        );
+        assert_eq!(
+            ValidationMethods::card_brand("2223003122003222"),
+            Some(CardBrand::Mastercard)
+        );
+        assert_eq!(
+            ValidationMethods::card_brand("378282246310005"),
+            Some(CardBrand::AmericanExpress)
+        );
+        assert_eq!(
+            ValidationMethods::card_brand("6011111111111117"),
+            Some(CardBrand::Discover)
+        );
+        assert_eq!(
+            ValidationMethods::card_brand("30569309025904"),
+            Some(CardBrand::DinersClub)
+        );
+        assert_eq!(
+            ValidationMethods::card_brand("3530111333300000"),
+            Some(CardBrand::Jcb)
+        );
+        assert_eq!(
+            ValidationMethods::card_brand("6200000000000005"),
+            Some(CardBrand::UnionPay)
+        );
+        assert_eq!(ValidationMethods::card_brand("4111111111111112"), None);
+    }
+
+    #[test]
+    fn test_is_uuid() {
+        assert!(ValidationMethods::is_uuid(
+            "123e4567-e89b-12d3-a456-426614174000"
+        ));
         assert!(!ValidationMethods::is_uuid(
             "123e4567-e89b-12d3-a456-42661417400"
         ));
+        assert!(!ValidationMethods::is_uuid(
+            "123e4567-e89b-02d3-a456-426614174000"
+        ));
+        assert!(!ValidationMethods::is_uuid(
+            "123e4567-e89b-12d3-c456-426614174000"
+        ));
+        assert!(ValidationMethods::is_uuid(
+            "00000000-0000-0000-0000-000000000000"
+        ));
+        assert!(ValidationMethods::is_uuid(
+            "FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF"
+        ));
+    }
+
+    #[test]
+    fn test_uuid_version() {
+        let v4 = "f47ac10b-58cc-4372-A567-0e02b2c3d479";
+        assert_eq!(ValidationMethods::uuid_version(v4), Some(4));
+        assert!(ValidationMethods::is_uuid_version(v4, 4));
+        assert!(!ValidationMethods::is_uuid_version(v4, 7));
+        assert_eq!(
+            ValidationMethods::uuid_version("00000000-0000-0000-0000-000000000000"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_ip_address() {
+        assert!(ValidationMethods::is_ipv4("192.168.0.1"));
+        assert!(!ValidationMethods::is_ipv4("256.1.1.1"));
+        assert!(!ValidationMethods::is_ipv4("1.2.3"));
+        assert!(ValidationMethods::is_ipv6("2001:db8::1"));
+        assert!(ValidationMethods::is_ipv6("::ffff:192.0.2.1"));
+        assert!(!ValidationMethods::is_ipv6("2001:db8:::1"));
+        assert!(ValidationMethods::is_ip_address("::1"));
+        assert!(ValidationMethods::is_ip_address("10.0.0.1"));
+        assert!(!ValidationMethods::is_ip_address("example.com"));
+    }
+
+    #[test]
+    fn test_is_cidr() {
+        assert!(ValidationMethods::is_cidr("10.0.0.0/8"));
+        assert!(ValidationMethods::is_cidr("192.168.1.0/32"));
+        assert!(ValidationMethods::is_cidr("2001:db8::/128"));
+        assert!(!ValidationMethods::is_cidr("10.0.0.0/33"));
+        assert!(!ValidationMethods::is_cidr("10.0.0.0/08"));
+        assert!(!ValidationMethods::is_cidr("10.0.0.0/"));
+        assert!(!ValidationMethods::is_cidr("10.0.0.0"));
+        assert!(!ValidationMethods::is_cidr("2001:db8::/129"));
+    }
+
+    #[test]
+    fn test_is_hostname() {
+        assert!(ValidationMethods::is_hostname("localhost"));
+        assert!(ValidationMethods::is_hostname("my-host.example.com."));
+        assert!(!ValidationMethods::is_hostname("-host"));
+        assert!(!ValidationMethods::is_hostname("host..example"));
+        assert!(!ValidationMethods::is_hostname("host_name"));
+        assert!(!ValidationMethods::is_hostname(&"a".repeat(64)));
+    }
+
+    #[test]
+    fn test_is_domain() {
+        assert!(ValidationMethods::is_domain("example.com"));
+        assert!(ValidationMethods::is_domain("sub.example.co.uk"));
+        assert!(ValidationMethods::is_domain("example.xn--p1ai"));
+        assert!(!ValidationMethods::is_domain("localhost"));
+        assert!(!ValidationMethods::is_domain("example.c"));
+        assert!(!ValidationMethods::is_domain("192.168.0.1"));
+    }
+
+    #[test]
+    fn test_is_iban() {
+        assert!(ValidationMethods::is_iban("DE89 3704 0044 0532 0130 00"));
+        assert!(ValidationMethods::is_iban("GB82WEST12345698765432"));
+        assert!(ValidationMethods::is_iban("gb82west12345698765432"));
+        assert!(!ValidationMethods::is_iban("GB82WEST12345698765433"));
+        assert!(!ValidationMethods::is_iban("DE89370400440532013"));
+        assert!(!ValidationMethods::is_iban("XX89370400440532013000"));
+    }
+
+    #[test]
+    fn test_is_isbn() {
+        assert!(ValidationMethods::is_isbn10("0-306-40615-2"));
+        assert!(ValidationMethods::is_isbn10("080442957X"));
+        assert!(!ValidationMethods::is_isbn10("0-306-40615-3"));
+        assert!(!ValidationMethods::is_isbn10("0--306-40615-2"));
+        assert!(ValidationMethods::is_isbn13("978-0-306-40615-7"));
+        assert!(!ValidationMethods::is_isbn13("978-0-306-40615-8"));
+        assert!(!ValidationMethods::is_isbn13("123-0-306-40615-7"));
+        assert!(ValidationMethods::is_isbn("9780306406157"));
+        assert!(ValidationMethods::is_isbn("0306406152"));
+    }
+
+    #[test]
+    fn test_is_semver() {
+        assert!(ValidationMethods::is_semver("1.2.3"));
+        assert!(ValidationMethods::is_semver("1.0.0-alpha.1+build.5"));
+        assert!(!ValidationMethods::is_semver("1.2"));
+        assert!(!ValidationMethods::is_semver("01.2.3"));
+        assert!(!ValidationMethods::is_semver("1.2.3-"));
+    }
+
+    #[test]
+    fn test_is_hex_color() {
+        assert!(ValidationMethods::is_hex_color("#fff"));
+        assert!(ValidationMethods::is_hex_color("#FFCC00"));
+        assert!(ValidationMethods::is_hex_color("#ffcc0080"));
+        assert!(!ValidationMethods::is_hex_color("ffcc00"));
+        assert!(!ValidationMethods::is_hex_color("#ffcc0"));
+        assert!(!ValidationMethods::is_hex_color("#ggg"));
+    }
+
+    #[test]
+    fn test_is_slug() {
+        assert!(ValidationMethods::is_slug("my-first-post"));
+        assert!(ValidationMethods::is_slug("post2"));
+        assert!(!ValidationMethods::is_slug("My-Post"));
+        assert!(!ValidationMethods::is_slug("my--post"));
+        assert!(!ValidationMethods::is_slug("-post"));
+    }
+
+    #[test]
+    fn test_is_ascii_and_alphanumeric() {
+        assert!(ValidationMethods::is_ascii("Hello, world!"));
+        assert!(!ValidationMethods::is_ascii("Grüße"));
+        assert!(ValidationMethods::is_alphanumeric("abc123"));
+        assert!(!ValidationMethods::is_alphanumeric("abc 123"));
+    }
+
+    #[test]
+    fn test_is_base64() {
+        assert!(ValidationMethods::is_base64("aGVsbG8="));
+        assert!(ValidationMethods::is_base64("aGVsbG8gd29ybGQ="));
+        assert!(ValidationMethods::is_base64("YWJj"));
+        assert!(!ValidationMethods::is_base64("aGVsbG8"));
+        assert!(!ValidationMethods::is_base64("aGV=sbG8"));
+    }
+
+    #[test]
+    fn test_is_json() {
+        assert!(ValidationMethods::is_json(r#"{"a": [1, 2.5, "x", null]}"#));
+        assert!(ValidationMethods::is_json("true"));
+        assert!(!ValidationMethods::is_json("{a: 1}"));
+        assert!(!ValidationMethods::is_json("[1, 2"));
+    }
+
+    #[test]
+    fn test_is_iso8601_duration() {
+        assert!(ValidationMethods::is_iso8601_duration("P3Y6M4DT12H30M5S"));
+        assert!(ValidationMethods::is_iso8601_duration("PT0.5S"));
+        assert!(ValidationMethods::is_iso8601_duration("P2W"));
+        assert!(!ValidationMethods::is_iso8601_duration("P"));
+        assert!(!ValidationMethods::is_iso8601_duration("P1DT"));
+        assert!(!ValidationMethods::is_iso8601_duration("PT1.5H"));
+        assert!(!ValidationMethods::is_iso8601_duration("3Y"));
+    }
+
+    #[test]
+    fn test_is_lat_long() {
+        assert!(ValidationMethods::is_latitude("-90"));
+        assert!(!ValidationMethods::is_latitude("90.0001"));
+        assert!(ValidationMethods::is_longitude("180"));
+        assert!(!ValidationMethods::is_longitude("inf"));
+        assert!(ValidationMethods::is_lat_long("52.52, 13.405"));
+        assert!(ValidationMethods::is_lat_long("-33.8688,151.2093"));
+        assert!(!ValidationMethods::is_lat_long("13.405, 190"));
+        assert!(!ValidationMethods::is_lat_long("52.52"));
+    }
+
+    #[test]
+    fn test_named_patterns() {
+        let patterns = Patterns::new()
+            .register("order_id", r"^ORD-\d{6}$")
+            .unwrap();
+        assert!(patterns.contains("order_id"));
+        assert_eq!(patterns.matches("order_id", "ORD-123456"), Ok(true));
+        assert_eq!(patterns.matches("order_id", "ORD-12"), Ok(false));
+        assert!(patterns.matches("missing", "x").is_err());
+        assert!(patterns.clone().register("invalid", "(").is_err());
+        assert!(!Patterns::new().contains("order_id"));
+
+        let validator = Validator::new(vec![(ValidationMethods::not_empty, Some("Required"))])
+            .pattern(&patterns, "order_id", Some("Invalid order id"))
+            .unwrap();
+        assert_eq!(validator.validate(""), Err("Required".to_string()));
+        assert_eq!(
+            validator.validate("ORD-1"),
+            Err("Invalid order id".to_string())
+        );
+        assert!(validator.validate("ORD-000001").is_ok());
+        assert_eq!(validator.pattern_regexes(), vec![r"^ORD-\d{6}$"]);
+
+        let replaced = patterns.register("order_id", r"^\d+$").unwrap();
+        assert!(validator.validate("ORD-000001").is_ok());
+        assert!(
+            Validator::new(Vec::new())
+                .pattern(&replaced, "order_id", None)
+                .unwrap()
+                .validate("ORD-000001")
+                .is_err()
+        );
+        assert!(
+            Validator::new(Vec::new())
+                .pattern(&replaced, "unknown", None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_local_regexes() {
+        let validator = Validator::new(Vec::new())
+            .regex(r"^[A-Z]{3}$", Some("Three capitals"))
+            .unwrap()
+            .regex(r"^A", None)
+            .unwrap();
+        assert!(validator.validate("ABC").is_ok());
+        assert_eq!(validator.validate("AB"), Err("Three capitals".to_string()));
+        assert_eq!(validator.validate("BCD"), Err(DEFAULT_MESSAGE.to_string()));
+        assert_eq!(
+            validator.requirements(),
+            vec!["Three capitals", "Must match '^A'"]
+        );
+        assert_eq!(validator.pattern_regexes(), vec![r"^[A-Z]{3}$", "^A"]);
+        assert!(Validator::new(Vec::new()).regex("(", None).is_err());
+    }
+
+    fn is_disposable(value: &str) -> bool {
+        value.ends_with("@mailinator.com")
+    }
+
+    #[test]
+    fn test_any_and_not() {
+        let contact = Validator::any(vec![
+            Validator::rule(
+                ValidationMethods::is_phone_number,
+                Some("Invalid phone number"),
+            ),
+            Validator::rule(ValidationMethods::validate_email, Some("Invalid email")),
+        ])
+        .and(Validator::not(
+            Validator::rule(is_disposable, None),
+            Some("Disposable addresses are not allowed"),
+        ));
+        assert!(contact.validate("+1234567890").is_ok());
+        assert!(contact.validate("jane@example.com").is_ok());
+        assert_eq!(
+            contact.validate("jane"),
+            Err("Invalid phone number or Invalid email".to_string())
+        );
+        assert_eq!(
+            contact.validate("jane@mailinator.com"),
+            Err("Disposable addresses are not allowed".to_string())
+        );
+        assert!(Validator::any(Vec::new()).validate("x").is_err());
+    }
+
+    #[test]
+    fn test_when_nonempty() {
+        let website = Validator::when_nonempty(Validator::rule(
+            ValidationMethods::is_url,
+            Some("Invalid URL"),
+        ));
+        assert!(website.validate("").is_ok());
+        assert!(website.validate("https://example.com").is_ok());
+        assert_eq!(website.validate("example"), Err("Invalid URL".to_string()));
+        assert!(
+            Validator::optional(Validator::rule(ValidationMethods::is_integer, None))
+                .validate("  ")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_each() {
+        let emails = Validator::each(Validator::rule(
+            ValidationMethods::validate_email,
+            Some("Invalid email"),
+        ));
+        assert!(emails.validate("a@example.com, b@example.com").is_ok());
+        assert_eq!(
+            emails.validate("a@example.com, b"),
+            Err("Item 2 ('b'): Invalid email".to_string())
+        );
+        let tags =
+            Validator::each_separated(Validator::rule(ValidationMethods::is_slug, None), ';');
+        assert!(tags.validate("rust;forms").is_ok());
+        assert!(tags.validate("rust;Forms").is_err());
+    }
+
+    #[test]
+    fn test_nested_combinators() {
+        let validator =
+            Validator::rule(ValidationMethods::not_empty, Some("Required")).and(Validator::each(
+                Validator::rule(ValidationMethods::is_ipv4, None)
+                    .or(Validator::rule(ValidationMethods::is_cidr, None))
+                    .with_message("Expected an IPv4 address or network"),
+            ));
+        assert_eq!(validator.validate(""), Err("Required".to_string()));
+        assert!(validator.validate("10.0.0.1, 192.168.0.0/16").is_ok());
+        assert_eq!(
+            validator.validate("10.0.0.1, nope"),
+            Err("Item 2 ('nope'): Expected an IPv4 address or network".to_string())
+        );
+        assert_eq!(
+            validator.with_message("Invalid list").validate(""),
+            Err("Invalid list".to_string())
+        );
+    }
+
+    #[test]
+    fn test_named_rules() {
+        assert_eq!(NamedRule::Email.name(), "validate_email");
+        assert_eq!(
+            NamedRule::Email.requirement(),
+            "Must be a valid email address"
+        );
+        assert_eq!(NamedRule::from_name("is_iban"), Some(NamedRule::Iban));
+        assert!(NamedRule::from_name("is_disposable").is_none());
+        assert_eq!(NamedRule::ALL.len(), 35);
+        assert!(
+            NamedRule::ALL
+                .iter()
+                .all(|rule| NamedRule::from_name(rule.name()) == Some(*rule))
+        );
+
+        let rule = NamedRule::from_name("validate_email").unwrap().function();
+        assert!(rule("jane@example.com"));
+        assert!(!rule("jane"));
+        assert_eq!(NamedRule::from_format("uri"), Some(NamedRule::Url));
+        assert_eq!(NamedRule::Latitude.range(), Some((-90.0, 90.0)));
+    }
+
+    #[test]
+    fn test_requirements() {
+        let validator = Validator::new(vec![
+            (is_disposable, Some("Disposable addresses are not allowed")),
+            (is_disposable, None),
+        ])
+        .builtin(NamedRule::NotEmpty, Some("Email cannot be empty"))
+        .and(Validator::when_nonempty(
+            Validator::named(NamedRule::PhoneNumber, None)
+                .or(Validator::named(NamedRule::Email, None)),
+        ));
+        assert_eq!(
+            validator.requirements(),
+            vec![
+                "Disposable addresses are not allowed".to_string(),
+                "Must not be empty".to_string(),
+                "One of: Must be a phone number in international format; Must be a valid email address (if provided)".to_string(),
+            ]
+        );
+        assert_eq!(
+            Validator::each(Validator::named(NamedRule::Slug, None))
+                .with_message("Tags must be slugs")
+                .requirements(),
+            vec!["Tags must be slugs".to_string()]
+        );
     }
 }
@@ -0,0 +1,431 @@
+use crate::json;
+use crate::optional::Optional;
+use std::any::{Any, TypeId};
+use std::fmt::{self, Debug, Display};
+
+/// A dynamically typed value of a form field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A text value.
+    String(String),
+    /// An integer value.
+    Integer(i64),
+    /// A floating-point value.
+    Float(f64),
+    /// A boolean value.
+    Bool(bool),
+    /// A single character.
+    Char(char),
+    /// A list of values, such as the selections of a multiselect field.
+    List(Vec<Value>),
+    /// A record of named values, such as a section or an entry of a repeatable group.
+    Record(ValueMap),
+    /// The absence of a value.
+    Null,
+}
+
+/// Converts a value of one of the listed types to a `Value`, returning early on a match.
+macro_rules! convert_as {
+    ($value:expr, $variant:ident, $($ty:ty),*) => {
+        $(
+            if let Some(value) = $value.downcast_ref::<$ty>() {
+                return Some(Value::$variant((*value).into()));
+            }
+        )*
+    };
+}
+
+/// Converts an integer of one of the listed types to a `Value::Integer`, or to a
+/// `Value::String` if it does not fit into an `i64`, returning early on a match.
+macro_rules! convert_wide_as {
+    ($value:expr, $($ty:ty),*) => {
+        $(
+            if let Some(value) = $value.downcast_ref::<$ty>() {
+                return Some(
+                    i64::try_from(*value)
+                        .map_or_else(|_| Value::String(value.to_string()), Value::Integer),
+                );
+            }
+        )*
+    };
+}
+
+/// Converts an `Optional` of one of the listed types to a `Value`, returning early on a match.
+macro_rules! convert_optional_as {
+    ($value:expr, $($ty:ty),*) => {
+        $(
+            if let Some(value) = $value.downcast_ref::<Optional<$ty>>() {
+                return Some(match value {
+                    Optional::Some(value) => Value::of(value),
+                    Optional::None => Value::Null,
+                });
+            }
+            if let Some(value) = $value.downcast_ref::<Option<$ty>>() {
+                return Some(value.as_ref().map_or(Value::Null, Value::of));
+            }
+        )*
+    };
+}
+
+impl Value {
+    /// Converts a value to a `Value`.
+    ///
+    /// Strings, characters, booleans, integers, floats and optional values of
+    /// these types are converted to the matching variant, and a `Value` is
+    /// cloned. Other types are converted to their `Debug` representation.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The value to be converted.
+    ///
+    /// # Returns
+    ///
+    /// * The converted `Value`.
+    pub fn of<T>(value: &T) -> Value
+    where
+        T: 'static + Debug,
+    {
+        Self::from_any(value).unwrap_or_else(|| Value::String(format!("{:?}", value)))
+    }
+
+    /// Converts a value of a known type to a `Value`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Value)` if the type of the value is supported, `None` otherwise.
+    fn from_any(value: &dyn Any) -> Option<Value> {
+        if let Some(value) = value.downcast_ref::<Value>() {
+            return Some(value.clone());
+        }
+        if let Some(value) = value.downcast_ref::<String>() {
+            return Some(Value::String(value.clone()));
+        }
+        if let Some(value) = value.downcast_ref::<&str>() {
+            return Some(Value::String(value.to_string()));
+        }
+        convert_as!(value, Integer, i8, i16, i32, i64, u8, u16, u32);
+        convert_as!(value, Float, f32, f64);
+        convert_as!(value, Bool, bool);
+        convert_as!(value, Char, char);
+        convert_wide_as!(value, isize, u64, usize, i128, u128);
+        convert_optional_as!(
+            value, String, char, bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128,
+            usize, f32, f64
+        );
+        None
+    }
+
+    /// Checks whether the value is `Value::Null`.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the value is `Value::Null`, `false` otherwise.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Writes the value as compact JSON text.
+    ///
+    /// Records become objects with their entries in order, characters become
+    /// strings and non-finite floats become `null`.
+    ///
+    /// # Returns
+    ///
+    /// * The JSON text.
+    pub fn to_json(&self) -> String {
+        json::write(self)
+    }
+
+    /// Parses a JSON text into a `Value`.
+    ///
+    /// Objects become `Value::Record`, arrays `Value::List`, and numbers
+    /// without a fraction or exponent that fit into an `i64` `Value::Integer`.
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The JSON text.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Value)` with the parsed value.
+    /// * `Err(String)` if the text is not valid JSON.
+    pub fn from_json(text: &str) -> Result<Value, String> {
+        json::parse(text)
+    }
+}
+
+impl Display for Value {
+    /// Formats the value as plain text, with lists and records in brackets.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(value) => write!(f, "{}", value),
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", value),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Char(value) => write!(f, "{}", value),
+            Value::List(values) => {
+                let values: Vec<String> = values.iter().map(Value::to_string).collect();
+                write!(f, "[{}]", values.join(", "))
+            }
+            Value::Record(values) => {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value))
+                    .collect();
+                write!(f, "{{{}}}", values.join(", "))
+            }
+            Value::Null => write!(f, "null"),
+        }
+    }
+}
+
+/// The kind of value entered into an input field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// A text value.
+    String,
+    /// An integer value.
+    Integer,
+    /// A floating-point value.
+    Float,
+    /// A boolean value.
+    Bool,
+    /// A single character.
+    Char,
+    /// A value of any other type.
+    Other,
+}
+
+/// Checks whether `id` is the `TypeId` of one of the listed types or of an optional value of them.
+macro_rules! is_one_of {
+    ($id:expr, $($ty:ty),*) => {
+        false $(
+            || $id == TypeId::of::<$ty>()
+            || $id == TypeId::of::<Optional<$ty>>()
+            || $id == TypeId::of::<Option<$ty>>()
+        )*
+    };
+}
+
+impl ValueKind {
+    /// Determines the kind of values of type `T`.
+    ///
+    /// Optional values have the kind of the value they contain.
+    ///
+    /// # Returns
+    ///
+    /// * The `ValueKind` of `T`.
+    pub fn of<T: 'static>() -> ValueKind {
+        let id = TypeId::of::<T>();
+        if is_one_of!(id, String) {
+            ValueKind::String
+        } else if is_one_of!(
+            id, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+        ) {
+            ValueKind::Integer
+        } else if is_one_of!(id, f32, f64) {
+            ValueKind::Float
+        } else if is_one_of!(id, bool) {
+            ValueKind::Bool
+        } else if is_one_of!(id, char) {
+            ValueKind::Char
+        } else {
+            ValueKind::Other
+        }
+    }
+}
+
+/// The kind of a form field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A text input field with the kind of value it parses.
+    Input(ValueKind),
+    /// A select field.
+    Select,
+    /// A multiselect field.
+    Multiselect,
+    /// A section containing a sub-form.
+    Section,
+    /// A repeatable group of fields.
+    Repeat,
+}
+
+/// An ordered map of field names to values.
+///
+/// Entries keep the order in which they were inserted, which is the order of
+/// the fields in the form.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValueMap {
+    /// The entries of the map.
+    entries: Vec<(String, Value)>,
+}
+
+impl ValueMap {
+    /// Creates a new, empty `ValueMap`.
+    ///
+    /// # Returns
+    ///
+    /// * A new `ValueMap` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value, replacing the value of an existing entry with the same name.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the field.
+    /// * `value` - The value of the field.
+    pub fn insert(&mut self, name: &str, value: Value) {
+        match self.entries.iter_mut().find(|(key, _)| key == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((name.to_string(), value)),
+        }
+    }
+
+    /// Gets the value of a field by its name.
+    ///
+    /// Names containing dots are resolved through records, so `address.city`
+    /// refers to the `city` entry of the `address` record.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name or dotted path of the field.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Value)` if the field is found, `None` otherwise.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        if let Some((_, value)) = self.entries.iter().find(|(key, _)| key == name) {
+            return Some(value);
+        }
+        let (record, rest) = name.split_once('.')?;
+        match self.get(record)? {
+            Value::Record(values) => values.get(rest),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the names and values, in field order.
+    ///
+    /// # Returns
+    ///
+    /// * An iterator of `(&str, &Value)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.entries
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Returns the number of entries.
+    ///
+    /// # Returns
+    ///
+    /// * The number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether the map has no entries.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the map is empty, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl IntoIterator for ValueMap {
+    type Item = (String, Value);
+    type IntoIter = std::vec::IntoIter<(String, Value)>;
+
+    /// Consumes the map, returning its entries in field order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_of() {
+        assert_eq!(
+            Value::of(&"John".to_string()),
+            Value::String("John".to_string())
+        );
+        assert_eq!(Value::of(&42u32), Value::Integer(42));
+        assert_eq!(Value::of(&-7i64), Value::Integer(-7));
+        assert_eq!(Value::of(&1.5f64), Value::Float(1.5));
+        assert_eq!(Value::of(&true), Value::Bool(true));
+        assert_eq!(Value::of(&'x'), Value::Char('x'));
+        assert_eq!(Value::of(&Optional::Some(3u8)), Value::Integer(3));
+        assert_eq!(Value::of(&Optional::<u8>::None), Value::Null);
+        assert_eq!(Value::of(&Some(2.0f32)), Value::Float(2.0));
+        assert_eq!(Value::of(&Value::Integer(5)), Value::Integer(5));
+        assert_eq!(Value::of(&u64::MAX), Value::String(u64::MAX.to_string()));
+    }
+
+    #[test]
+    fn test_value_of_other() {
+        #[derive(Debug)]
+        enum Size {
+            Large,
+        }
+        assert_eq!(Value::of(&Size::Large), Value::String("Large".to_string()));
+    }
+
+    #[test]
+    fn test_value_kind_of() {
+        assert_eq!(ValueKind::of::<String>(), ValueKind::String);
+        assert_eq!(ValueKind::of::<u16>(), ValueKind::Integer);
+        assert_eq!(ValueKind::of::<Optional<f64>>(), ValueKind::Float);
+        assert_eq!(ValueKind::of::<Option<bool>>(), ValueKind::Bool);
+        assert_eq!(ValueKind::of::<char>(), ValueKind::Char);
+        assert_eq!(ValueKind::of::<Vec<u8>>(), ValueKind::Other);
+    }
+
+    #[test]
+    fn test_value_display() {
+        let mut record = ValueMap::new();
+        record.insert("city", Value::String("Berlin".to_string()));
+        let value = Value::List(vec![Value::Integer(1), Value::Record(record), Value::Null]);
+        assert_eq!(value.to_string(), "[1, {city: Berlin}, null]");
+    }
+
+    #[test]
+    fn test_value_json() {
+        let mut record = ValueMap::new();
+        record.insert("initial", Value::Char('J'));
+        record.insert("tags", Value::List(vec![Value::Integer(1), Value::Null]));
+        let value = Value::Record(record);
+        assert_eq!(value.to_json(), r#"{"initial":"J","tags":[1,null]}"#);
+        assert_eq!(
+            Value::from_json(r#"{"tags":[1,null]}"#).map(|value| value.to_json()),
+            Ok(r#"{"tags":[1,null]}"#.to_string())
+        );
+        assert!(Value::from_json("{").is_err());
+    }
+
+    #[test]
+    fn test_value_map() {
+        let mut address = ValueMap::new();
+        address.insert("city", Value::String("Berlin".to_string()));
+        let mut values = ValueMap::new();
+        values.insert("name", Value::String("John".to_string()));
+        values.insert("address", Value::Record(address));
+        values.insert("name", Value::String("Jane".to_string()));
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values.get("name"), Some(&Value::String("Jane".to_string())));
+        assert_eq!(
+            values.get("address.city"),
+            Some(&Value::String("Berlin".to_string()))
+        );
+        assert_eq!(values.get("name.city"), None);
+        let names: Vec<&str> = values.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["name", "address"]);
+    }
+}